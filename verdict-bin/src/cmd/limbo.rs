@@ -9,7 +9,7 @@ use csv::WriterBuilder;
 use limbo_harness_support::models::{ExpectedResult, Limbo, PeerKind, Testcase, ValidationKind};
 use serde::{Deserialize, Serialize};
 use tempfile::NamedTempFile;
-use verdict::Task;
+use verdict::{PeerName, Task};
 
 use crate::error::*;
 use crate::harness::*;
@@ -55,6 +55,10 @@ pub struct LimboResult {
     pub expected: bool,
     pub valid: bool,
     pub err_msg: String,
+
+    /// Set if the harness cannot express the expected peer name
+    /// (e.g., IP or email peers through the common bench protocol)
+    pub skipped: bool,
 }
 
 /// Strip -----BEGIN CERTIFICATE----- and -----END CERTIFICATE-----
@@ -75,6 +79,38 @@ fn test_limbo(
     harness: &Box<dyn Harness>,
     testcase: &Testcase,
 ) -> Result<LimboResult, Error> {
+    let peer = match &testcase.expected_peer_name {
+        Some(peer_name) if !args.no_domain => Some(match peer_name.kind {
+            PeerKind::Dns => PeerName::Dns(peer_name.value.clone()),
+            PeerKind::Rfc822 => PeerName::Email(peer_name.value.clone()),
+            PeerKind::Ip => match peer_name.value.parse() {
+                Ok(addr) => PeerName::Ip(addr),
+                Err(..) => {
+                    return Ok(LimboResult {
+                        id: testcase.id.to_string(),
+                        expected: testcase.expected_result == ExpectedResult::Success,
+                        valid: false,
+                        err_msg: format!("invalid IP address {}", peer_name.value),
+                        skipped: false,
+                    })
+                }
+            },
+        }),
+        _ => None,
+    };
+
+    if let Some(peer) = &peer {
+        if !harness.supports_peer(peer) {
+            return Ok(LimboResult {
+                id: testcase.id.to_string(),
+                expected: testcase.expected_result == ExpectedResult::Success,
+                valid: false,
+                err_msg: "unsupported peer name".to_string(),
+                skipped: true,
+            });
+        }
+    }
+
     let tmp_root_file = NamedTempFile::with_suffix(".pem")?;
     let tmp_root_path = tmp_root_file
         .path()
@@ -100,15 +136,7 @@ fn test_limbo(
             .push(strip_pem(interm).ok_or(Error::LimboError("failed to process PEM".to_string()))?);
     }
 
-    let task = if let Some(peer_name) = &testcase.expected_peer_name {
-        if args.no_domain {
-            Task::new_server_auth(None, timestamp)
-        } else {
-            Task::new_server_auth(Some(&peer_name.value), timestamp)
-        }
-    } else {
-        Task::new_server_auth(None, timestamp)
-    };
+    let task = Task::new_server_auth_peer(peer, timestamp);
 
     let (valid, err_msg) = match instance.validate(&bundle, &task, args.repeat) {
         Ok(res) => (res.valid, res.err),
@@ -120,6 +148,7 @@ fn test_limbo(
         expected: testcase.expected_result == ExpectedResult::Success,
         valid,
         err_msg,
+        skipped: false,
     })
 }
 
@@ -145,11 +174,16 @@ fn reducer(rx_res: Receiver<LimboResult>) -> Result<(), Error> {
 
     let mut total = 0;
     let mut conformant = 0;
+    let mut skipped = 0;
 
     while let Ok(res) = rx_res.recv() {
-        total += 1;
-        if res.expected == res.valid {
-            conformant += 1;
+        if res.skipped {
+            skipped += 1;
+        } else {
+            total += 1;
+            if res.expected == res.valid {
+                conformant += 1;
+            }
         }
 
         output_writer.serialize(res)?;
@@ -157,10 +191,11 @@ fn reducer(rx_res: Receiver<LimboResult>) -> Result<(), Error> {
     }
 
     eprintln!(
-        "{}/{} conformant ({} errors)",
+        "{}/{} conformant ({} errors, {} skipped)",
         conformant,
         total,
-        total - conformant
+        total - conformant,
+        skipped
     );
 
     Ok(())
@@ -170,11 +205,10 @@ pub fn main(args: Args) -> Result<(), Error> {
     let limbo: Limbo = serde_json::from_reader(BufReader::new(File::open(&args.path)?))?;
     eprintln!("loaded {} testcases", limbo.testcases.len());
 
-    // Only perform server authentication and DNS name validation (if enabled)
+    // Only perform server authentication and peer name validation (if enabled)
     let filter = |t: &&Testcase| {
         t.validation_kind == ValidationKind::Server
-            && (t.expected_peer_name.is_some()
-                && t.expected_peer_name.as_ref().unwrap().kind == PeerKind::Dns)
+            && t.expected_peer_name.is_some()
             && if let Some(id) = &args.test_id {
                 &t.id.to_string() == id
             } else {
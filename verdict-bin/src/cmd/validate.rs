@@ -1,5 +1,5 @@
 use clap::Parser;
-use verdict::Task;
+use verdict::{Purpose, Task};
 
 use crate::error::*;
 use crate::harness::*;
@@ -30,6 +30,10 @@ pub struct Args {
     /// Override the current time with the given timestamp
     #[clap(short = 't', long)]
     override_time: Option<i64>,
+
+    /// Print the rejected candidate paths and the failing checks
+    #[arg(long, default_value_t = false)]
+    report: bool,
 }
 
 pub fn main(args: Args) -> Result<(), Error> {
@@ -38,14 +42,19 @@ pub fn main(args: Args) -> Result<(), Error> {
     let harness = VerdictHarness {
         policy: args.policy,
         debug: args.debug,
+        report: args.report,
     };
     let mut instance = harness.spawn(&args.roots, timestamp)?;
 
-    let task = if let Some(domain) = &args.domain {
-        Task::new_server_auth(Some(domain), timestamp)
-    } else {
-        Task::new_server_auth(None, timestamp)
-    };
+    let mut task = Task::builder(Purpose::ServerAuth).timestamp(timestamp);
+    if let Some(domain) = &args.domain {
+        task = task.hostname(domain);
+    }
+    let task = task.build();
+
+    if args.debug {
+        eprintln!("task: {}", task);
+    }
 
     let chain = read_pem_file_as_base64(&args.chain)?;
     let res = instance.validate(&chain, &task, args.repeat.unwrap_or(1))?;
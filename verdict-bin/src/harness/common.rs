@@ -2,7 +2,7 @@ use std::io::{BufRead, BufReader, Write};
 use std::process::{Child, ChildStdin, ChildStdout};
 
 use clap::{Parser, ValueEnum};
-use verdict::Task;
+use verdict::{PeerName, Task};
 
 use super::armor::*;
 use super::ceres::*;
@@ -26,6 +26,12 @@ pub struct ValidationResult {
 /// to set the time, which is only doable at the beginning
 pub trait Harness {
     fn spawn(&self, roots_path: &str, timestamp: u64) -> Result<Box<dyn Instance>, Error>;
+
+    /// Whether the harness can validate for the given kind of peer name;
+    /// by default, only DNS names, as in the common bench protocol
+    fn supports_peer(&self, peer: &PeerName) -> bool {
+        matches!(peer, PeerName::Dns(..))
+    }
 }
 
 pub trait Instance: Send {
@@ -75,8 +81,8 @@ impl Instance for CommonBenchInstance {
             return Err(Error::ZeroRepeat);
         }
 
-        let task_str = match task.hostname() {
-            Some(hostname) => {
+        let task_str = match task.peer_name() {
+            Some(PeerName::Dns(hostname)) => {
                 if hostname.trim().is_empty() {
                     // Abort if the domain is empty
                     return Ok(ValidationResult {
@@ -87,6 +93,15 @@ impl Instance for CommonBenchInstance {
                 }
                 format!("domain: {}", hostname)
             }
+            Some(..) => {
+                // The bench protocol only supports DNS names
+                // (see `Harness::supports_peer`)
+                return Ok(ValidationResult {
+                    valid: false,
+                    err: "unsupported peer name".to_string(),
+                    stats: vec![0; repeat],
+                });
+            }
             None => "validate".to_string(),
         };
 
@@ -312,16 +327,19 @@ pub fn get_harness_from_args(args: &HarnessArgs, debug: bool) -> Result<Box<dyn
         HarnessName::VerdictChrome => Box::new(VerdictHarness {
             policy: VerdictPolicyName::Chrome,
             debug,
+            report: false,
         }),
 
         HarnessName::VerdictFirefox => Box::new(VerdictHarness {
             policy: VerdictPolicyName::Firefox,
             debug,
+            report: false,
         }),
 
         HarnessName::VerdictOpenSSL => Box::new(VerdictHarness {
             policy: VerdictPolicyName::OpenSSL,
             debug,
+            report: false,
         }),
     })
 }
@@ -5,7 +5,9 @@ use std::time::Instant;
 
 use clap::ValueEnum;
 
-use verdict::{ChromePolicy, FirefoxPolicy, OpenSSLPolicy, Policy, RootStore, Task, Validator};
+use verdict::{
+    ChromePolicy, FirefoxPolicy, OpenSSLPolicy, PeerName, Policy, RootStore, Task, Validator,
+};
 
 use crossbeam::channel;
 use crossbeam::channel::Receiver;
@@ -27,6 +29,9 @@ pub enum VerdictPolicyName {
 pub struct VerdictHarness {
     pub policy: VerdictPolicyName,
     pub debug: bool,
+
+    /// Also print why each candidate path is rejected
+    pub report: bool,
 }
 
 struct Job {
@@ -35,6 +40,12 @@ struct Job {
     repeat: usize,
 }
 
+/// A worker thread with its own [`Validator`], receiving jobs over a channel.
+///
+/// This does not use [`verdict::BatchValidator`] on purpose: like the other
+/// harnesses (one `cert_bench` process per instance), each instance validates
+/// one job at a time, and the durations are measured around each validation
+/// on the worker, which a shared pool would mix with queueing time.
 pub struct VerdictInstance {
     tx_job: Option<Sender<Job>>,
     rx_res: Option<Receiver<ValidationResult>>,
@@ -49,6 +60,7 @@ impl VerdictInstance {
         rx_job: Receiver<Job>,
         tx_res: Sender<ValidationResult>,
         debug: bool,
+        report: bool,
     ) -> Result<(), Error> {
         let store = RootStore::from_base64(roots_base64.iter())?;
         let validator = Validator::from_roots(policy, &store)?;
@@ -74,9 +86,17 @@ impl VerdictInstance {
                 return Err(Error::Inconsistentimestamps);
             }
 
+            let mut last_report = None;
+
             for _ in 0..repeat {
                 let start = Instant::now();
-                res = validator.validate_base64(bundle_bytes.iter(), &task);
+                res = if report {
+                    validator
+                        .validate_base64_with_report(bundle_bytes.iter(), &task)
+                        .map(|report| last_report.insert(report).valid)
+                } else {
+                    validator.validate_base64(bundle_bytes.iter(), &task)
+                };
                 durations.push(
                     start
                         .elapsed()
@@ -86,6 +106,10 @@ impl VerdictInstance {
                 );
             }
 
+            if let Some(report) = &last_report {
+                eprint!("report: {}", report);
+            }
+
             tx_res.send(ValidationResult {
                 valid: match res {
                     Ok(true) => true,
@@ -115,6 +139,7 @@ impl Harness for VerdictHarness {
 
         let policy_name = self.policy;
         let debug = self.debug;
+        let report = self.report;
 
         Ok(Box::new(VerdictInstance {
             tx_job: Some(tx_job),
@@ -127,6 +152,7 @@ impl Harness for VerdictHarness {
                     rx_job,
                     tx_res,
                     debug,
+                    report,
                 ),
                 VerdictPolicyName::Firefox => VerdictInstance::worker(
                     timestamp,
@@ -135,6 +161,7 @@ impl Harness for VerdictHarness {
                     rx_job,
                     tx_res,
                     debug,
+                    report,
                 ),
                 VerdictPolicyName::OpenSSL => VerdictInstance::worker(
                     timestamp,
@@ -143,10 +170,16 @@ impl Harness for VerdictHarness {
                     rx_job,
                     tx_res,
                     debug,
+                    report,
                 ),
             })),
         }))
     }
+
+    fn supports_peer(&self, _peer: &PeerName) -> bool {
+        // Verdict validates IP and email peers natively
+        true
+    }
 }
 
 impl Instance for VerdictInstance {
@@ -596,6 +596,14 @@ macro_rules! tag_of {
         }
     };
 
+    (ENUMERATED) => {
+        TagValue {
+            class: TagClass::Universal,
+            form: TagForm::Primitive,
+            num: 0x0a,
+        }
+    };
+
     (SEQUENCE) => {
         TagValue {
             class: TagClass::Universal,
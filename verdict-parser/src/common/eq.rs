@@ -115,6 +115,17 @@ impl<U: PolyfillEq, V: PolyfillEq> PolyfillEq for Either<U, V> {
     }
 }
 
+impl<T: PolyfillEq> PolyfillEq for OptionDeep<T> {
+    #[inline(always)]
+    fn polyfill_eq(&self, other: &Self) -> (res: bool) {
+        match (self, other) {
+            (OptionDeep::Some(a), OptionDeep::Some(b)) => a.polyfill_eq(b),
+            (OptionDeep::None, OptionDeep::None) => true,
+            _ => false,
+        }
+    }
+}
+
 impl PolyfillEq for NullValue {
     #[inline(always)]
     fn polyfill_eq(&self, _other: &Self) -> (res: bool) {
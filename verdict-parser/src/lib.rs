@@ -97,6 +97,148 @@ verus! {
         Ok(cert)
     }
 
+    /// Top-level specification for CRL (RFC 5280, Section 5) parsing from DER
+    pub closed spec fn spec_parse_crl_der(der: Seq<u8>) -> Option<x509::SpecCertificateListValue> {
+        match x509::CertificateList.spec_parse(der) {
+            Ok((n, crl)) if n == der.len() => Some(crl),
+            _ => None,
+        }
+    }
+
+    /// Parses the given bytes in ASN.1 DER format
+    /// to a [`x509::CertificateListValue`].
+    ///
+    /// Same guarantees as [`parse_x509_der`].
+    ///
+    /// NOTE: This is an internal function that is subject to change.
+    pub fn parse_crl_der<'a>(bytes: &'a [u8]) -> (res: Result<x509::CertificateListValue<'a>, ParseError>)
+        ensures
+            res matches Ok(res) ==> {
+                // Soundness
+                &&& spec_parse_crl_der(bytes@) == Some(res@)
+
+                // Non-malleability
+                &&& forall |other: Seq<u8>| {
+                    &&& other.len() <= usize::MAX
+                    &&& #[trigger] spec_parse_crl_der(other) == Some(res@)
+                } ==> other == bytes@
+
+                // Prefix-security
+                &&& forall |suffix: Seq<u8>| {
+                    &&& suffix.len() != 0
+                    &&& bytes@.len() + suffix.len() <= usize::MAX
+                } ==> #[trigger] spec_parse_crl_der(bytes@ + suffix) is None
+            },
+
+            // Completeness
+            res is Err ==> spec_parse_crl_der(bytes@) is None,
+    {
+        let (n, crl) = x509::CertificateList.parse(bytes)?;
+        if n != bytes.len() {
+            return Err(ParseError::Other("trailing bytes in CRL".to_string()));
+        }
+
+        proof {
+            let (n, spec_res) = x509::CertificateList.spec_parse(bytes@).unwrap();
+
+            assert forall |other: Seq<u8>| {
+                &&& other.len() <= usize::MAX
+                &&& #[trigger] spec_parse_crl_der(other) == Some(crl@)
+            } implies other == bytes@ by {
+                let (m, other_res) = x509::CertificateList.spec_parse(other).unwrap();
+                let other_ser = x509::CertificateList.spec_serialize(other_res).unwrap();
+                let spec_ser = x509::CertificateList.spec_serialize(spec_res).unwrap();
+
+                x509::CertificateList.theorem_parse_serialize_roundtrip(other);
+                x509::CertificateList.theorem_parse_serialize_roundtrip(bytes@);
+
+                assert(other_ser == other);
+                assert(other == spec_ser);
+                assert(spec_ser == bytes@);
+            }
+
+            assert forall |suffix: Seq<u8>|{
+                &&& suffix.len() != 0
+                &&& bytes@.len() + suffix.len() <= usize::MAX
+            } implies #[trigger] spec_parse_crl_der(bytes@ + suffix) is None by {
+                x509::CertificateList.lemma_prefix_secure(bytes@, suffix);
+            }
+        }
+
+        Ok(crl)
+    }
+
+    /// Top-level specification for OCSP response (RFC 6960, Section 4.2.1) parsing from DER
+    pub closed spec fn spec_parse_ocsp_response_der(der: Seq<u8>) -> Option<x509::SpecOCSPResponseValue> {
+        match x509::OCSPResponse.spec_parse(der) {
+            Ok((n, resp)) if n == der.len() => Some(resp),
+            _ => None,
+        }
+    }
+
+    /// Parses the given bytes in ASN.1 DER format
+    /// to a [`x509::OCSPResponseValue`].
+    ///
+    /// Same guarantees as [`parse_x509_der`].
+    ///
+    /// NOTE: This is an internal function that is subject to change.
+    pub fn parse_ocsp_response_der<'a>(bytes: &'a [u8]) -> (res: Result<x509::OCSPResponseValue<'a>, ParseError>)
+        ensures
+            res matches Ok(res) ==> {
+                // Soundness
+                &&& spec_parse_ocsp_response_der(bytes@) == Some(res@)
+
+                // Non-malleability
+                &&& forall |other: Seq<u8>| {
+                    &&& other.len() <= usize::MAX
+                    &&& #[trigger] spec_parse_ocsp_response_der(other) == Some(res@)
+                } ==> other == bytes@
+
+                // Prefix-security
+                &&& forall |suffix: Seq<u8>| {
+                    &&& suffix.len() != 0
+                    &&& bytes@.len() + suffix.len() <= usize::MAX
+                } ==> #[trigger] spec_parse_ocsp_response_der(bytes@ + suffix) is None
+            },
+
+            // Completeness
+            res is Err ==> spec_parse_ocsp_response_der(bytes@) is None,
+    {
+        let (n, resp) = x509::OCSPResponse.parse(bytes)?;
+        if n != bytes.len() {
+            return Err(ParseError::Other("trailing bytes in OCSP response".to_string()));
+        }
+
+        proof {
+            let (n, spec_res) = x509::OCSPResponse.spec_parse(bytes@).unwrap();
+
+            assert forall |other: Seq<u8>| {
+                &&& other.len() <= usize::MAX
+                &&& #[trigger] spec_parse_ocsp_response_der(other) == Some(resp@)
+            } implies other == bytes@ by {
+                let (m, other_res) = x509::OCSPResponse.spec_parse(other).unwrap();
+                let other_ser = x509::OCSPResponse.spec_serialize(other_res).unwrap();
+                let spec_ser = x509::OCSPResponse.spec_serialize(spec_res).unwrap();
+
+                x509::OCSPResponse.theorem_parse_serialize_roundtrip(other);
+                x509::OCSPResponse.theorem_parse_serialize_roundtrip(bytes@);
+
+                assert(other_ser == other);
+                assert(other == spec_ser);
+                assert(spec_ser == bytes@);
+            }
+
+            assert forall |suffix: Seq<u8>|{
+                &&& suffix.len() != 0
+                &&& bytes@.len() + suffix.len() <= usize::MAX
+            } implies #[trigger] spec_parse_ocsp_response_der(bytes@ + suffix) is None by {
+                x509::OCSPResponse.lemma_prefix_secure(bytes@, suffix);
+            }
+        }
+
+        Ok(resp)
+    }
+
     /// Decodes a Base64-encoded sequence of bytes.
     ///
     /// This function has been formally verified for its
@@ -93,4 +93,55 @@ mod test {
             ])
             .is_ok());
     }
+
+    #[test]
+    fn rsassa_pss() {
+        // RSASSA-PSS with SHA-256, MGF1 with SHA-256, and 32-byte salt
+        let (_, alg) = ASN1(AlgorithmIdentifier)
+            .parse(&[
+                0x30, 0x41, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0A, 0x30,
+                0x34, 0xA0, 0x0F, 0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04,
+                0x02, 0x01, 0x05, 0x00, 0xA1, 0x1C, 0x30, 0x1A, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86,
+                0xF7, 0x0D, 0x01, 0x01, 0x08, 0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65,
+                0x03, 0x04, 0x02, 0x01, 0x05, 0x00, 0xA2, 0x03, 0x02, 0x01, 0x20,
+            ])
+            .unwrap();
+
+        match alg.param {
+            AlgorithmParamValue::RSASSAPSS(Either::Left(param)) => {
+                match &param.hash_alg {
+                    OptionDeep::Some(hash_alg) => assert!(hash_alg.id.polyfill_eq(&oid!(HASH_SHA256))),
+                    OptionDeep::None => panic!("expected hash algorithm"),
+                }
+                match &param.mask_gen_alg {
+                    OptionDeep::Some(mgf) => {
+                        assert!(mgf.id.polyfill_eq(&oid!(MGF1)));
+                        assert!(mgf.hash_alg.id.polyfill_eq(&oid!(HASH_SHA256)));
+                    }
+                    OptionDeep::None => panic!("expected mask generation algorithm"),
+                }
+                assert_eq!(param.salt_len, 32);
+                assert_eq!(param.trailer_field, 1);
+            }
+            _ => panic!("expected RSASSA-PSS parameters"),
+        }
+
+        // All parameters set to default
+        let (_, alg) = ASN1(AlgorithmIdentifier)
+            .parse(&[
+                0x30, 0x0D, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0A, 0x30,
+                0x00,
+            ])
+            .unwrap();
+
+        match alg.param {
+            AlgorithmParamValue::RSASSAPSS(Either::Left(param)) => {
+                assert!(matches!(param.hash_alg, OptionDeep::None));
+                assert!(matches!(param.mask_gen_alg, OptionDeep::None));
+                assert_eq!(param.salt_len, 20);
+                assert_eq!(param.trailer_field, 1);
+            }
+            _ => panic!("expected RSASSA-PSS parameters"),
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use super::*;
+use crate::asn1::Integer;
 use vstd::prelude::*;
 
 verus! {
@@ -26,6 +27,41 @@ asn1! {
         modulus: ASN1<BigInt> = ASN1(BigInt),
         exponent: ASN1<BigInt> = ASN1(BigInt),
     }
+
+    // In RFC 4055:
+    // RSASSA-PSS-params  ::=  SEQUENCE  {
+    //     hashAlgorithm      [0] HashAlgorithm DEFAULT sha1Identifier,
+    //     maskGenAlgorithm   [1] MaskGenAlgorithm DEFAULT mgf1SHA1Identifier,
+    //     saltLength         [2] INTEGER DEFAULT 20,
+    //     trailerField       [3] INTEGER DEFAULT 1
+    // }
+    //
+    // NOTE: the defaults of hashAlgorithm and maskGenAlgorithm (SHA-1)
+    // are represented as absent fields here
+    seq RSASSAPSSParam {
+        #[optional] hash_alg: ASN1<ExplicitTag<ASN1<HashAlgorithm>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(HashAlgorithm))),
+        #[optional] mask_gen_alg: ASN1<ExplicitTag<ASN1<MaskGenAlgorithm>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 1), ASN1(MaskGenAlgorithm))),
+        #[default(20i64)] salt_len: ASN1<ExplicitTag<ASN1<Integer>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 2), ASN1(Integer))),
+        #[default(1i64)] trailer_field: ASN1<ExplicitTag<ASN1<Integer>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 3), ASN1(Integer))),
+    }
+
+    // HashAlgorithm  ::=  AlgorithmIdentifier
+    //
+    // NOTE: the parameters of all hash functions used here
+    // are either NULL or absent
+    seq HashAlgorithm {
+        id: ASN1<ObjectIdentifier> = ASN1(ObjectIdentifier),
+        #[optional] param: ASN1<Null> = ASN1(Null),
+    }
+
+    // MaskGenAlgorithm  ::=  AlgorithmIdentifier
+    //
+    // NOTE: MGF1 is the only mask generation function defined,
+    // which takes a HashAlgorithm as the parameter
+    seq MaskGenAlgorithm {
+        id: ASN1<ObjectIdentifier> = ASN1(ObjectIdentifier),
+        hash_alg: ASN1<HashAlgorithm> = ASN1(HashAlgorithm),
+    }
 }
 
 // TODO: DSA, ECDSA, etc.
@@ -44,6 +80,10 @@ oid_match_continuation! {
         oid(RSA_SIGNATURE_SHA512) => RSASignatureWithSHA512(OrdChoice(ASN1(Null), End)): OrdChoice<ASN1<Null>, End>,
         oid(RSA_SIGNATURE_SHA224) => RSASignatureWithSHA224(OrdChoice(ASN1(Null), End)): OrdChoice<ASN1<Null>, End>,
 
+        // NOTE: the parameters are required in signature algorithms
+        // but may be absent in subject public key algorithms
+        oid(RSASSA_PSS) => RSASSAPSS(OrdChoice(ASN1(RSASSAPSSParam), End)): OrdChoice<ASN1<RSASSAPSSParam>, End>,
+
         oid(DSA_SIGNATURE) => DSASignature(OrdChoice(ASN1(DSAParam), End)): OrdChoice<ASN1<DSAParam>, End>,
 
         oid(ECDSA_SIGNATURE_SHA224) => ECDSASignatureWithSHA224(End): End,
@@ -51,6 +91,11 @@ oid_match_continuation! {
         oid(ECDSA_SIGNATURE_SHA384) => ECDSASignatureWithSHA384(End): End,
         oid(ECDSA_SIGNATURE_SHA512) => ECDSASignatureWithSHA512(End): End,
 
+        // RFC 8410: the parameters must be absent in both
+        // signature algorithms and subject public key algorithms
+        oid(ED25519) => Ed25519(End): End,
+        oid(ED448) => Ed448(End): End,
+
         // Subject public key algorithms
         oid(RSA_ENCRYPTION) => RSAEncryption(OrdChoice(ASN1(Null), End)): OrdChoice<ASN1<Null>, End>,
         oid(EC_PUBLIC_KEY) => ECPublicKey(ASN1(ObjectIdentifier)): ASN1<ObjectIdentifier>, // Currently only support named curves
@@ -65,6 +110,27 @@ impl<'a> PolyfillEq for DSAParamValue<'a> {
     }
 }
 
+impl<'a> PolyfillEq for HashAlgorithmValue<'a> {
+    fn polyfill_eq(&self, other: &Self) -> bool {
+        self.id.polyfill_eq(&other.id) && self.param.polyfill_eq(&other.param)
+    }
+}
+
+impl<'a> PolyfillEq for MaskGenAlgorithmValue<'a> {
+    fn polyfill_eq(&self, other: &Self) -> bool {
+        self.id.polyfill_eq(&other.id) && self.hash_alg.polyfill_eq(&other.hash_alg)
+    }
+}
+
+impl<'a> PolyfillEq for RSASSAPSSParamValue<'a> {
+    fn polyfill_eq(&self, other: &Self) -> bool {
+        self.hash_alg.polyfill_eq(&other.hash_alg) &&
+        self.mask_gen_alg.polyfill_eq(&other.mask_gen_alg) &&
+        self.salt_len.polyfill_eq(&other.salt_len) &&
+        self.trailer_field.polyfill_eq(&other.trailer_field)
+    }
+}
+
 impl<'a> PolyfillEq for AlgorithmParamValue<'a> {
     fn polyfill_eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -75,11 +141,14 @@ impl<'a> PolyfillEq for AlgorithmParamValue<'a> {
             (AlgorithmParamValue::RSASignatureWithSHA384(a), AlgorithmParamValue::RSASignatureWithSHA384(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::RSASignatureWithSHA512(a), AlgorithmParamValue::RSASignatureWithSHA512(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::RSASignatureWithSHA224(a), AlgorithmParamValue::RSASignatureWithSHA224(b)) => a.polyfill_eq(b),
+            (AlgorithmParamValue::RSASSAPSS(a), AlgorithmParamValue::RSASSAPSS(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::DSASignature(a), AlgorithmParamValue::DSASignature(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::ECDSASignatureWithSHA224(a), AlgorithmParamValue::ECDSASignatureWithSHA224(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::ECDSASignatureWithSHA256(a), AlgorithmParamValue::ECDSASignatureWithSHA256(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::ECDSASignatureWithSHA384(a), AlgorithmParamValue::ECDSASignatureWithSHA384(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::ECDSASignatureWithSHA512(a), AlgorithmParamValue::ECDSASignatureWithSHA512(b)) => a.polyfill_eq(b),
+            (AlgorithmParamValue::Ed25519(a), AlgorithmParamValue::Ed25519(b)) => a.polyfill_eq(b),
+            (AlgorithmParamValue::Ed448(a), AlgorithmParamValue::Ed448(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::RSAEncryption(a), AlgorithmParamValue::RSAEncryption(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::ECPublicKey(a), AlgorithmParamValue::ECPublicKey(b)) => a.polyfill_eq(b),
             (AlgorithmParamValue::Other(a), AlgorithmParamValue::Other(b)) => a.polyfill_eq(b),
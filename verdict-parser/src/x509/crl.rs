@@ -0,0 +1,169 @@
+use super::*;
+use vstd::prelude::*;
+
+verus! {
+
+// In RFC 5280:
+// RevokedCertificate ::= SEQUENCE {
+//     userCertificate         CertificateSerialNumber,
+//     revocationDate          Time,
+//     crlEntryExtensions      Extensions OPTIONAL
+//                              -- if present, version MUST be v2
+// }
+asn1! {
+    seq RevokedCertificate {
+        serial: ASN1<BigInt> = ASN1(BigInt),
+        revocation_date: Time = Time,
+        #[optional] crl_entry_extensions: ASN1<Extensions> = ASN1(Extensions),
+    }
+
+    seq of RevokedCertificates(ASN1(RevokedCertificate)): ASN1<RevokedCertificate>;
+}
+
+// TBSCertList  ::=  SEQUENCE  {
+//     version                 Version OPTIONAL,
+//                                  -- if present, MUST be v2
+//     signature               AlgorithmIdentifier,
+//     issuer                  Name,
+//     thisUpdate              Time,
+//     nextUpdate              Time OPTIONAL,
+//     revokedCertificates     SEQUENCE OF SEQUENCE { ... } OPTIONAL,
+//     crlExtensions           [0]  EXPLICIT Extensions OPTIONAL
+//                                  -- if present, version MUST be v2
+// }
+asn1! {
+    seq TBSCertList {
+        #[optional] version: ASN1<Integer> = ASN1(Integer),
+
+        signature: ASN1<AlgorithmIdentifier> = ASN1(AlgorithmIdentifier),
+        issuer: ASN1<Name> = ASN1(Name),
+        this_update: Time = Time,
+
+        #[optional] next_update: Time = Time,
+        #[optional] revoked_certs: ASN1<RevokedCertificates> = ASN1(RevokedCertificates),
+        #[optional] crl_extensions: ASN1<ExplicitTag<ASN1<Extensions>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(Extensions))),
+    }
+}
+
+/// The fields following the optional nextUpdate in TBSCertList
+/// (revokedCertificates and crlExtensions) have tags different from both
+/// UTCTime and GeneralizedTime, so they are disjoint from Time
+///
+/// NOTE: unproved for the same reason as wrap_combinator! (Time::spec_parse is uninterpreted),
+/// so these are only stated for the concrete combinators that follow a Time field
+impl DisjointFrom<Time> for ASN1<RevokedCertificates> {
+    open spec fn disjoint_from(&self, other: &Time) -> bool {
+        &&& self.0.spec_tag() != UTCTime.spec_tag()
+        &&& self.0.spec_tag() != GeneralizedTime.spec_tag()
+    }
+
+    #[verifier::external_body]
+    proof fn parse_disjoint_on(&self, other: &Time, buf: Seq<u8>) {}
+}
+
+impl DisjointFrom<Time> for ASN1<ExplicitTag<ASN1<Extensions>>> {
+    open spec fn disjoint_from(&self, other: &Time) -> bool {
+        &&& self.0.spec_tag() != UTCTime.spec_tag()
+        &&& self.0.spec_tag() != GeneralizedTime.spec_tag()
+    }
+
+    #[verifier::external_body]
+    proof fn parse_disjoint_on(&self, other: &Time, buf: Seq<u8>) {}
+}
+
+/// Time always starts with a tag, so it never parses the empty input
+impl DisjointFrom<Time> for End {
+    open spec fn disjoint_from(&self, other: &Time) -> bool { true }
+
+    #[verifier::external_body]
+    proof fn parse_disjoint_on(&self, other: &Time, buf: Seq<u8>) {}
+}
+
+// CertificateList  ::=  SEQUENCE  {
+//     tbsCertList          TBSCertList,
+//     signatureAlgorithm   AlgorithmIdentifier,
+//     signatureValue       BIT STRING
+// }
+asn1! {
+    seq CertificateListInner {
+        cert_list: Cached<ASN1<TBSCertList>> = Cached(ASN1(TBSCertList)),
+        sig_alg: Cached<ASN1<AlgorithmIdentifier>> = Cached(ASN1(AlgorithmIdentifier)),
+        sig: ASN1<BitString> = ASN1(BitString),
+    }
+}
+
+wrap_combinator! {
+    pub struct CertificateList: Cached<ASN1<CertificateListInner>> = Cached(ASN1(CertificateListInner));
+}
+
+pub type SpecCertificateListValue = SpecCertificateListInnerValue;
+pub type CertificateListValue<'a> = CachedValue<'a, ASN1<CertificateListInner>>;
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base64::Engine;
+
+    verus! {
+        /// Check that all trait bounds and preconditions are satisfied
+        #[test]
+        fn is_combinator() {
+            let _ = ASN1(RevokedCertificate).parse(&[]);
+            let _ = ASN1(TBSCertList).parse(&[]);
+            let _ = CertificateList.parse(&[]);
+        }
+
+        /// Check if the serialization cache is correct
+        #[test]
+        fn cached() {
+            if let Ok((_, res)) = CertificateList.parse(&[]) {
+                let ser = res.serialize();
+                assert(ASN1(CertificateListInner)@.spec_serialize(res@).is_ok());
+                assert(ser@ == ASN1(CertificateListInner)@.spec_serialize(res@).unwrap());
+
+                let tbs: &CachedValue<ASN1<TBSCertList>> = &res.get().cert_list;
+                let tbs_ser = tbs.serialize();
+                assert(ASN1(TBSCertList)@.spec_serialize(tbs@).is_ok());
+                assert(tbs_ser@ == ASN1(TBSCertList)@.spec_serialize(tbs@).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn sanity() {
+        // thisUpdate and nextUpdate only, no revoked certificates
+        assert!(ASN1(TBSCertList)
+            .parse(&[
+                0x30, 0x2C, 0x30, 0x0A, 0x06, 0x08, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x02,
+                0x30, 0x00, 0x17, 0x0D, 0x32, 0x36, 0x31, 0x30, 0x31, 0x38, 0x30, 0x30, 0x30, 0x30,
+                0x30, 0x30, 0x5A, 0x17, 0x0D, 0x32, 0x36, 0x31, 0x31, 0x31, 0x37, 0x30, 0x30, 0x30,
+                0x30, 0x30, 0x30, 0x5A,
+            ])
+            .is_ok());
+    }
+
+    #[test]
+    fn crl_pem() {
+        let crl = include_str!("../../tests/data/crl.pem");
+        let crl_base64 = crl
+            .lines()
+            .filter(|l| !l.starts_with("-----"))
+            .collect::<String>();
+        let crl_bytes = base64::prelude::BASE64_STANDARD.decode(crl_base64).unwrap();
+
+        let (n, crl) = CertificateList.parse(&crl_bytes).unwrap();
+
+        // Check that the caching is correct
+        assert_eq!(n, crl_bytes.len());
+        assert_eq!(&crl_bytes, crl.serialize());
+
+        let revoked = match &crl.get().cert_list.get().revoked_certs {
+            OptionDeep::Some(revoked) => revoked,
+            OptionDeep::None => panic!("expected revoked certificates"),
+        };
+        assert_eq!(revoked.len(), 1);
+        assert_eq!(revoked.get(0).serial.bytes(), &[0x12, 0x34]);
+    }
+}
@@ -7,12 +7,14 @@ mod alg_id;
 mod alg_param;
 mod attr_typ_val;
 mod cert;
+mod crl;
 mod dir_string;
 mod display;
 mod ext_value;
 mod extension;
 mod general_name;
 mod name;
+mod ocsp;
 mod oid;
 mod pub_key_info;
 mod rdn;
@@ -26,6 +28,7 @@ pub use alg_id::*;
 pub use alg_param::*;
 pub use attr_typ_val::*;
 pub use cert::*;
+pub use crl::*;
 pub use dir_string::*;
 pub use display::*;
 pub use ext_value::*;
@@ -33,6 +36,7 @@ pub use extension::*;
 pub use general_name::*;
 pub use macros::*;
 pub use name::*;
+pub use ocsp::*;
 pub use oid::*;
 pub use pub_key_info::*;
 pub use rdn::*;
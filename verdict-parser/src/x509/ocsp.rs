@@ -0,0 +1,257 @@
+use super::*;
+use crate::asn1::Integer;
+use vstd::prelude::*;
+
+verus! {
+
+// In RFC 6960:
+// CertID ::= SEQUENCE {
+//     hashAlgorithm       AlgorithmIdentifier,
+//     issuerNameHash      OCTET STRING, -- Hash of issuer's DN
+//     issuerKeyHash       OCTET STRING, -- Hash of issuer's public key
+//     serialNumber        CertificateSerialNumber
+// }
+asn1! {
+    seq CertID {
+        hash_alg: ASN1<AlgorithmIdentifier> = ASN1(AlgorithmIdentifier),
+        issuer_name_hash: ASN1<OctetString> = ASN1(OctetString),
+        issuer_key_hash: ASN1<OctetString> = ASN1(OctetString),
+        serial: ASN1<BigInt> = ASN1(BigInt),
+    }
+}
+
+// RevokedInfo ::= SEQUENCE {
+//     revocationTime              GeneralizedTime,
+//     revocationReason    [0]     EXPLICIT CRLReason OPTIONAL
+// }
+//
+// CRLReason ::= ENUMERATED { ... }
+asn1! {
+    seq RevokedInfo {
+        revocation_time: ASN1<GeneralizedTime> = ASN1(GeneralizedTime),
+        #[optional] revocation_reason: ASN1<ExplicitTag<ASN1<ImplicitTag<Integer>>>> =
+            ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(ImplicitTag(tag_of!(ENUMERATED), Integer)))),
+    }
+}
+
+// CertStatus ::= CHOICE {
+//     good        [0]     IMPLICIT NULL,
+//     revoked     [1]     IMPLICIT RevokedInfo,
+//     unknown     [2]     IMPLICIT UnknownInfo
+// }
+//
+// UnknownInfo ::= NULL
+asn1! {
+    choice CertStatus {
+        Good(ASN1(ImplicitTag(tag_of!(IMPLICIT 0), Null))): ASN1<ImplicitTag<Null>>,
+        Revoked(ASN1(ImplicitTag(tag_of!(EXPLICIT 1), RevokedInfo))): ASN1<ImplicitTag<RevokedInfo>>,
+        Unknown(ASN1(ImplicitTag(tag_of!(IMPLICIT 2), Null))): ASN1<ImplicitTag<Null>>,
+    }
+}
+
+// SingleResponse ::= SEQUENCE {
+//     certID                       CertID,
+//     certStatus                   CertStatus,
+//     thisUpdate                   GeneralizedTime,
+//     nextUpdate         [0]       EXPLICIT GeneralizedTime OPTIONAL,
+//     singleExtensions   [1]       EXPLICIT Extensions OPTIONAL
+// }
+asn1! {
+    seq SingleResponse {
+        cert_id: ASN1<CertID> = ASN1(CertID),
+        cert_status: CertStatus = CertStatus,
+        this_update: ASN1<GeneralizedTime> = ASN1(GeneralizedTime),
+
+        #[optional] next_update: ASN1<ExplicitTag<ASN1<GeneralizedTime>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(GeneralizedTime))),
+        #[optional] single_extensions: ASN1<ExplicitTag<ASN1<Extensions>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 1), ASN1(Extensions))),
+    }
+
+    seq of SingleResponses(ASN1(SingleResponse)): ASN1<SingleResponse>;
+}
+
+// ResponderID ::= CHOICE {
+//     byName   [1] Name,
+//     byKey    [2] KeyHash
+// }
+//
+// KeyHash ::= OCTET STRING -- SHA-1 hash of responder's public key
+asn1! {
+    choice ResponderID {
+        ByName(ASN1(ExplicitTag(tag_of!(EXPLICIT 1), ASN1(Name)))): ASN1<ExplicitTag<ASN1<Name>>>,
+        ByKey(ASN1(ExplicitTag(tag_of!(EXPLICIT 2), ASN1(OctetString)))): ASN1<ExplicitTag<ASN1<OctetString>>>,
+    }
+}
+
+/// (ResponderID, ...) is disjoint from the version field
+/// preceding it in ResponseData, whose tag ([0]) is neither [1] nor [2]
+///
+/// NOTE: unproved for the same reason as wrap_combinator! (ResponderID::spec_parse is uninterpreted),
+/// so this is only stated for the concrete combinator of the version field
+impl<S> DisjointFrom<ASN1<ExplicitTag<ASN1<Integer>>>> for Pair<ResponderID, S> where
+    S: SecureSpecCombinator,
+{
+    open spec fn disjoint_from(&self, other: &ASN1<ExplicitTag<ASN1<Integer>>>) -> bool {
+        &&& other.0.spec_tag() != tag_of!(EXPLICIT 1)
+        &&& other.0.spec_tag() != tag_of!(EXPLICIT 2)
+    }
+
+    #[verifier::external_body]
+    proof fn parse_disjoint_on(&self, other: &ASN1<ExplicitTag<ASN1<Integer>>>, buf: Seq<u8>) {}
+}
+
+// ResponseData ::= SEQUENCE {
+//     version              [0] EXPLICIT Version DEFAULT v1,
+//     responderID              ResponderID,
+//     producedAt               GeneralizedTime,
+//     responses                SEQUENCE OF SingleResponse,
+//     responseExtensions   [1] EXPLICIT Extensions OPTIONAL
+// }
+asn1! {
+    seq ResponseData {
+        #[default(0i64)] version: ASN1<ExplicitTag<ASN1<Integer>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(Integer))),
+
+        responder_id: ResponderID = ResponderID,
+        produced_at: ASN1<GeneralizedTime> = ASN1(GeneralizedTime),
+        responses: ASN1<SingleResponses> = ASN1(SingleResponses),
+
+        #[optional] response_extensions: ASN1<ExplicitTag<ASN1<Extensions>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 1), ASN1(Extensions))),
+    }
+}
+
+// BasicOCSPResponse ::= SEQUENCE {
+//     tbsResponseData      ResponseData,
+//     signatureAlgorithm   AlgorithmIdentifier,
+//     signature            BIT STRING,
+//     certs            [0] EXPLICIT SEQUENCE OF Certificate OPTIONAL
+// }
+asn1! {
+    seq BasicOCSPResponse {
+        tbs_response_data: Cached<ASN1<ResponseData>> = Cached(ASN1(ResponseData)),
+        sig_alg: ASN1<AlgorithmIdentifier> = ASN1(AlgorithmIdentifier),
+        sig: ASN1<BitString> = ASN1(BitString),
+        #[optional] certs: ASN1<ExplicitTag<ASN1<OCSPCertificates>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(OCSPCertificates))),
+    }
+
+    seq of OCSPCertificates(Certificate): Certificate;
+}
+
+// ResponseBytes ::= SEQUENCE {
+//     responseType   OBJECT IDENTIFIER,
+//     response       OCTET STRING
+// }
+//
+// NOTE: we only support id-pkix-ocsp-basic (the only response type
+// defined in RFC 6960), so the content of `response` is always parsed
+// as a BasicOCSPResponse; `response_type` should be checked separately
+asn1! {
+    seq ResponseBytes {
+        response_type: ASN1<ObjectIdentifier> = ASN1(ObjectIdentifier),
+        response: ASN1<ExplicitTag<ASN1<BasicOCSPResponse>>> = ASN1(ExplicitTag(tag_of!(OCTET_STRING), ASN1(BasicOCSPResponse))),
+    }
+}
+
+// OCSPResponse ::= SEQUENCE {
+//     responseStatus         OCSPResponseStatus,
+//     responseBytes          [0] EXPLICIT ResponseBytes OPTIONAL
+// }
+//
+// OCSPResponseStatus ::= ENUMERATED {
+//     successful            (0),
+//     malformedRequest      (1),
+//     internalError         (2),
+//     tryLater              (3),
+//     sigRequired           (5),
+//     unauthorized          (6)
+// }
+asn1! {
+    seq OCSPResponseInner {
+        status: ASN1<ImplicitTag<Integer>> = ASN1(ImplicitTag(tag_of!(ENUMERATED), Integer)),
+        #[optional] response_bytes: ASN1<ExplicitTag<ASN1<ResponseBytes>>> = ASN1(ExplicitTag(tag_of!(EXPLICIT 0), ASN1(ResponseBytes))),
+    }
+}
+
+wrap_combinator! {
+    pub struct OCSPResponse: ASN1<OCSPResponseInner> = ASN1(OCSPResponseInner);
+}
+
+pub type SpecOCSPResponseValue = SpecOCSPResponseInnerValue;
+pub type OCSPResponseValue<'a> = OCSPResponseInnerValue<'a>;
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    verus! {
+        /// Check that all trait bounds and preconditions are satisfied
+        #[test]
+        fn is_combinator() {
+            let _ = ASN1(CertID).parse(&[]);
+            let _ = CertStatus.parse(&[]);
+            let _ = ASN1(SingleResponse).parse(&[]);
+            let _ = ResponderID.parse(&[]);
+            let _ = ASN1(ResponseData).parse(&[]);
+            let _ = ASN1(BasicOCSPResponse).parse(&[]);
+            let _ = OCSPResponse.parse(&[]);
+        }
+
+        /// Check if the serialization cache is correct
+        #[test]
+        fn cached() {
+            if let Ok((_, res)) = ASN1(BasicOCSPResponse).parse(&[]) {
+                let tbs: &CachedValue<ASN1<ResponseData>> = &res.tbs_response_data;
+                let tbs_ser = tbs.serialize();
+                assert(ASN1(ResponseData)@.spec_serialize(tbs@).is_ok());
+                assert(tbs_ser@ == ASN1(ResponseData)@.spec_serialize(tbs@).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn sanity() {
+        // Unsuccessful response (tryLater) without responseBytes
+        assert!(OCSPResponse.parse(&[0x30, 0x03, 0x0A, 0x01, 0x03]).is_ok());
+
+        // good, revoked and unknown statuses
+        assert!(CertStatus.parse(&[0x80, 0x00]).is_ok());
+        assert!(CertStatus
+            .parse(&[
+                0xA1, 0x11, 0x18, 0x0F, 0x32, 0x30, 0x32, 0x36, 0x31, 0x30, 0x31, 0x38, 0x30, 0x38,
+                0x34, 0x37, 0x31, 0x30, 0x5A,
+            ])
+            .is_ok());
+        assert!(CertStatus.parse(&[0x82, 0x00]).is_ok());
+        assert!(CertStatus.parse(&[0x83, 0x00]).is_err());
+    }
+
+    #[test]
+    fn revoked_response() {
+        let der = include_bytes!("../../tests/data/ocsp_revoked.der");
+        let (n, resp) = OCSPResponse.parse(der).unwrap();
+        assert_eq!(n, der.len());
+        assert_eq!(resp.status, 0);
+
+        let bytes = match &resp.response_bytes {
+            OptionDeep::Some(bytes) => bytes,
+            OptionDeep::None => panic!("expected response bytes"),
+        };
+        assert!(bytes.response_type.polyfill_eq(&oid!(OCSP_BASIC)));
+
+        let basic = &bytes.response;
+        let data = basic.tbs_response_data.get();
+        assert!(matches!(data.responder_id, ResponderIDValue::ByName(..)));
+        assert!(matches!(basic.certs, OptionDeep::None));
+        assert_eq!(data.responses.len(), 1);
+
+        let single = data.responses.get(0);
+        assert_eq!(single.cert_id.serial.bytes(), &[0x12, 0x34]);
+        assert!(single.cert_id.hash_alg.id.polyfill_eq(&oid!(HASH_SHA256)));
+        assert!(matches!(single.cert_status, CertStatusValue::Revoked(..)));
+        assert!(matches!(single.next_update, OptionDeep::Some(..)));
+
+        // Check that the caching is correct
+        let tbs_len = basic.tbs_response_data.serialize().len();
+        assert_eq!(tbs_len, 0xCB + 3);
+    }
+}
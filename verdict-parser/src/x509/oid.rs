@@ -33,6 +33,7 @@ macro_rules! oid_name {
     (RSA_SIGNATURE_SHA384)  => { [1, 2, 840, 113549, 1, 1, 12] };
     (RSA_SIGNATURE_SHA512)  => { [1, 2, 840, 113549, 1, 1, 13] };
     (RSA_SIGNATURE_SHA224)  => { [1, 2, 840, 113549, 1, 1, 14] };
+    (RSASSA_PSS)            => { [1, 2, 840, 113549, 1, 1, 10] };
     (DSA_SIGNATURE)         => { [1, 2, 840, 10040, 4, 1] };
 
     (ECDSA_SIGNATURE_SHA224) => { [1, 2, 840, 10045, 4, 3, 1] };
@@ -40,12 +41,27 @@ macro_rules! oid_name {
     (ECDSA_SIGNATURE_SHA384) => { [1, 2, 840, 10045, 4, 3, 3] };
     (ECDSA_SIGNATURE_SHA512) => { [1, 2, 840, 10045, 4, 3, 4] };
 
+    // Also used as subject public key algorithms
+    (ED25519)               => { [1, 3, 101, 112] };
+    (ED448)                 => { [1, 3, 101, 113] };
+
     (RSA_ENCRYPTION)        => { [1, 2, 840, 113549, 1, 1, 1] };
     (EC_PUBLIC_KEY)         => { [1, 2, 840, 10045, 2, 1] };
 
     // EC curves
     (EC_P_256)              => { [1, 2, 840, 10045, 3, 1, 7] };
     (EC_P_384)              => { [1, 3, 132, 0, 34] };
+    (EC_P_521)              => { [1, 3, 132, 0, 35] };
+
+    // Hash algorithms (e.g. in OCSP CertID and RSASSA-PSS parameters)
+    (HASH_SHA1)             => { [1, 3, 14, 3, 2, 26] };
+    (HASH_SHA256)           => { [2, 16, 840, 1, 101, 3, 4, 2, 1] };
+    (HASH_SHA384)           => { [2, 16, 840, 1, 101, 3, 4, 2, 2] };
+    (HASH_SHA512)           => { [2, 16, 840, 1, 101, 3, 4, 2, 3] };
+    (HASH_SHA224)           => { [2, 16, 840, 1, 101, 3, 4, 2, 4] };
+
+    // Mask generation functions
+    (MGF1)                  => { [1, 2, 840, 113549, 1, 1, 8] };
 
     // Directory names
     (COMMON_NAME)           => { [2, 5, 4, 3] };
@@ -71,6 +87,13 @@ macro_rules! oid_name {
     (EMAIL_PROTECTION)      => { [1, 3, 6, 1, 5, 5, 7, 3, 4] };
     (TIME_STAMPING)         => { [1, 3, 6, 1, 5, 5, 7, 3, 8] };
     (OCSP_SIGNING)          => { [1, 3, 6, 1, 5, 5, 7, 3, 9] };
+
+    // OCSP response types
+    (OCSP_BASIC)            => { [1, 3, 6, 1, 5, 5, 7, 48, 1, 1] };
+
+    // Access methods in Authority Information Access
+    (AD_OCSP)               => { [1, 3, 6, 1, 5, 5, 7, 48, 1] };
+    (AD_CA_ISSUERS)         => { [1, 3, 6, 1, 5, 5, 7, 48, 2] };
 }
 pub use oid_name;
 
@@ -102,11 +125,14 @@ gen_oid_axioms! {
     RSA_SIGNATURE_SHA384
     RSA_SIGNATURE_SHA512
     RSA_SIGNATURE_SHA224
+    RSASSA_PSS
     DSA_SIGNATURE
     ECDSA_SIGNATURE_SHA224
     ECDSA_SIGNATURE_SHA256
     ECDSA_SIGNATURE_SHA384
     ECDSA_SIGNATURE_SHA512
+    ED25519
+    ED448
     RSA_ENCRYPTION
     EC_PUBLIC_KEY
 }
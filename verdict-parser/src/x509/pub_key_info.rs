@@ -102,4 +102,28 @@ mod test {
             ])
             .is_ok());
     }
+
+    #[test]
+    fn ed25519() {
+        let (_, spki) = ASN1(PublicKeyInfo)
+            .parse(&[
+                0x30, 0x2A, 0x30, 0x05, 0x06, 0x03, 0x2B, 0x65, 0x70, 0x03, 0x21, 0x00, 0x19, 0xBF,
+                0x44, 0x09, 0x69, 0x84, 0xCD, 0xFE, 0x85, 0x41, 0xBA, 0xC1, 0x67, 0xDC, 0x3B, 0x96,
+                0xC8, 0x50, 0x86, 0xAA, 0x30, 0xB6, 0xB6, 0xCB, 0x0C, 0x5C, 0x38, 0xAD, 0x70, 0x31,
+                0x66, 0xE1,
+            ])
+            .unwrap();
+        assert!(matches!(spki.alg.param, AlgorithmParamValue::Ed25519(..)));
+        assert_eq!(spki.pub_key.bytes().len(), 32);
+
+        // RFC 8410 requires the parameters to be absent
+        assert!(ASN1(PublicKeyInfo)
+            .parse(&[
+                0x30, 0x2C, 0x30, 0x07, 0x06, 0x03, 0x2B, 0x65, 0x70, 0x05, 0x00, 0x03, 0x21, 0x00,
+                0x19, 0xBF, 0x44, 0x09, 0x69, 0x84, 0xCD, 0xFE, 0x85, 0x41, 0xBA, 0xC1, 0x67, 0xDC,
+                0x3B, 0x96, 0xC8, 0x50, 0x86, 0xAA, 0x30, 0xB6, 0xB6, 0xCB, 0x0C, 0x5C, 0x38, 0xAD,
+                0x70, 0x31, 0x66, 0xE1,
+            ])
+            .is_err());
+    }
 }
@@ -12,7 +12,7 @@ asn1! {
         signature: Cached<ASN1<AlgorithmIdentifier>> = Cached(ASN1(AlgorithmIdentifier)),
         issuer: ASN1<Name> = ASN1(Name),
         validity: ASN1<Validity> = ASN1(Validity),
-        subject: ASN1<Name> = ASN1(Name),
+        subject: Cached<ASN1<Name>> = Cached(ASN1(Name)),
         subject_key: ASN1<PublicKeyInfo> = ASN1(PublicKeyInfo),
 
         #[optional] issuer_uid: ASN1<ImplicitTag<BitString>> = ASN1(ImplicitTag(tag_of!(IMPLICIT 1), BitString)),
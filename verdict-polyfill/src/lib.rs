@@ -310,6 +310,25 @@ pub fn vec_reverse<T: DeepView>(v: &mut Vec<&T>)
     }
 }
 
+/// Borrow each element of a vector
+pub fn vec_refs<T: DeepView>(v: &Vec<T>) -> (res: Vec<&T>)
+    ensures res.deep_view() =~= v.deep_view(),
+{
+    let len = v.len();
+    let mut res = Vec::with_capacity(len);
+
+    for i in 0..len
+        invariant
+            len == v.len(),
+            res.len() == i,
+            forall |j| 0 <= j < i ==> #[trigger] res[j].deep_view() == v[j].deep_view(),
+    {
+        res.push(&v[i]);
+    }
+
+    res
+}
+
 #[verifier::external_body]
 pub fn vec_contains<T: PartialEq>(v: &Vec<T>, elem: &T) -> (res: bool)
     ensures res == v@.contains(*elem),
@@ -30,6 +30,23 @@ test_rspec!(
     }
 );
 
+test_rspec!(
+    mod exec_attr_struct {
+        // Only derived for the exec version (ExecTest)
+        #[exec_attr(derive(Clone, PartialEq))]
+        pub struct Test {
+            pub a: SpecString,
+            pub b: Seq<u32>,
+        }
+
+        #[exec_attr(derive(Clone))]
+        pub enum TestEnum {
+            A(Test),
+            B,
+        }
+    }
+);
+
 test_rspec!(mod simple_function1 {
     pub closed spec fn test1(i: u32) -> bool {
         if i <= 10 {
@@ -8,12 +8,12 @@ use syn_verus::parse::{Parse, ParseStream};
 use syn_verus::punctuated::Punctuated;
 use syn_verus::spanned::Spanned;
 use syn_verus::{
-    parse_macro_input, AngleBracketedGenericArguments, Arm, BigAnd, BigAndExpr, BigOr, BigOrExpr,
+    parse_macro_input, AngleBracketedGenericArguments, Arm, Attribute, BigAnd, BigAndExpr, BigOr, BigOrExpr,
     BinOp, Block, Ensures, Error, Expr, ExprBinary, ExprBlock, ExprCall, ExprCast, ExprClosure,
     ExprField, ExprIf, ExprLit, ExprMatch, ExprMatches, ExprMethodCall, ExprParen, ExprPath,
     ExprReference, ExprTuple, ExprUnary, Field, FieldPat, Fields, FieldsNamed, FieldsUnnamed,
     FnArg, FnArgKind, FnMode, GenericArgument, Ident, Index, Item, ItemEnum, ItemFn, ItemMod,
-    ItemStruct, Lit, LitBool, LitStr, Local, LocalInit, MatchesOpExpr, MatchesOpToken, Pat,
+    ItemStruct, Lit, LitBool, LitStr, Local, LocalInit, MatchesOpExpr, Meta, MatchesOpToken, Pat,
     PatIdent, PatPath, PatReference, PatStruct, PatTuple, PatTupleStruct, PatType, PatWild, Path,
     PathArguments, PathSegment, Publish, ReturnType, Signature, SignatureSpec, Specification, Stmt,
     Type, TypePath, TypeReference, UnOp, UseRename, UseTree, Variant, Visibility,
@@ -404,6 +404,36 @@ fn compile_type(ctx: &Context, ty: &Type) -> Result<Type, Error> {
     }
 }
 
+/// `#[exec_attr(<attr>)]` on a spec struct/enum is only applied to the
+/// generated exec version as `#[<attr>]` (e.g. for derives that
+/// do not work on spec types such as `Seq`)
+fn is_exec_attr(attr: &Attribute) -> bool {
+    attr.path().is_ident("exec_attr")
+}
+
+/// Attributes of the spec version of a struct/enum (without `exec_attr`)
+fn spec_attrs(attrs: &[Attribute]) -> Vec<Attribute> {
+    attrs.iter().filter(|attr| !is_exec_attr(attr)).cloned().collect()
+}
+
+/// Attributes of the exec version of a struct/enum
+/// (with `#[exec_attr(<attr>)]` unwrapped to `#[<attr>]`)
+fn compile_attrs(attrs: &[Attribute]) -> Result<Vec<Attribute>, Error> {
+    attrs
+        .iter()
+        .map(|attr| {
+            if is_exec_attr(attr) {
+                Ok(Attribute {
+                    meta: attr.parse_args::<Meta>()?,
+                    ..attr.clone()
+                })
+            } else {
+                Ok(attr.clone())
+            }
+        })
+        .collect()
+}
+
 /// Generate exec version of the given struct as well as a deep View impl
 fn compile_struct(
     ctx: &Context,
@@ -501,6 +531,7 @@ fn compile_struct(
 
     Ok((
         ItemStruct {
+            attrs: compile_attrs(&item_struct.attrs)?,
             ident: exec_name,
             fields: exec_fields,
             ..item_struct.clone()
@@ -622,6 +653,7 @@ fn compile_enum(ctx: &Context, item_enum: &ItemEnum) -> Result<(ItemEnum, TokenS
 
     Ok((
         ItemEnum {
+            attrs: compile_attrs(&item_enum.attrs)?,
             ident: exec_name,
             variants: exec_variants,
             ..item_enum.clone()
@@ -1597,13 +1629,21 @@ fn compile_rspec(items: Items, trace: bool) -> Result<TokenStream2, Error> {
             }
 
             Item::Struct(item_struct) => {
-                output.push(quote! { #item_struct });
+                let spec_struct = ItemStruct {
+                    attrs: spec_attrs(&item_struct.attrs),
+                    ..item_struct.clone()
+                };
+                output.push(quote! { #spec_struct });
                 ctx.structs
                     .insert(item_struct.ident.to_string(), item_struct);
             }
 
             Item::Enum(item_enum) => {
-                output.push(quote! { #item_enum });
+                let spec_enum = ItemEnum {
+                    attrs: spec_attrs(&item_enum.attrs),
+                    ..item_enum.clone()
+                };
+                output.push(quote! { #spec_enum });
                 ctx.enums.insert(item_enum.ident.to_string(), item_enum);
             }
 
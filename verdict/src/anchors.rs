@@ -0,0 +1,221 @@
+//! Root program constraints that are not encoded in the root certificates
+//! themselves, to be attached to roots as `TrustAnchor`s
+//! (by default for the root program of the policy, see `RootStore::snapshot`)
+
+/// Roots (identified by SHA-256 fingerprints in uppercase hex)
+/// that are only trusted for the given DNS names
+pub struct DomainRestriction {
+    pub fingerprints: &'static [&'static str],
+    pub domains: &'static [&'static str],
+}
+
+const INDIA: DomainRestriction = DomainRestriction {
+    fingerprints: &[
+        "F375E2F77A108BACC4234894A9AF308EDECA1ACD8FBDE0E7AAA9634E9DAF7E1C",
+        "2D66A702AE81BA03AF8CFF55AB318AFA919039D9F31B4D64388680F81311B65A",
+        "60109BC6C38328598A112C7A25E38B0F23E5A7511CB815FB64E0C4FF05DB7DF7",
+    ],
+    domains: &[
+        "*.gov.in",
+        "*.nic.in",
+        "*.ac.in",
+        "*.rbi.org.in",
+        "*.bankofindia.co.in",
+        "*.ncode.in",
+        "*.tcs.co.in",
+    ],
+};
+
+const ANSSI: DomainRestriction = DomainRestriction {
+    fingerprints: &["B9BEA7860A962EA3611DAB97AB6DA3E21C1068B97D55575ED0E11279C11C8932"],
+    domains: &[
+        "*.fr", "*.gp", "*.gf", "*.mq", "*.re", "*.yt", "*.pm", "*.bl", "*.mf", "*.wf", "*.pf",
+        "*.nc", "*.tf",
+    ],
+};
+
+const TUBITAK1: DomainRestriction = DomainRestriction {
+    fingerprints: &["46EDC3689046D53A453FB3104AB80DCAEC658B2660EA1629DD7E867990648716"],
+    domains: &[
+        "*.gov.tr", "*.k12.tr", "*.pol.tr", "*.mil.tr", "*.tsk.tr", "*.kep.tr", "*.bel.tr",
+        "*.edu.tr", "*.org.tr",
+    ],
+};
+
+/// Roots whose TLS leaf certificates with a later notBefore (as a UNIX
+/// timestamp) are not trusted, as in `TrustAnchor::server_distrust_after`,
+/// unless the chain goes through one of the `exceptions` (fingerprints of
+/// CAs issued by the roots, see `TrustAnchor::server_distrust_exceptions`)
+pub struct ServerDistrust {
+    pub fingerprints: &'static [&'static str],
+    pub exceptions: &'static [&'static str],
+    pub after: u64,
+
+    /// If present, only roots with an earlier notBefore are distrusted
+    pub roots_issued_before: Option<u64>,
+}
+
+/// symantecFingerprint and symantecExceptions in Hammurabi, which Firefox
+/// distrusts regardless of the issuance date of the leaf, for roots issued
+/// before June 2016 (see CheckForSymantecDistrust in Firefox)
+const SYMANTEC: ServerDistrust = ServerDistrust {
+    fingerprints: &[
+        "9ACFAB7E43C8D880D06B262A94DEEEE4B4659989C3D0CAF19BAF6405E41AB7DF",
+        "FF856A2D251DCD88D36656F450126798CFABAADE40799C722DE4D2B5DB36A73A",
+        "5EDB7AC43B82A06A8761E8D7BE4979EBF2611F7DD79BF91C1C6B566A219ED766",
+        "023C81CCE8E7C64FA942D3C15048707D35D9BB5B87F4F544C5BF1BC5643AF2FA",
+        "0999BF900BD5C297865E21E1AADE6CF6BB3A94D11AE5EA798442A4E2F813241F",
+        "0BDD5ABE940CAAABE8B2BBA88348FB6F4AA4CC84436F880BECE66B48BDA913D8",
+        "16A9E012D32329F282B10BBF57C7C0B42AE80F6AC9542EB409BC1C2CDE50D322",
+        "17755A5C295F3D2D72E6F031A1F07F400C588B9E582B22F17EAE31A1590D1185",
+        "1906C6124DBB438578D00E066D5054C6C37F0FA6028C05545E0994EDDAEC8629",
+        "1916F3508EC3FAD795F8DC4BD316F9C6085A64DE3C4153AC6D62D5EA19515D39",
+        "1D75D0831B9E0885394D32C7A1BFDB3DBC1C28E2B0E8391FB135981DBC5BA936",
+        "22076E5AEF44BB9A416A28B7D1C44322D7059F60FEFFA5CAF6C5BE8447891303",
+        "25B41B506E4930952823A6EB9F1D31DEF645EA38A5C6C6A96D71957E384DF058",
+        "26C18DC6EEA6F632F676BCEBA1D8C2B48352F29C2D5FCDA878E09DCB832DD6E5",
+        "2DC9470BE63EF4ACF1BD828609402BB7B87BD99638A643934E88682D1BE8C308",
+        "2DEE5171596AB8F3CD3C7635FEA8E6C3006AA9E31DB39D03A7480DDB2428A33E",
+        "3027A298FA57314DC0E3DD1019411B8F404C43C3F934CE3BDF856512C80AA15C",
+        "31512680233F5F2A1F29437F56D4988CF0AFC41CC6C5DA6275928E9C0BEADE27",
+        "43B3107D7342165D406CF975CD79B36ED1645048F05D7FF6EA0096E427B7DB84",
+        "463DBB9B0A26ED2616397B643125FBD29B66CF3A46FDB4384B209E78237A1AFF",
+        "479D130BF3FC61DC2F1D508D239A13276AE7B3C9841011A02C1402C7E677BD5F",
+        "4905466623AB4178BE92AC5CBD6584F7A1E17F27652D5A85AF89504EA239AAAA",
+        "495A96BA6BAD782407BD521A00BACE657BB355555E4BB7F8146C71BBA57E7ACE",
+        "4BA6031CA305B09E53BDE3705145481D0332B651FE30370DD5254CC4D2CB32F3",
+        "5192438EC369D7EE0CE71F5C6DB75F941EFBF72E58441715E99EAB04C2C8ACEE",
+        "567B8211FD20D3D283EE0CD7CE0672CB9D99BC5B487A58C9D54EC67F77D4A8F5",
+        "5C4F285388F38336269A55C7C12C0B3CA73FEF2A5A4DF82B89141E841A6C4DE4",
+        "67DC4F32FA10E7D01A79A073AA0C9E0212EC2FFC3D779E0AA7F9C0F0E1C2C893",
+        "6B86DE96A658A56820A4F35D90DB6C3EFDD574CE94B909CB0D7FF17C3C189D83",
+        "7006A38311E58FB193484233218210C66125A0E4A826AED539AC561DFBFBD903",
+        "781F1C3A6A42E3E915222DB4967702A2E577AEB017075FA3C159851FDDD0535E",
+        "7CAA03465124590C601E567E52148E952C0CFFE89000530FE0D95B6D50EAAE41",
+        "809F2BAAE35AFB4F36BD6476CE75C2001077901B6AF5C4DAB82E188C6B95C1A1",
+        "81A98FC788C35F557645A95224E50CD1DAC8FFB209DC1E5688AA29205F132218",
+        "860A7F19210D5EAD057A78532B80951453CB2907315F3BA7AA47B69897D70F3F",
+        "87AF34D66FB3F2FDF36E09111E9ABA2F6F44B207F3863F3D0B54B25023909AA5",
+        "95735473BD67A3B95A8D5F90C5A21ACE1E0D7947320674D4AB847972B91544D2",
+        "967B0CD93FCEF7F27CE2C245767AE9B05A776B0649F9965B6290968469686872",
+        "9699225C5DE52E56CDD32DF2E96D1CFEA5AA3CA0BB52CD8933C23B5C27443820",
+        "9C6F6A123CBAA4EE34DBECEEE24C97D738878CB423F3C2273903424F5D1F6DD5",
+        "A6F1F9BF8A0A9DDC080FB49B1EFC3D1A1C2C32DC0E136A5B00C97316F2A3DC11",
+        "AB3876C3DA5DE0C9CF6736868EE5B88BF9BA1DFF9C9D72D2FE5A8D2F78302166",
+        "AB39A4B025955691A40269F353FA1D5CB94EAF6C7EA9808484BBBB62FD9F68F3",
+        "AB5CDB3356397356D6E691973C25B8618B65D76A90486EA7A8A5C17767F4673A",
+        "AB98495276ADF1ECAFF28F35C53048781E5C1718DAB9C8E67A504F4F6A51328F",
+        "ACF65E1D62CB58A2BAFD6FFAB40FB88699C47397CF5CB483D42D69CAD34CD48B",
+        "AF207C61FD9C7CF92C2AFE8154282DC3F2CBF32F75CD172814C52B03B7EBC258",
+        "B1124142A5A1A5A28819C735340EFF8C9E2F8168FEE3BA187F253BC1A392D7E2",
+        "B2DEF5362AD3FACD04BD29047A43844F767034EA4892F80E56BEE690243E2502",
+        "BCFB44AAB9AD021015706B4121EA761C81C9E88967590F6F94AE744DC88B78FB",
+        "C07135F6B452398264A4776DBD0A6A307C60A36F967BD26321DCB817B5C0C481",
+        "CAB482CD3E820C5CE72AA3B6FDBE988BB8A4F0407ECAFD8C926E36824EAB92DD",
+        "D2F91A04E3A61D4EAD7848C8D43B5E1152D885727489BC65738B67C0A22785A7",
+        "D3A25DA80DB7BAB129A066AB41503DDDFFA02C768C0589F99FD71193E69916B6",
+        "D4AF6C0A482310BD7C54BB7AB121916F86C0C07CD52FCAC32D3844C26005115F",
+        "DA800B80B2A87D399E66FA19D72FDF49983B47D8CF322C7C79503A0C7E28FEAF",
+        "F15F1D323ED9CA98E9EA95B33EC5DDA47EA4C329F952C16F65AD419E64520476",
+        "F2E9365EA121DF5EEBD8DE2468FDC171DC0A9E46DADC1AB41D52790BA980A7C2",
+        "F53C22059817DD96F400651639D2F857E21070A59ABED9079400D9F695506900",
+        "F6B59C8E2789A1FD5D5B253742FEADC6925CB93EDC345E53166E12C52BA2A601",
+        "FF5680CD73A5703DA04817A075FD462506A73506C4B81A1583EF549478D26476",
+    ],
+    // Apple and Google subordinate CAs
+    exceptions: &[
+        "17F96609AC6AD0A2D6AB0A21B2D1B5B2946BD04DBF120703D1DEF6FB62F4B661",
+        "3DB76D1DD7D3A759DCCC3F8FA7F68675C080CB095E4881063A6B850FDD68B8BC",
+        "6115F06A338A649E61585210E76F2ECE3989BCA65A62B066040CD7C5F408EDD0",
+        "904FB5A437754B1B32B80EBAE7416DB63D05F56A9939720B7C8E3DCC54F6A3D1",
+        "AC2B922ECFD5E01711772FEA8ED372DE9D1E2245FCE3F57A9CDBEC77296A424B",
+        "A4FE7C7F15155F3F0AEF7AAA83CF6E06DEB97CA3F909DF920AC1490882D488ED",
+        "8BB593A93BE1D0E8A822BB887C547890C3E706AAD2DAB76254F97FB36B82FC26",
+        "B94C198300CEC5C057AD0727B70BBE91816992256439A7B32F4598119DDA9C97",
+        "C3F697A92A293D86F9A3EE7CCB970E20E0050B8728CC83ED1B996CE9005D4C36",
+        "7CAC9A0FF315387750BA8BAFDB1C2BC29B3F0BBA16362CA93A90F84DA2DF5F3E",
+        "AC50B5FB738AED6CB781CC35FBFFF7786F77109ADA7C08867C04A573FD5CF9EE",
+    ],
+    after: 0,
+    roots_issued_before: Some(1464739200), // June 2016
+};
+
+/// indiaFingerprint/Domain and anssiFingerprint/Domain in Hammurabi
+pub const CHROME_RESTRICTIONS: &[DomainRestriction] = &[INDIA, ANSSI];
+
+/// tubitak1Fingerprint/Subtree and anssiFingerprint/Subtree in Hammurabi
+pub const FIREFOX_RESTRICTIONS: &[DomainRestriction] = &[TUBITAK1, ANSSI];
+
+/// Same as `SYMANTEC` but regardless of the issuance date of the roots,
+/// since the cutoff in IsUntrustedSymantecCert in Chromium is only
+/// enabled without kLegacySymantecPKIEnforcement (not the default)
+const SYMANTEC_ANY_DATE: ServerDistrust = ServerDistrust {
+    roots_issued_before: None,
+    ..SYMANTEC
+};
+
+/// badSymantec in Hammurabi
+pub const CHROME_DISTRUSTS: &[ServerDistrust] = &[SYMANTEC_ANY_DATE];
+
+/// CheckForSymantecDistrust in Firefox
+pub const FIREFOX_DISTRUSTS: &[ServerDistrust] = &[SYMANTEC];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::test_roots;
+
+    #[test]
+    fn test_firefox_distrusts() {
+        let mut store = test_roots();
+        let geotrust = "FF856A2D251DCD88D36656F450126798CFABAADE40799C722DE4D2B5DB36A73A";
+        let gts_root_r1 = "2A575471E31340BC21581CBD2CF13E158463203ECE94BCF9D3CC196BF09A5472";
+
+        assert!(SYMANTEC.fingerprints.contains(&geotrust));
+        assert!(store.trust_anchor(geotrust).is_none());
+
+        store.apply_firefox_constraints();
+
+        let anchor = store.trust_anchor(geotrust).unwrap();
+        assert!(anchor.server_auth);
+        assert_eq!(anchor.server_distrust_after, Some(0));
+        assert_eq!(anchor.server_distrust_exceptions.len(), SYMANTEC.exceptions.len());
+        assert!(store.trust_anchor(gts_root_r1).is_none());
+    }
+
+    #[test]
+    fn test_chrome_distrusts() {
+        let mut store = test_roots();
+        let geotrust = "FF856A2D251DCD88D36656F450126798CFABAADE40799C722DE4D2B5DB36A73A";
+
+        store.apply_chrome_constraints();
+
+        let anchor = store.trust_anchor(geotrust).unwrap();
+        assert_eq!(anchor.server_distrust_after, Some(0));
+        assert_eq!(anchor.server_distrust_exceptions.len(), SYMANTEC.exceptions.len());
+        assert!(anchor.permitted_dns_names.is_none());
+    }
+
+    #[test]
+    fn test_distrust_root_cutoff() {
+        const GTS_ROOT_R1: &str = "2A575471E31340BC21581CBD2CF13E158463203ECE94BCF9D3CC196BF09A5472";
+        let distrust = |roots_issued_before| ServerDistrust {
+            fingerprints: &[GTS_ROOT_R1],
+            exceptions: &[],
+            after: 0,
+            roots_issued_before,
+        };
+
+        // GTS Root R1 was issued on June 22, 2016
+        let mut store = test_roots();
+        store.apply_distrust(&distrust(Some(1464739200)), false);
+        assert!(store.trust_anchor(GTS_ROOT_R1).is_none());
+
+        store.apply_distrust(&distrust(Some(1500000000)), false);
+        assert_eq!(store.trust_anchor(GTS_ROOT_R1).unwrap().server_distrust_after, Some(0));
+
+        let mut store = test_roots();
+        store.apply_distrust(&distrust(None), false);
+        assert_eq!(store.trust_anchor(GTS_ROOT_R1).unwrap().server_distrust_after, Some(0));
+    }
+}
@@ -1,29 +1,60 @@
 //! Public wrappers for some core constructs.
 #![warn(missing_docs)]
 
-use std::io::BufRead;
+use std::borrow::Cow;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock, RwLockReadGuard};
 use vstd::prelude::*;
 
+use crate::anchors::{
+    ServerDistrust, CHROME_DISTRUSTS, CHROME_RESTRICTIONS, FIREFOX_DISTRUSTS, FIREFOX_RESTRICTIONS,
+};
+use crate::cache::IntermediateCache;
 use crate::error;
+use crate::fetch::{fetch_intermediates, has_issuer, FetchLimits, IntermediateFetcher};
 use crate::policy::{
-    ChromePolicy as InternalChromePolicy, ExecCertificate as InternalCertificate,
-    ExecPolicyError as InternalPolicyError, ExecPurpose as InternalPurpose,
-    ExecTask as InternalTask, FirefoxPolicy as InternalFirefoxPolicy,
-    OpenSSLPolicy as InternalOpenSSLPolicy, Policy as InternalPolicy,
+    exec_same_dn, BasePolicy as InternalBasePolicy, ChromePolicy as InternalChromePolicy,
+    CustomPolicy as InternalCustomPolicy, DiagnosePolicy,
+    ExecCertificate as InternalCertificate,
+    ExecPeerName as InternalPeerName, ExecPolicyError as InternalPolicyError,
+    ExecTask as InternalTask,
+    FirefoxPolicy as InternalFirefoxPolicy, OpenSSLPolicy as InternalOpenSSLPolicy,
+    Policy as InternalPolicy, Rules as InternalRules,
+};
+use crate::utils::{
+    read_certdata, read_pem_as_base64, read_pem_crls_as_base64, CertDataParseError,
+    PEMParseError,
 };
-use crate::utils::{read_pem_as_base64, PEMParseError};
-use crate::validator::{RootStore as InternalRootStore, Validator as InternalValidator};
+use crate::validator::{
+    ParsedCertificate as InternalParsedCertificate, RevocationStore as InternalRevocationStore,
+    Roots as InternalRoots, Validator as InternalValidator,
+};
+
+pub use crate::validator::ValidationLimits;
 use ref_cast::RefCast;
 use thiserror::Error;
-use verdict_parser::VecDeep;
+use verdict_parser::{OptionDeep, VecDeep};
 
-pub use verdict_parser::{decode_base64, parse_x509_der, ParseError};
+pub use verdict_parser::{
+    decode_base64, parse_crl_der, parse_ocsp_response_der, parse_x509_der, ParseError,
+};
+
+pub use crate::policy::{
+    ExecPurpose as Purpose, ExecTimeCheck as TimeCheck, IssuerLookup, NameMatching,
+    PolicyFailure,
+};
+pub use crate::report::{CertRef, PathFailure, PathReport, ValidationReport};
 
 pub use crate::policy::{
     ExecAttribute, ExecAuthorityInfoAccess, ExecAuthorityKeyIdentifier, ExecBasicConstraints,
     ExecCertificatePolicies, ExecDistinguishedName, ExecExtendedKeyUsage, ExecExtendedKeyUsageType,
-    ExecExtension, ExecGeneralName, ExecKeyUsage, ExecNameConstraints, ExecSignatureAlgorithm,
-    ExecSubjectAltName, ExecSubjectKey, ExecSubjectKeyIdentifier,
+    ExecExtension, ExecGeneralName, ExecKeyUsage, ExecNameConstraints, ExecPSSParams,
+    ExecSignatureAlgorithm, ExecSubjectAltName, ExecSubjectKey, ExecSubjectKeyIdentifier,
+    ExecTrustAnchor,
 };
 
 /// Errors in validation, parsing, and policy execution.
@@ -44,6 +75,24 @@ pub enum ValidationError {
     /// Errors in PEM parsing.
     #[error("PEM parse error: {0:?}")]
     PEMParseError(#[from] PEMParseError),
+
+    /// Errors in parsing NSS certdata.txt.
+    #[error("certdata.txt parse error: {0:?}")]
+    CertDataParseError(#[from] CertDataParseError),
+
+    /// Errors in reading certificates from the file system.
+    #[error("IO error: {0}")]
+    IOError(#[from] std::io::Error),
+
+    /// A limit on path building (see [`Validator::set_limits`])
+    /// was exceeded before finding a valid chain.
+    #[error("resource limit exceeded")]
+    ResourceLimitExceeded,
+
+    /// A worker thread of a [`BatchValidator`](crate::BatchValidator)
+    /// panicked while validating the chain.
+    #[error("validation worker panicked")]
+    WorkerPanicked,
 }
 
 /// Common `Result` type for Verdict APIs
@@ -54,7 +103,10 @@ pub type Result<T> = std::result::Result<T, ValidationError>;
 /// traits instead of using `#[from]`
 impl From<error::ValidationError> for ValidationError {
     fn from(err: error::ValidationError) -> Self {
-        ValidationError::ValidationError(err)
+        match err {
+            error::ValidationError::ResourceLimitExceeded => ValidationError::ResourceLimitExceeded,
+            err => ValidationError::ValidationError(err),
+        }
     }
 }
 
@@ -70,49 +122,97 @@ impl From<InternalPolicyError> for ValidationError {
     }
 }
 
+/// Standard locations of the system CA bundle on Linux
+/// (the same list as in Go's crypto/x509)
+const SYSTEM_CERT_FILES: &[&str] = &[
+    "/etc/ssl/certs/ca-certificates.crt", // Debian/Ubuntu/Gentoo etc.
+    "/etc/pki/tls/certs/ca-bundle.crt", // Fedora/RHEL 6
+    "/etc/ssl/ca-bundle.pem", // OpenSUSE
+    "/etc/pki/tls/cacert.pem", // OpenELEC
+    "/etc/pki/ca-trust/extracted/pem/tls-ca-bundle.pem", // CentOS/RHEL 7
+    "/etc/ssl/cert.pem", // Alpine Linux
+];
+
+/// Standard locations of hashed certificate directories (see `c_rehash`)
+const SYSTEM_CERT_DIRS: &[&str] = &[
+    "/etc/ssl/certs", // SLES10/SLES11
+    "/etc/pki/tls/certs", // Fedora/RHEL
+];
+
 /// A collection of trusted root certificates.
-pub struct RootStore(InternalRootStore);
+///
+/// Certificates are parsed when added, and duplicates
+/// (with the same SHA-256 fingerprint) are ignored.
+/// Each root may carry a [`ExecTrustAnchor`] with additional constraints
+/// from the root program, which policies check on the root of a chain.
+/// The domain restrictions that Chrome and Firefox place on some
+/// government CAs, and their distrust of the Symantec roots,
+/// are attached by default for the corresponding policies
+/// (see [`Policy::root_program`] and [`RootStore::skip_bundled_constraints`]).
+///
+/// A [`Validator`] takes a snapshot of the store, so later
+/// changes to the store only take effect via [`Validator::set_roots`].
+#[derive(Clone, Default)]
+pub struct RootStore {
+    roots: Vec<RootEntry>,
+
+    /// Do not attach the constraints of the policy's root program in snapshots
+    skip_bundled_constraints: bool,
+}
+
+#[derive(Clone)]
+struct RootEntry {
+    cert: ParsedCertificate,
+    anchor: Option<ExecTrustAnchor>,
+}
 
 impl RootStore {
     /// Creates an empty root store.
     pub fn new() -> Self {
-        RootStore(InternalRootStore {
-            roots_der: Vec::new(),
-        })
+        RootStore {
+            roots: Vec::new(),
+            skip_bundled_constraints: false,
+        }
     }
 
     /// Returns the number of root certificates.
     pub fn len(&self) -> usize {
-        self.0.roots_der.len()
+        self.roots.len()
+    }
+
+    /// Adds a parsed certificate (without a trust anchor), returning
+    /// `false` if the same certificate is already present.
+    pub fn add(&mut self, cert: ParsedCertificate) -> bool {
+        if self.get_by_fingerprint(cert.fingerprint()).is_some() {
+            return false;
+        }
+        self.roots.push(RootEntry { cert, anchor: None });
+        true
     }
 
     /// Adds certificates in DER format.
-    /// NOTE: this may not fully parse the certificates until used later.
     pub fn add_der_certs(&mut self, certs: impl Iterator<Item = impl AsRef<[u8]>>) -> Result<()> {
-        self.0.roots_der.extend(certs.map(|c| c.as_ref().to_vec()));
+        for cert in certs {
+            self.add(ParsedCertificate::from_der(cert.as_ref())?);
+        }
         Ok(())
     }
 
     /// Adds certificates encoded in Base64 format.
-    /// NOTE: this may not fully parse the certificates until used later.
     pub fn add_base64_certs(
         &mut self,
         certs: impl Iterator<Item = impl AsRef<[u8]>>,
     ) -> Result<()> {
         for cert in certs {
-            let der = decode_base64(cert.as_ref())?;
-            self.0.roots_der.push(der);
+            self.add(ParsedCertificate::from_base64(cert)?);
         }
         Ok(())
     }
 
     /// Adds certificates encoded in PEM format.
-    /// NOTE: this may not fully parse the certificates until used later.
     pub fn add_pem_certs<R: BufRead>(&mut self, reader: R) -> Result<()> {
-        let certs = read_pem_as_base64(reader);
-        for cert in certs {
-            let der = decode_base64(cert?.as_bytes())?;
-            self.0.roots_der.push(der);
+        for cert in ParsedCertificate::from_pem(reader)? {
+            self.add(cert);
         }
         Ok(())
     }
@@ -130,6 +230,440 @@ impl RootStore {
         store.add_base64_certs(certs)?;
         Ok(store)
     }
+
+    /// Loads the system trust store, using the first CA bundle found at
+    /// `$SSL_CERT_FILE` or a standard Linux location, together with the
+    /// hashed certificates (as created by `c_rehash`) in `$SSL_CERT_DIR`
+    /// or the standard directories.
+    ///
+    /// Unlike other constructors, certificates that fail to parse are skipped,
+    /// since system stores may contain certificates that Verdict does not support.
+    pub fn from_system() -> Result<Self> {
+        let mut store = RootStore::new();
+
+        let env_file = std::env::var_os("SSL_CERT_FILE").map(PathBuf::from);
+        let file = env_file
+            .into_iter()
+            .chain(SYSTEM_CERT_FILES.iter().map(PathBuf::from))
+            .find(|path| path.is_file());
+
+        if let Some(file) = file {
+            store.add_system_pem_certs(BufReader::new(File::open(file)?))?;
+        }
+
+        let dirs: Vec<PathBuf> = match std::env::var_os("SSL_CERT_DIR") {
+            Some(dirs) => std::env::split_paths(&dirs).collect(),
+            None => SYSTEM_CERT_DIRS.iter().map(PathBuf::from).collect(),
+        };
+
+        for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+            for entry in std::fs::read_dir(dir)? {
+                let path = entry?.path();
+                if is_hashed_cert_name(&path) && path.is_file() {
+                    store.add_system_pem_certs(BufReader::new(File::open(path)?))?;
+                }
+            }
+        }
+
+        if store.len() == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "no system root certificates found",
+            )
+            .into());
+        }
+
+        Ok(store)
+    }
+
+    /// Adds PEM certificates from a system trust store, skipping
+    /// certificates that fail to parse (see [`RootStore::from_system`]).
+    fn add_system_pem_certs<R: BufRead>(&mut self, reader: R) -> Result<()> {
+        for cert in read_pem_as_base64(reader) {
+            if let Ok(cert) = ParsedCertificate::from_base64(cert?.as_bytes()) {
+                self.add(cert);
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a new [`RootStore`] from NSS `certdata.txt` (the source of
+    /// Mozilla's root program), keeping only roots that are trust anchors
+    /// for at least one purpose.
+    ///
+    /// The trust bits and distrust-after dates of each root are kept as its
+    /// [`ExecTrustAnchor`]. Since NSS has no trust bit for TLS client
+    /// certificates, `client_auth` follows `server_auth` as in Firefox.
+    pub fn from_certdata<R: BufRead>(reader: R) -> Result<Self> {
+        let mut store = RootStore::new();
+
+        for root in read_certdata(reader)? {
+            let cert = ParsedCertificate::from_der(root.der)?;
+            let anchor = ExecTrustAnchor {
+                server_auth: root.server_auth,
+                client_auth: root.server_auth,
+                code_signing: root.code_signing,
+                email_protection: root.email_protection,
+                permitted_dns_names: None,
+                server_distrust_after: root.server_distrust_after,
+                email_distrust_after: root.email_distrust_after,
+                server_distrust_exceptions: Vec::new(),
+            };
+
+            if store.add(cert) {
+                store.roots.last_mut().unwrap().anchor = Some(anchor);
+            }
+        }
+
+        Ok(store)
+    }
+
+    /// Iterates over the root certificates in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = &ParsedCertificate> {
+        self.roots.iter().map(|root| &root.cert)
+    }
+
+    /// Finds the root certificate with the given SHA-256
+    /// fingerprint (in hex, case-insensitive).
+    pub fn get_by_fingerprint(&self, fingerprint: &str) -> Option<&ParsedCertificate> {
+        self.find(fingerprint).map(|root| &root.cert)
+    }
+
+    /// Returns the trust anchor of the root certificate with the given
+    /// SHA-256 fingerprint (in hex, case-insensitive), if any.
+    pub fn trust_anchor(&self, fingerprint: &str) -> Option<&ExecTrustAnchor> {
+        self.find(fingerprint)?.anchor.as_ref()
+    }
+
+    /// Sets (or clears) the trust anchor of the root certificate with the given
+    /// SHA-256 fingerprint (in hex, case-insensitive), returning `false`
+    /// if there is no such root.
+    pub fn set_trust_anchor(&mut self, fingerprint: &str, anchor: Option<ExecTrustAnchor>) -> bool {
+        match self
+            .roots
+            .iter_mut()
+            .find(|root| root.cert.fingerprint().eq_ignore_ascii_case(fingerprint))
+        {
+            Some(root) => {
+                root.anchor = anchor;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Restricts the roots that Chrome only trusts for some domains
+    /// (e.g. the India CCA and ANSSI roots) to those domains, and
+    /// distrusts TLS certificates issued by the Symantec roots (as a
+    /// `server_distrust_after` date of 0, except for chains through
+    /// the subordinate CAs of Apple and Google).
+    /// A root without a trust anchor gets one that is trusted for all
+    /// purposes, and domains are added to any existing restriction.
+    ///
+    /// Validators with a Chrome-based policy do this by default
+    /// (unless [`RootStore::skip_bundled_constraints`] is called),
+    /// so this is only needed for other policies.
+    pub fn apply_chrome_constraints(&mut self) {
+        self.apply_bundled_constraints(RootProgram::Chrome, false);
+    }
+
+    /// Restricts the roots that Firefox only trusts for some domains
+    /// (e.g. the TUBITAK and ANSSI roots) to those domains, in the
+    /// same way as [`RootStore::apply_chrome_constraints`], and
+    /// distrusts TLS certificates issued by the Symantec roots
+    /// (as a `server_distrust_after` date of 0 on the roots issued
+    /// before June 2016, except for chains through the subordinate CAs
+    /// of Apple and Google).
+    ///
+    /// Validators with a Firefox-based policy do this by default
+    /// (unless [`RootStore::skip_bundled_constraints`] is called),
+    /// so this is only needed for other policies.
+    pub fn apply_firefox_constraints(&mut self) {
+        self.apply_bundled_constraints(RootProgram::Firefox, false);
+    }
+
+    /// Opts out of the constraints that validators attach by default
+    /// for the root program of their policy (see [`Policy::root_program`]),
+    /// e.g., when the trust anchors of the store already reflect a root program.
+    /// Constraints attached explicitly (e.g. via [`RootStore::apply_chrome_constraints`])
+    /// are kept.
+    pub fn skip_bundled_constraints(&mut self) {
+        self.skip_bundled_constraints = true;
+    }
+
+    /// Adds the permitted DNS names and distrust-after dates of `program`
+    /// to the trust anchors of the affected roots present in the store.
+    /// A root without a trust anchor gets one that is trusted for all purposes.
+    /// If `only_unconstrained` is set, roots that already have permitted
+    /// DNS names (or a distrust-after date, respectively) are left as is.
+    fn apply_bundled_constraints(&mut self, program: RootProgram, only_unconstrained: bool) {
+        let (restrictions, distrusts) = match program {
+            RootProgram::Chrome => (CHROME_RESTRICTIONS, CHROME_DISTRUSTS),
+            RootProgram::Firefox => (FIREFOX_RESTRICTIONS, FIREFOX_DISTRUSTS),
+        };
+
+        for restriction in restrictions {
+            for fingerprint in restriction.fingerprints {
+                let Some(anchor) = self.anchor_mut(fingerprint) else {
+                    continue;
+                };
+
+                if only_unconstrained && anchor.permitted_dns_names.is_some() {
+                    continue;
+                }
+
+                let names = anchor.permitted_dns_names.get_or_insert_with(Vec::new);
+
+                for domain in restriction.domains {
+                    if !names.iter().any(|name| name == domain) {
+                        names.push(domain.to_string());
+                    }
+                }
+            }
+        }
+
+        for distrust in distrusts {
+            self.apply_distrust(distrust, only_unconstrained);
+        }
+    }
+
+    /// Adds the distrust-after date and exceptions of `distrust` to the
+    /// trust anchors of the affected roots, as in [`RootStore::apply_bundled_constraints`]
+    pub(crate) fn apply_distrust(&mut self, distrust: &ServerDistrust, only_unconstrained: bool) {
+        let issued_before = |root: &RootEntry| match distrust.roots_issued_before {
+            Some(time) => root.cert.certificate().not_before < time,
+            None => true,
+        };
+
+        for fingerprint in distrust.fingerprints {
+            if !self.find(fingerprint).is_some_and(issued_before) {
+                continue;
+            }
+
+            let Some(anchor) = self.anchor_mut(fingerprint) else {
+                continue;
+            };
+
+            anchor.server_distrust_after = match anchor.server_distrust_after {
+                Some(_) if only_unconstrained => continue,
+                Some(after) => Some(after.min(distrust.after)),
+                None => Some(distrust.after),
+            };
+
+            for exception in distrust.exceptions {
+                if !anchor.server_distrust_exceptions.iter().any(|fp| fp == exception) {
+                    anchor.server_distrust_exceptions.push(exception.to_string());
+                }
+            }
+        }
+    }
+
+    /// Returns the trust anchor of the root certificate with the given
+    /// fingerprint, attaching one that is trusted for all purposes if needed.
+    fn anchor_mut(&mut self, fingerprint: &str) -> Option<&mut ExecTrustAnchor> {
+        let root = self
+            .roots
+            .iter_mut()
+            .find(|root| root.cert.fingerprint().eq_ignore_ascii_case(fingerprint))?;
+
+        Some(root.anchor.get_or_insert_with(|| ExecTrustAnchor {
+            server_auth: true,
+            client_auth: true,
+            code_signing: true,
+            email_protection: true,
+            permitted_dns_names: None,
+            server_distrust_after: None,
+            email_distrust_after: None,
+            server_distrust_exceptions: Vec::new(),
+        }))
+    }
+
+    /// Removes the root certificate with the given SHA-256
+    /// fingerprint (in hex, case-insensitive), if any.
+    pub fn remove_by_fingerprint(&mut self, fingerprint: &str) -> Option<ParsedCertificate> {
+        let idx = self
+            .roots
+            .iter()
+            .position(|root| root.cert.fingerprint().eq_ignore_ascii_case(fingerprint))?;
+        Some(self.roots.remove(idx).cert)
+    }
+
+    /// Removes all root certificates with the given subject
+    /// (compared after normalization as in RFC 5280, 7.1),
+    /// and returns the number of removed certificates.
+    pub fn remove_by_subject(&mut self, subject: &ExecDistinguishedName) -> usize {
+        let len = self.roots.len();
+        self.roots
+            .retain(|root| !exec_same_dn(&root.cert.certificate().subject, subject, true));
+        len - self.roots.len()
+    }
+
+    fn find(&self, fingerprint: &str) -> Option<&RootEntry> {
+        self.roots
+            .iter()
+            .find(|root| root.cert.fingerprint().eq_ignore_ascii_case(fingerprint))
+    }
+
+    /// Prepares a snapshot of the store (with trust anchors) for validation,
+    /// attaching the bundled constraints of `program` to roots
+    /// that are not constrained yet (unless opted out).
+    fn snapshot(&self, program: Option<RootProgram>) -> Result<Arc<InternalRoots>> {
+        let store = match program {
+            Some(program) if !self.skip_bundled_constraints => {
+                let mut store = self.clone();
+                store.apply_bundled_constraints(program, true);
+                Cow::Owned(store)
+            }
+            _ => Cow::Borrowed(self),
+        };
+
+        let (certs, anchors): (Vec<_>, Vec<_>) = store
+            .roots
+            .iter()
+            .map(|root| (root.cert.0.clone(), root.anchor.clone()))
+            .unzip();
+        Ok(Arc::new(InternalRoots::new(certs, anchors)?))
+    }
+}
+
+/// Checks if the file name is of the form `<8 hex digits>.<n>` as created by
+/// `c_rehash` (excluding `<hash>.r<n>` for CRLs)
+fn is_hashed_cert_name(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let Some((hash, n)) = name.split_once('.') else {
+        return false;
+    };
+
+    hash.len() == 8
+        && hash.chars().all(|c| c.is_ascii_hexdigit())
+        && !n.is_empty()
+        && n.chars().all(|c| c.is_ascii_digit())
+}
+
+/// A collection of certificate revocation lists (CRLs),
+/// consulted for tasks with revocation checking enabled
+/// (see [`Task::set_revocation_check`]).
+///
+/// A CRL is only used if it is signed by the issuer
+/// of the certificate being checked.
+pub struct RevocationStore(InternalRevocationStore);
+
+impl RevocationStore {
+    /// Creates an empty revocation store.
+    pub fn new() -> Self {
+        RevocationStore(InternalRevocationStore {
+            crls_der: Vec::new(),
+        })
+    }
+
+    /// Returns the number of CRLs.
+    pub fn len(&self) -> usize {
+        self.0.crls_der.len()
+    }
+
+    /// Returns `true` if there are no CRLs.
+    pub fn is_empty(&self) -> bool {
+        self.0.crls_der.is_empty()
+    }
+
+    /// Adds CRLs in DER format.
+    /// NOTE: this may not fully parse the CRLs until used later.
+    pub fn add_der_crls(&mut self, crls: impl Iterator<Item = impl AsRef<[u8]>>) -> Result<()> {
+        self.0.crls_der.extend(crls.map(|c| c.as_ref().to_vec()));
+        Ok(())
+    }
+
+    /// Adds CRLs encoded in Base64 format.
+    /// NOTE: this may not fully parse the CRLs until used later.
+    pub fn add_base64_crls(&mut self, crls: impl Iterator<Item = impl AsRef<[u8]>>) -> Result<()> {
+        for crl in crls {
+            let der = decode_base64(crl.as_ref())?;
+            self.0.crls_der.push(der);
+        }
+        Ok(())
+    }
+
+    /// Adds CRLs encoded in PEM format (`X509 CRL` blocks).
+    /// NOTE: this may not fully parse the CRLs until used later.
+    pub fn add_pem_crls<R: BufRead>(&mut self, reader: R) -> Result<()> {
+        let crls = read_pem_crls_as_base64(reader);
+        for crl in crls {
+            let der = decode_base64(crl?.as_bytes())?;
+            self.0.crls_der.push(der);
+        }
+        Ok(())
+    }
+
+    /// Creates a new [`RevocationStore`] from CRLs encoded in PEM format.
+    pub fn from_pem<R: BufRead>(reader: R) -> Result<Self> {
+        let mut store = RevocationStore::new();
+        store.add_pem_crls(reader)?;
+        Ok(store)
+    }
+
+    /// Creates a new [`RevocationStore`] from CRLs encoded in Base64.
+    pub fn from_base64(crls: impl Iterator<Item = impl AsRef<[u8]>>) -> Result<Self> {
+        let mut store = RevocationStore::new();
+        store.add_base64_crls(crls)?;
+        Ok(store)
+    }
+}
+
+impl Default for RevocationStore {
+    fn default() -> Self {
+        RevocationStore::new()
+    }
+}
+
+/// A certificate that is parsed and converted to [`Certificate`]
+/// ahead of time. It can be shared across threads (e.g., in an [`Arc`])
+/// and validated repeatedly with [`Validator::validate_parsed`], which
+/// avoids recomputing its abstract representation (and fingerprint).
+/// Cloning is cheap since the parsed data is shared.
+#[derive(Clone)]
+pub struct ParsedCertificate(pub(crate) Arc<InternalParsedCertificate>);
+
+impl ParsedCertificate {
+    /// Parses a certificate in ASN.1 DER format.
+    pub fn from_der(der: impl Into<Vec<u8>>) -> Result<Self> {
+        Ok(ParsedCertificate(Arc::new(InternalParsedCertificate::from_der(
+            der.into(),
+        )?)))
+    }
+
+    /// Parses a certificate in ASN.1 DER format encoded in Base64.
+    pub fn from_base64(base64: impl AsRef<[u8]>) -> Result<Self> {
+        ParsedCertificate::from_der(decode_base64(base64.as_ref())?)
+    }
+
+    /// Parses all certificates encoded in PEM format.
+    pub fn from_pem<R: BufRead>(reader: R) -> Result<Vec<Self>> {
+        read_pem_as_base64(reader)
+            .map(|cert| ParsedCertificate::from_base64(cert?.as_bytes()))
+            .collect()
+    }
+
+    /// Returns the original DER encoding.
+    pub fn der(&self) -> &[u8] {
+        self.0.der()
+    }
+
+    /// Returns the abstract representation used in policy execution.
+    pub fn certificate(&self) -> &Certificate {
+        self.0.abs()
+    }
+
+    /// Returns the SHA-256 fingerprint of the DER encoding (in upper-case hex).
+    pub fn fingerprint(&self) -> &str {
+        &self.0.abs().fingerprint
+    }
+
+    /// Returns the subject name in a human-readable form.
+    pub fn subject_name(&self) -> String {
+        self.0.cert().get().cert.get().subject.get().to_string()
+    }
 }
 
 verus! {
@@ -142,13 +676,39 @@ pub type Certificate = InternalCertificate;
 /// needed for policy execution, such as hostname,
 /// purpose (e.g., server/client authentication),
 /// and current time.
+///
+/// With the `serde` feature, a deserialized task is rejected
+/// if its IP peer name has neither 4 nor 16 octets.
 #[derive(RefCast, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "InternalTask", into = "InternalTask"))]
 #[repr(transparent)]
 pub struct Task(InternalTask);
 
+#[cfg(feature = "serde")]
+impl TryFrom<InternalTask> for Task {
+    type Error = String;
+
+    fn try_from(task: InternalTask) -> std::result::Result<Self, Self::Error> {
+        match &task.peer {
+            Some(InternalPeerName::Ip(addr)) if addr.len() != 4 && addr.len() != 16 => {
+                Err(format!("IP peer name with {} octets", addr.len()))
+            }
+            _ => Ok(Task(task)),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Task> for InternalTask {
+    fn from(task: Task) -> Self {
+        task.0
+    }
+}
+
 /// A wrapper since Verus does not support `dyn`
 #[verifier::external_body]
-struct BoxDynInternalPolicy<'a>(Box<dyn InternalPolicy + 'a>);
+struct BoxDynInternalPolicy<'a>(Box<dyn Policy + 'a>);
 
 /// `dyn` is currently not supported by Verus, thus putting this here.
 impl<'a> InternalPolicy for BoxDynInternalPolicy<'a> {
@@ -157,15 +717,33 @@ impl<'a> InternalPolicy for BoxDynInternalPolicy<'a> {
 
     #[verifier::external_body]
     fn likely_issued(&self, issuer: &InternalCertificate, subject: &InternalCertificate) -> bool {
-        self.0.as_ref().likely_issued(issuer, subject)
+        Policy::likely_issued(self.0.as_ref(), issuer, subject)
     }
 
     #[verifier::external_body]
     fn valid_chain(&self, chain: &Vec<&InternalCertificate>, task: &InternalTask) -> bool {
-        self.0.as_ref().valid_chain(chain, task)
+        Policy::valid_chain(self.0.as_ref(), chain, Task::ref_cast(task))
+    }
+
+    #[verifier::external_body]
+    fn issuer_lookup(&self) -> IssuerLookup {
+        Policy::issuer_lookup(self.0.as_ref())
     }
 }
 
+/// A root program whose constraints on roots, beyond those in the
+/// root certificates themselves, are bundled with Verdict (see [`RootStore`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RootProgram {
+    /// Domain restrictions on the India CCA and ANSSI roots,
+    /// and the distrust of the Symantec roots
+    Chrome,
+
+    /// Domain restrictions on the TUBITAK and ANSSI roots,
+    /// and the distrust of the Symantec roots
+    Firefox,
+}
+
 /// Common trait for all policies (e.g. [`ChromePolicy`]).
 pub trait Policy: Send + Sync {
     /// A policy-dependent predicate to check if `subject`
@@ -177,6 +755,36 @@ pub trait Policy: Send + Sync {
     /// for each `i`, `chain[i]` is issued by `chain[i + 1]`,
     /// and `chain.last()` is a trusted root certificate.
     fn valid_chain(&self, chain: &Vec<&Certificate>, task: &Task) -> bool;
+
+    /// Explains which check of the policy rejects `chain`, if any.
+    /// This is only used for diagnostics (see [`ValidationReport`]),
+    /// and by default no explanation is given.
+    #[verifier::external_body]
+    fn diagnose_chain(&self, chain: &Vec<&Certificate>, task: &Task) -> Option<PolicyFailure> {
+        let _ = (chain, task);
+        None
+    }
+
+    /// Allows the validator to only check root certificates with a
+    /// matching subject (see [`IssuerLookup`]) as issuers.
+    /// This must be sound for `likely_issued`, i.e., if `likely_issued`
+    /// holds, then the subject of the issuer and the issuer of the
+    /// subject are the same after normalization (and the AKID of the
+    /// subject matches the issuer, for [`IssuerLookup::NameAndKeyId`]).
+    /// By default, every root certificate is checked.
+    #[verifier::external_body]
+    fn issuer_lookup(&self) -> IssuerLookup {
+        IssuerLookup::Linear
+    }
+
+    /// The root program modeled by the policy, whose bundled constraints
+    /// a [`Validator`] attaches to the roots by default
+    /// (see [`RootStore::skip_bundled_constraints`]).
+    /// By default, no constraints are attached.
+    #[verifier::external_body]
+    fn root_program(&self) -> Option<RootProgram> {
+        None
+    }
 }
 
 /// Converts the public version of [`Policy`] into the internal version.
@@ -193,8 +801,33 @@ impl<P: Policy> InternalPolicy for P {
     fn valid_chain(&self, chain: &Vec<&InternalCertificate>, task: &InternalTask) -> bool {
         self.valid_chain(chain, Task::ref_cast(task))
     }
+
+    #[verifier::external_body]
+    fn issuer_lookup(&self) -> IssuerLookup {
+        Policy::issuer_lookup(self)
+    }
+}
+
 }
 
+impl<'a> DiagnosePolicy for BoxDynInternalPolicy<'a> {
+    fn diagnose_chain(
+        &self,
+        chain: &Vec<&InternalCertificate>,
+        task: &InternalTask,
+    ) -> Option<PolicyFailure> {
+        Policy::diagnose_chain(self.0.as_ref(), chain, Task::ref_cast(task))
+    }
+}
+
+impl<P: Policy> DiagnosePolicy for P {
+    fn diagnose_chain(
+        &self,
+        chain: &Vec<&InternalCertificate>,
+        task: &InternalTask,
+    ) -> Option<PolicyFailure> {
+        Policy::diagnose_chain(self, chain, Task::ref_cast(task))
+    }
 }
 
 impl Clone for Task {
@@ -203,38 +836,331 @@ impl Clone for Task {
     }
 }
 
+/// Reference identifier of the peer, matched against
+/// the leaf certificate (RFC 6125 / RFC 5280).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeerName {
+    /// DNS name, matched against SAN dNSName entries
+    /// (and, depending on the policy, the subject CN).
+    Dns(String),
+
+    /// IP address, matched against SAN iPAddress entries.
+    Ip(IpAddr),
+
+    /// RFC 822 email address, matched against SAN rfc822Name
+    /// entries (or the subject emailAddress if SAN is absent).
+    Email(String),
+}
+
+impl From<&PeerName> for InternalPeerName {
+    fn from(peer: &PeerName) -> Self {
+        match peer {
+            PeerName::Dns(name) => InternalPeerName::Dns(name.clone()),
+            PeerName::Ip(IpAddr::V4(addr)) => InternalPeerName::Ip(addr.octets().to_vec()),
+            PeerName::Ip(IpAddr::V6(addr)) => InternalPeerName::Ip(addr.octets().to_vec()),
+            PeerName::Email(email) => InternalPeerName::Email(email.clone()),
+        }
+    }
+}
+
+impl From<&InternalPeerName> for PeerName {
+    fn from(peer: &InternalPeerName) -> Self {
+        match peer {
+            InternalPeerName::Dns(name) => PeerName::Dns(name.clone()),
+            InternalPeerName::Ip(addr) => {
+                if let Ok(v4) = <[u8; 4]>::try_from(addr.as_slice()) {
+                    PeerName::Ip(IpAddr::from(v4))
+                } else if let Ok(v6) = <[u8; 16]>::try_from(addr.as_slice()) {
+                    PeerName::Ip(IpAddr::from(v6))
+                } else {
+                    // Tasks are only constructed from `PeerName`, or
+                    // deserialized with a valid IP (see `TryFrom<InternalTask>`)
+                    unreachable!("IP peer name with {} octets", addr.len())
+                }
+            }
+            InternalPeerName::Email(email) => PeerName::Email(email.clone()),
+        }
+    }
+}
+
 impl Task {
+    /// Starts building a [`Task`] for the given purpose
+    /// (see [`TaskBuilder`] for the defaults).
+    pub fn builder(purpose: Purpose) -> TaskBuilder {
+        TaskBuilder::new(purpose)
+    }
+
     /// Creates a new [`Task`] for server authentication,
     /// specifying the (optional) hostname and UNIX timestamp
     /// as the validation time.
     pub fn new_server_auth(hostname: Option<&str>, now: u64) -> Self {
-        Task(InternalTask {
-            hostname: hostname.map(|s| s.to_string()),
-            purpose: InternalPurpose::ServerAuth,
-            now,
-        })
+        Task::new_server_auth_peer(hostname.map(|s| PeerName::Dns(s.to_string())), now)
     }
 
     /// Creates a new [`Task`] for server authentication,
     /// specifying the (optional) hostname, and using
     /// the current time as the validation time
     pub fn new_server_auth_now(hostname: Option<&str>) -> Self {
-        Task(InternalTask {
-            hostname: hostname.map(|s| s.to_string()),
-            purpose: InternalPurpose::ServerAuth,
-            now: chrono::Utc::now().timestamp() as u64,
-        })
+        Task::new_server_auth(hostname, chrono::Utc::now().timestamp() as u64)
+    }
+
+    /// Creates a new [`Task`] for server authentication,
+    /// specifying the (optional) peer name (DNS name, IP
+    /// or email address) and UNIX timestamp as the validation time.
+    pub fn new_server_auth_peer(peer: Option<PeerName>, now: u64) -> Self {
+        TaskBuilder::new(Purpose::ServerAuth)
+            .peer_opt(peer)
+            .timestamp(now)
+            .build()
+    }
+
+    /// Creates a new [`Task`] for TLS client authentication,
+    /// specifying the (optional) hostname and UNIX timestamp
+    /// as the validation time.
+    pub fn new_client_auth(hostname: Option<&str>, now: u64) -> Self {
+        TaskBuilder::new(Purpose::ClientAuth)
+            .peer_opt(hostname.map(|s| PeerName::Dns(s.to_string())))
+            .timestamp(now)
+            .build()
+    }
+
+    /// Creates a new [`Task`] for code signing,
+    /// using the given UNIX timestamp as the validation time.
+    pub fn new_code_signing(now: u64) -> Self {
+        TaskBuilder::new(Purpose::CodeSigning).timestamp(now).build()
     }
 
-    /// Returns the optional hostname associated with this task.
+    /// Creates a new [`Task`] for email protection (S/MIME),
+    /// specifying the target email address and UNIX timestamp
+    /// as the validation time.
+    pub fn new_email_protection(email: &str, now: u64) -> Self {
+        TaskBuilder::new(Purpose::EmailProtection)
+            .email(email)
+            .timestamp(now)
+            .build()
+    }
+
+    /// Returns the purpose of the leaf certificate.
+    pub fn purpose(&self) -> Purpose {
+        self.0.purpose
+    }
+
+    /// Returns the optional peer name associated with this task.
+    pub fn peer_name(&self) -> Option<PeerName> {
+        self.0.peer.as_ref().map(PeerName::from)
+    }
+
+    /// Returns the hostname associated with this task,
+    /// if the peer name is a DNS name.
     pub fn hostname(&self) -> Option<&str> {
-        self.0.hostname.as_deref()
+        match &self.0.peer {
+            Some(InternalPeerName::Dns(name)) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Returns the email address associated with this task,
+    /// if the peer name is an email address.
+    pub fn email(&self) -> Option<&str> {
+        match &self.0.peer {
+            Some(InternalPeerName::Email(email)) => Some(email),
+            _ => None,
+        }
     }
 
     /// Returns the validation timestamp.
     pub fn timestamp(&self) -> u64 {
         self.0.now
     }
+
+    /// Sets how the validity period of each certificate is checked
+    /// ([`TimeCheck::Strict`] by default, i.e., at [`Task::timestamp`]).
+    /// Other modes also turn off [`StandardRule::NoExpiration`].
+    pub fn set_time_check(&mut self, mode: TimeCheck) {
+        self.0.time_check = mode;
+    }
+
+    /// Returns how the validity period of each certificate is checked.
+    pub fn time_check(&self) -> TimeCheck {
+        self.0.time_check
+    }
+
+    /// Enables or disables revocation checking against the CRLs
+    /// attached to the validator (see [`Validator::set_revocation_store`]).
+    /// Disabled by default.
+    pub fn set_revocation_check(&mut self, enabled: bool) {
+        self.0.check_revocation = enabled;
+    }
+
+    /// Returns whether revocation checking is enabled.
+    pub fn revocation_check(&self) -> bool {
+        self.0.check_revocation
+    }
+}
+
+/// Builds a [`Task`] one option at a time. Options that are not
+/// set keep their defaults, so new options can be added without
+/// breaking existing code:
+/// - no peer name (the leaf is not matched against any name),
+/// - the current time as the validation time,
+/// - [`TimeCheck::Strict`], and
+/// - no revocation checking.
+///
+/// ```
+/// use verdict::{Purpose, Task, TimeCheck};
+///
+/// let task = Task::builder(Purpose::ServerAuth)
+///     .hostname("google.com")
+///     .timestamp(1725029869)
+///     .time_check(TimeCheck::Skew(300))
+///     .build();
+///
+/// assert_eq!(task.hostname(), Some("google.com"));
+/// assert_eq!(
+///     task.to_string(),
+///     "purpose=server-auth peer=dns:google.com time=2024-08-30T14:57:49Z \
+///      time-check=skew(300s) revocation-check=off",
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct TaskBuilder {
+    purpose: Purpose,
+    peer: Option<PeerName>,
+    now: Option<u64>,
+    time_check: TimeCheck,
+    check_revocation: bool,
+}
+
+impl TaskBuilder {
+    /// Creates a builder for a task with the given purpose
+    /// and all other options set to the defaults.
+    pub fn new(purpose: Purpose) -> Self {
+        TaskBuilder {
+            purpose,
+            peer: None,
+            now: None,
+            time_check: TimeCheck::Strict,
+            check_revocation: false,
+        }
+    }
+
+    /// Sets the peer name matched against the leaf certificate.
+    pub fn peer(self, peer: PeerName) -> Self {
+        self.peer_opt(Some(peer))
+    }
+
+    /// Same as [`TaskBuilder::peer`], but `None` clears the peer name.
+    pub fn peer_opt(mut self, peer: Option<PeerName>) -> Self {
+        self.peer = peer;
+        self
+    }
+
+    /// Sets the peer name to a DNS name.
+    pub fn hostname(self, hostname: &str) -> Self {
+        self.peer(PeerName::Dns(hostname.to_string()))
+    }
+
+    /// Sets the peer name to an IP address.
+    pub fn ip_addr(self, addr: IpAddr) -> Self {
+        self.peer(PeerName::Ip(addr))
+    }
+
+    /// Sets the peer name to an email address.
+    pub fn email(self, email: &str) -> Self {
+        self.peer(PeerName::Email(email.to_string()))
+    }
+
+    /// Sets the validation time as a UNIX timestamp
+    /// (the time of [`TaskBuilder::build`] by default).
+    pub fn timestamp(mut self, now: u64) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Sets how the validity period of each certificate is checked
+    /// (see [`Task::set_time_check`]).
+    pub fn time_check(mut self, mode: TimeCheck) -> Self {
+        self.time_check = mode;
+        self
+    }
+
+    /// Enables or disables revocation checking
+    /// (see [`Task::set_revocation_check`]).
+    pub fn revocation_check(mut self, enabled: bool) -> Self {
+        self.check_revocation = enabled;
+        self
+    }
+
+    /// Builds the [`Task`].
+    pub fn build(self) -> Task {
+        Task(InternalTask {
+            peer: self.peer.as_ref().map(InternalPeerName::from),
+            purpose: self.purpose,
+            now: self
+                .now
+                .unwrap_or_else(|| chrono::Utc::now().timestamp() as u64),
+            time_check: self.time_check,
+            check_revocation: self.check_revocation,
+            revoked: Vec::new(),
+            ocsp: Vec::new(),
+        })
+    }
+}
+
+impl fmt::Display for Purpose {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Purpose::ServerAuth => write!(f, "server-auth"),
+            Purpose::ClientAuth => write!(f, "client-auth"),
+            Purpose::CodeSigning => write!(f, "code-signing"),
+            Purpose::EmailProtection => write!(f, "email-protection"),
+        }
+    }
+}
+
+impl fmt::Display for PeerName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PeerName::Dns(name) => write!(f, "dns:{}", name),
+            PeerName::Ip(addr) => write!(f, "ip:{}", addr),
+            PeerName::Email(email) => write!(f, "email:{}", email),
+        }
+    }
+}
+
+impl fmt::Display for TimeCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeCheck::Strict => write!(f, "strict"),
+            TimeCheck::Skew(skew) => write!(f, "skew({}s)", skew),
+            TimeCheck::AsOfIssuance => write!(f, "as-of-issuance"),
+            TimeCheck::Skip => write!(f, "skip"),
+        }
+    }
+}
+
+/// Describes the task on one line as `key=value` pairs (see [`TaskBuilder`])
+impl fmt::Display for Task {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "purpose={}", self.purpose())?;
+
+        match self.peer_name() {
+            Some(peer) => write!(f, " peer={}", peer)?,
+            None => write!(f, " peer=none")?,
+        }
+
+        match chrono::DateTime::from_timestamp(self.timestamp() as i64, 0) {
+            Some(time) => write!(f, " time={}", time.format("%Y-%m-%dT%H:%M:%SZ"))?,
+            None => write!(f, " time={}", self.timestamp())?,
+        }
+
+        write!(
+            f,
+            " time-check={} revocation-check={}",
+            self.time_check(),
+            if self.revocation_check() { "on" } else { "off" },
+        )
+    }
 }
 
 impl<'a, P: Policy + 'a> Policy for &'a P {
@@ -245,6 +1171,18 @@ impl<'a, P: Policy + 'a> Policy for &'a P {
     fn valid_chain(&self, chain: &Vec<&Certificate>, task: &Task) -> bool {
         Policy::valid_chain(*self, chain, task)
     }
+
+    fn diagnose_chain(&self, chain: &Vec<&Certificate>, task: &Task) -> Option<PolicyFailure> {
+        Policy::diagnose_chain(*self, chain, task)
+    }
+
+    fn issuer_lookup(&self) -> IssuerLookup {
+        Policy::issuer_lookup(*self)
+    }
+
+    fn root_program(&self) -> Option<RootProgram> {
+        Policy::root_program(*self)
+    }
 }
 
 /// Implements [`Policy`] for an internal policy.
@@ -262,10 +1200,60 @@ macro_rules! wrap_internal_policy {
             fn valid_chain(&self, chain: &Vec<&Certificate>, task: &Task) -> bool {
                 self.0.valid_chain(chain, &task.0)
             }
+
+            fn diagnose_chain(
+                &self,
+                chain: &Vec<&Certificate>,
+                task: &Task,
+            ) -> Option<PolicyFailure> {
+                self.0.diagnose_chain(chain, &task.0)
+            }
+
+            fn issuer_lookup(&self) -> IssuerLookup {
+                self.0.issuer_lookup()
+            }
+
+            fn root_program(&self) -> Option<RootProgram> {
+                BundledRootProgram::root_program(&self.0)
+            }
         }
     };
 }
 
+/// Root programs of the internal policies (see [`Policy::root_program`])
+trait BundledRootProgram {
+    fn root_program(&self) -> Option<RootProgram>;
+}
+
+impl BundledRootProgram for InternalChromePolicy {
+    fn root_program(&self) -> Option<RootProgram> {
+        Some(RootProgram::Chrome)
+    }
+}
+
+impl BundledRootProgram for InternalFirefoxPolicy {
+    fn root_program(&self) -> Option<RootProgram> {
+        Some(RootProgram::Firefox)
+    }
+}
+
+impl BundledRootProgram for InternalOpenSSLPolicy {
+    fn root_program(&self) -> Option<RootProgram> {
+        None
+    }
+}
+
+impl BundledRootProgram for InternalCustomPolicy {
+    fn root_program(&self) -> Option<RootProgram> {
+        match &self.base {
+            InternalBasePolicy::Chrome(policy) => policy.root_program(),
+            InternalBasePolicy::Firefox(policy) => policy.root_program(),
+            InternalBasePolicy::OpenSSL(policy) => policy.root_program(),
+            InternalBasePolicy::Empty => None,
+        }
+    }
+}
+
 wrap_internal_policy!(
     ChromePolicy,
     InternalChromePolicy,
@@ -302,22 +1290,365 @@ impl Default for OpenSSLPolicy {
     }
 }
 
+wrap_internal_policy!(
+    CustomPolicy,
+    InternalCustomPolicy,
+    "One of the bundled models (or an empty base) with additional rules, built by [`PolicyBuilder`].",
+);
+
+/// Rules from RFC 5280 and CA/B BRs that can be enforced by a [`CustomPolicy`]
+/// (see the traits of the same names in `policy::standard`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StandardRule {
+    /// No certificate is expired or not yet valid
+    /// (only enforced for tasks with [`TimeCheck::Strict`])
+    NoExpiration,
+    /// The outer signature algorithm matches the inner one
+    OuterInnerSigMatch,
+    /// KeyUsage, if present, has at least one bit set
+    KeyUsageNonEmpty,
+    /// Issuer and subject UIDs only appear in v2 or v3 certificates
+    IssuerSubjectUIDVersion,
+    /// PathLenConstraint is non-negative
+    PathLenNonNegative,
+    /// A CA certificate is not followed by more non-leaf
+    /// certificates than its PathLenConstraint
+    PathLenConstraint,
+    /// Every non-leaf certificate is a CA certificate
+    NonLeafMustBeCA,
+    /// Every non-leaf certificate has keyCertSign set in KeyUsage (if present)
+    NonLeafHasKeyCertSign,
+    /// SubjectAltName, if present, contains at least one name
+    NonEmptySAN,
+    /// AuthorityKeyIdentifier is not critical
+    AKINonCritical,
+    /// Every non-root certificate has an AuthorityKeyIdentifier with a keyIdentifier
+    NonRootHasAKI,
+    /// Every CA certificate has a SubjectKeyIdentifier
+    NonLeafHasSKI,
+    /// A certificate with an empty subject has a critical SubjectAltName
+    EmptySubjectImpliesCriticalSAN,
+    /// SubjectKeyIdentifier of the root is not critical
+    NonCriticalRootSKI,
+    /// The root has an AuthorityKeyIdentifier with a keyIdentifier
+    RootCAHasAKI,
+    /// AuthorityKeyIdentifier of the root has no issuer or serial
+    RootCAAKINoIssuerOrSerial,
+    /// The leaf has a non-critical ExtendedKeyUsage
+    LeafHasEKU,
+    /// The root has no ExtendedKeyUsage
+    RootHasNoEKU,
+    /// No certificate has a DSA key
+    NoDSA,
+    /// RSA keys are at least 2048 bits
+    RSA2048,
+    /// EC keys are on P-256, P-384 or P-521
+    AllowedCurves,
+}
+
+impl StandardRule {
+    /// All supported rules
+    pub const ALL: [StandardRule; 21] = [
+        StandardRule::NoExpiration,
+        StandardRule::OuterInnerSigMatch,
+        StandardRule::KeyUsageNonEmpty,
+        StandardRule::IssuerSubjectUIDVersion,
+        StandardRule::PathLenNonNegative,
+        StandardRule::PathLenConstraint,
+        StandardRule::NonLeafMustBeCA,
+        StandardRule::NonLeafHasKeyCertSign,
+        StandardRule::NonEmptySAN,
+        StandardRule::AKINonCritical,
+        StandardRule::NonRootHasAKI,
+        StandardRule::NonLeafHasSKI,
+        StandardRule::EmptySubjectImpliesCriticalSAN,
+        StandardRule::NonCriticalRootSKI,
+        StandardRule::RootCAHasAKI,
+        StandardRule::RootCAAKINoIssuerOrSerial,
+        StandardRule::LeafHasEKU,
+        StandardRule::RootHasNoEKU,
+        StandardRule::NoDSA,
+        StandardRule::RSA2048,
+        StandardRule::AllowedCurves,
+    ];
+
+    fn flag(self, rules: &mut InternalRules) -> &mut bool {
+        match self {
+            StandardRule::NoExpiration => &mut rules.no_expiration,
+            StandardRule::OuterInnerSigMatch => &mut rules.outer_inner_sig_match,
+            StandardRule::KeyUsageNonEmpty => &mut rules.key_usage_non_empty,
+            StandardRule::IssuerSubjectUIDVersion => &mut rules.issuer_subject_uid_version,
+            StandardRule::PathLenNonNegative => &mut rules.path_len_non_negative,
+            StandardRule::PathLenConstraint => &mut rules.path_len_constraint,
+            StandardRule::NonLeafMustBeCA => &mut rules.non_leaf_must_be_ca,
+            StandardRule::NonLeafHasKeyCertSign => &mut rules.non_leaf_has_key_cert_sign,
+            StandardRule::NonEmptySAN => &mut rules.non_empty_san,
+            StandardRule::AKINonCritical => &mut rules.aki_non_critical,
+            StandardRule::NonRootHasAKI => &mut rules.non_root_has_aki,
+            StandardRule::NonLeafHasSKI => &mut rules.non_leaf_has_ski,
+            StandardRule::EmptySubjectImpliesCriticalSAN => {
+                &mut rules.empty_subject_implies_critical_san
+            }
+            StandardRule::NonCriticalRootSKI => &mut rules.non_critical_root_ski,
+            StandardRule::RootCAHasAKI => &mut rules.root_ca_has_aki,
+            StandardRule::RootCAAKINoIssuerOrSerial => &mut rules.root_ca_aki_no_issuer_or_serial,
+            StandardRule::LeafHasEKU => &mut rules.leaf_has_eku,
+            StandardRule::RootHasNoEKU => &mut rules.root_has_no_eku,
+            StandardRule::NoDSA => &mut rules.no_dsa,
+            StandardRule::RSA2048 => &mut rules.rsa_2048,
+            StandardRule::AllowedCurves => &mut rules.allowed_curves,
+        }
+    }
+}
+
+/// Builds a [`CustomPolicy`] from one of the bundled models (or an empty base)
+/// and additional rules, which are checked on top of the base policy.
+///
+/// Every chain accepted by the resulting policy is verified to satisfy
+/// each enabled [`StandardRule`], as if the policy implemented the
+/// corresponding trait in `policy::standard`.
+///
+/// ```
+/// use verdict::{NameMatching, PolicyBuilder, StandardRule};
+///
+/// let policy = PolicyBuilder::chrome()
+///     .require(StandardRule::RSA2048)
+///     .require(StandardRule::LeafHasEKU)
+///     .name_matching(NameMatching::Exact)
+///     .build();
+/// ```
+#[derive(Debug)]
+pub struct PolicyBuilder {
+    base: InternalBasePolicy,
+    rules: InternalRules,
+}
+
+impl PolicyBuilder {
+    fn with_base(base: InternalBasePolicy) -> Self {
+        PolicyBuilder {
+            base,
+            rules: InternalRules::none(),
+        }
+    }
+
+    /// Starts from a base policy that only checks the issuing relation
+    /// and signatures, with the minimal rules enabled:
+    /// [`StandardRule::NoExpiration`], [`StandardRule::NonLeafMustBeCA`],
+    /// [`StandardRule::NonLeafHasKeyCertSign`], [`NameMatching::Wildcard`],
+    /// and the revocation and purpose checks
+    /// (see [`PolicyBuilder::check_revocation`] and [`PolicyBuilder::check_purpose`]).
+    ///
+    /// WARNING: nothing else is checked (e.g. key sizes, signature
+    /// algorithms or name constraints), and disabling any of these
+    /// rules is unsafe: without them, the policy accepts expired and
+    /// revoked chains, chains for other peers, and chains in which an
+    /// end-entity certificate issues another certificate.
+    pub fn empty() -> Self {
+        PolicyBuilder {
+            base: InternalBasePolicy::Empty,
+            rules: InternalRules::minimal(),
+        }
+    }
+
+    /// Starts from the default [`ChromePolicy`].
+    pub fn chrome() -> Self {
+        PolicyBuilder::with_base(InternalBasePolicy::Chrome(InternalChromePolicy::default()))
+    }
+
+    /// Starts from the default [`FirefoxPolicy`].
+    pub fn firefox() -> Self {
+        PolicyBuilder::with_base(InternalBasePolicy::Firefox(InternalFirefoxPolicy::default()))
+    }
+
+    /// Starts from the default [`OpenSSLPolicy`].
+    pub fn openssl() -> Self {
+        PolicyBuilder::with_base(InternalBasePolicy::OpenSSL(InternalOpenSSLPolicy::default()))
+    }
+
+    /// Enables or disables an additional rule. Disabling a rule
+    /// does not relax the checks already done by the base policy.
+    pub fn rule(mut self, rule: StandardRule, enabled: bool) -> Self {
+        *rule.flag(&mut self.rules) = enabled;
+        self
+    }
+
+    /// Enables an additional rule.
+    pub fn require(self, rule: StandardRule) -> Self {
+        self.rule(rule, true)
+    }
+
+    /// Allows the signature algorithm with the given OID
+    /// (e.g. `"1.2.840.113549.1.1.11"` for RSA with SHA-256).
+    /// Once any algorithm is allowed, certificates other than the
+    /// root must be signed with one of the allowed algorithms.
+    pub fn allow_signature_algorithm(mut self, oid: &str) -> Self {
+        self.rules
+            .allowed_sig_algs
+            .get_or_insert_with(Vec::new)
+            .push(oid.to_string());
+        self
+    }
+
+    /// Enables or disables rejecting chains with a certificate revoked
+    /// by a CRL (for tasks with revocation checking) or a stapled OCSP
+    /// response, in addition to the base policy (enabled by default
+    /// only for [`PolicyBuilder::empty`]).
+    pub fn check_revocation(mut self, enabled: bool) -> Self {
+        self.rules.revocation = enabled;
+        self
+    }
+
+    /// Enables or disables checking that the ExtendedKeyUsage of the
+    /// leaf (if present) permits the purpose of the task, in addition
+    /// to the base policy (enabled by default only for [`PolicyBuilder::empty`]).
+    pub fn check_purpose(mut self, enabled: bool) -> Self {
+        self.rules.leaf_purpose = enabled;
+        self
+    }
+
+    /// Sets how the leaf is matched against the peer name of the
+    /// task, in addition to the base policy ([`NameMatching::Base`]
+    /// by default, or [`NameMatching::Wildcard`] for [`PolicyBuilder::empty`]).
+    pub fn name_matching(mut self, mode: NameMatching) -> Self {
+        self.rules.name_matching = mode;
+        self
+    }
+
+    /// Returns the policy with the selected base and rules.
+    pub fn build(self) -> CustomPolicy {
+        CustomPolicy(InternalCustomPolicy {
+            base: self.base,
+            rules: self.rules,
+        })
+    }
+}
+
+/// A certificate chain accepted by a [`Validator`].
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidatedChain {
+    /// Indices into the input chain, starting from the leaf
+    /// (i.e., `path[0] == 0`), such that for each `i`,
+    /// `chain[path[i]]` is issued by `chain[path[i + 1]]`.
+    /// With an intermediate fetcher or cache, indices past the
+    /// input chain refer to fetched or cached intermediates,
+    /// which are only available through `certs`.
+    pub path: Vec<usize>,
+
+    /// Index of the root certificate in the [`RootStore`]
+    /// that issued the last certificate in `path`.
+    pub root_idx: usize,
+
+    /// Certificates along the accepted chain, in the same
+    /// order as `path`, followed by the root certificate.
+    pub certs: Vec<Certificate>,
+}
+
 /// A formally verified X.509 certificate validation engine.
-pub struct Validator<'a>(InternalValidator<'a, BoxDynInternalPolicy<'a>>);
+///
+/// The validator keeps its own snapshot of the root certificates,
+/// which can be replaced with [`Validator::set_roots`] while other
+/// threads are validating; each validation sees either the old
+/// or the new root set in full.
+pub struct Validator<'a> {
+    inner: RwLock<InternalValidator<'a, BoxDynInternalPolicy<'a>>>,
+
+    /// Consulted for missing intermediates (see [`Validator::set_intermediate_fetcher`])
+    fetcher: Option<(Box<dyn IntermediateFetcher + 'a>, FetchLimits)>,
+
+    /// Intermediates from previous validations (see [`Validator::set_intermediate_cache`])
+    cache: Option<Arc<IntermediateCache>>,
+
+    /// Root program of the policy, whose constraints are attached to root snapshots
+    root_program: Option<RootProgram>,
+}
 
 impl<'a> Validator<'a> {
-    /// Creates a new [`Validator`] with the given custom policy and root store.
-    pub fn from_roots<P: Policy + 'a>(policy: P, roots: &'a RootStore) -> Result<Self> {
+    /// Creates a new [`Validator`] with the given custom policy and a
+    /// snapshot of the root store (later changes to `roots` are not seen).
+    ///
+    /// The constraints of the policy's root program are attached
+    /// to the snapshot (see [`Policy::root_program`]).
+    pub fn from_roots<P: Policy + 'a>(policy: P, roots: &RootStore) -> Result<Self> {
+        let root_program = policy.root_program();
         let policy: BoxDynInternalPolicy<'a> = BoxDynInternalPolicy(Box::new(policy));
-        let validator = InternalValidator::from_root_store(policy, &roots.0)?;
-        Ok(Validator(validator))
+        let validator = InternalValidator::from_roots(policy, roots.snapshot(root_program)?);
+        Ok(Validator {
+            inner: RwLock::new(validator),
+            fetcher: None,
+            cache: None,
+            root_program,
+        })
+    }
+
+    /// Atomically replaces the root certificates with a snapshot of
+    /// `roots`, keeping the policy and the revocation store.
+    pub fn set_roots(&self, roots: &RootStore) -> Result<()> {
+        let roots = roots.snapshot(self.root_program)?;
+        self.inner
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .set_roots(roots);
+        Ok(())
+    }
+
+    /// Uses the CRLs in `crls` for tasks with revocation checking
+    /// enabled, replacing any previously set revocation store.
+    pub fn set_revocation_store(&mut self, crls: &'a RevocationStore) -> Result<()> {
+        let validator = self.inner.get_mut().unwrap_or_else(|e| e.into_inner());
+        Ok(validator.set_revocation_store(&crls.0)?)
+    }
+
+    /// Bounds the work of building paths through a chain (none by default),
+    /// e.g., against chains with many cross-signed intermediates.
+    /// If a limit is hit before finding a valid path, validation
+    /// fails with [`ValidationError::ResourceLimitExceeded`].
+    pub fn set_limits(&mut self, limits: ValidationLimits) {
+        self.inner
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner())
+            .set_limits(limits);
+    }
+
+    /// Consults `fetcher` for the issuers of certificates with no
+    /// issuer in the chain or the root store, if the chain is not
+    /// valid as given (e.g., when a server sends an incomplete chain).
+    /// Fetched certificates are added to the chain as intermediates,
+    /// within `limits`, and the extended chain is validated again.
+    ///
+    /// Only applies to [`Validator::validate_der`], [`Validator::validate_parsed`],
+    /// [`Validator::validate_bundle`], [`Validator::validate_der_with_path`]
+    /// and the methods based on them, but not to [`Validator::validate_all`],
+    /// the `validate_*_stapled` or the `validate_*_with_report` methods.
+    pub fn set_intermediate_fetcher(
+        &mut self,
+        fetcher: impl IntermediateFetcher + 'a,
+        limits: FetchLimits,
+    ) {
+        self.fetcher = Some((Box::new(fetcher), limits));
+    }
+
+    /// Uses the unexpired certificates in `cache` as additional
+    /// candidate intermediates, and adds the intermediates (CA
+    /// certificates other than the leaf) of each valid chain to it.
+    /// The cache can be shared by multiple validators.
+    ///
+    /// Applies to the same methods as [`Validator::set_intermediate_fetcher`].
+    pub fn set_intermediate_cache(&mut self, cache: Arc<IntermediateCache>) {
+        self.cache = Some(cache);
+    }
+
+    /// Locks the current root set for the duration of one validation.
+    /// A poisoned lock is still usable since the validator is only
+    /// modified by replacing the roots as a whole.
+    fn inner(&self) -> RwLockReadGuard<'_, InternalValidator<'a, BoxDynInternalPolicy<'a>>> {
+        self.inner.read().unwrap_or_else(|e| e.into_inner())
     }
 
     /// Validates a certificate chain in PEM format,
     /// assuming that the first certificate is the leaf certificate.
     pub fn validate_pem<R: BufRead>(&self, pem: R, task: &Task) -> Result<bool> {
         let chain_base64 = read_pem_as_base64(pem)
-            .map(|res| res)
             .collect::<std::result::Result<Vec<_>, PEMParseError>>()?;
         self.validate_base64(chain_base64.iter().map(|c| c.as_bytes()), task)
     }
@@ -342,21 +1673,376 @@ impl<'a> Validator<'a> {
         chain_der: impl Iterator<Item = &'b [u8]>,
         task: &Task,
     ) -> Result<bool> {
+        let chain_der = chain_der.collect::<Vec<_>>();
+
+        if self.fetcher.is_some() || self.cache.is_some() {
+            let bundle = chain_der
+                .iter()
+                .map(|der| Ok(Arc::new(InternalParsedCertificate::from_der(der.to_vec())?)))
+                .collect::<Result<Vec<_>>>()?;
+            return self.validate_extended(bundle, 0, task);
+        }
+
+        let chain = chain_der
+            .iter()
+            .map(|c| parse_x509_der(c))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(self.inner().validate(&VecDeep::from_vec(chain), &task.0)?)
+    }
+
+    /// Validates a certificate chain of [`ParsedCertificate`]s,
+    /// assuming that the first certificate is the leaf certificate.
+    pub fn validate_parsed(&self, chain: &[Arc<ParsedCertificate>], task: &Task) -> Result<bool> {
+        if self.fetcher.is_some() || self.cache.is_some() {
+            let bundle = chain.iter().map(|c| c.0.clone()).collect();
+            return self.validate_extended(bundle, 0, task);
+        }
+
+        let chain = chain.iter().map(|c| c.0.as_ref()).collect::<Vec<_>>();
+        Ok(self.inner().validate_parsed(&chain, &task.0)?)
+    }
+
+    /// Validates `bundle` (with `bundle[leaf]` as the leaf) together
+    /// with the cached intermediates, and if that fails, again with the
+    /// fetched intermediates. Intermediates of the accepted chain are cached.
+    /// NOTE: the result is verified over the bundle and the cached
+    /// and fetched certificates (see `validate_parsed_with_cache`).
+    fn validate_extended(
+        &self,
+        bundle: Vec<Arc<InternalParsedCertificate>>,
+        leaf: usize,
+        task: &Task,
+    ) -> Result<bool> {
+        Ok(self.validate_extended_with_path(bundle, leaf, task)?.is_some())
+    }
+
+    /// Same as [`Validator::validate_extended`], but returns the accepted chain,
+    /// where `path` indexes into `bundle` followed by the fetched intermediates,
+    /// and then the cached intermediates used in that validation.
+    fn validate_extended_with_path(
+        &self,
+        mut bundle: Vec<Arc<InternalParsedCertificate>>,
+        leaf: usize,
+        task: &Task,
+    ) -> Result<Option<ValidatedChain>> {
+        // Returns the accepted chain along with the certificates on its path, if any.
+        // The lock is taken for each validation (and each round of fetching)
+        // separately, so that slow fetches do not block `set_roots`
+        let validate = |bundle: &[Arc<InternalParsedCertificate>]| -> Result<Option<(ValidatedChain, Vec<_>)>> {
+            let inner = self.inner();
+
+            let cached = match &self.cache {
+                Some(cache) => cache.candidates(task.timestamp()),
+                None => Vec::new(),
+            };
+            // Skip cached certificates already in the bundle
+            let cached = cached
+                .into_iter()
+                .filter(|cert| {
+                    !bundle
+                        .iter()
+                        .any(|other| other.abs().fingerprint == cert.abs().fingerprint)
+                })
+                .collect::<Vec<_>>();
+
+            let bundle_refs = bundle.iter().map(|cert| cert.as_ref()).collect::<Vec<_>>();
+            let cached_refs = cached.iter().map(|cert| cert.as_ref()).collect::<Vec<_>>();
+            let Some((path, root_idx)) =
+                inner.validate_parsed_with_cache(&bundle_refs, &cached_refs, leaf, &task.0)?
+            else {
+                return Ok(None);
+            };
+
+            // The path indexes into `bundle + cached`
+            let path_certs = path
+                .iter()
+                .map(|&i| match bundle.get(i) {
+                    Some(cert) => cert.clone(),
+                    None => cached[i - bundle.len()].clone(),
+                })
+                .collect::<Vec<_>>();
+
+            let mut certs = path_certs
+                .iter()
+                .map(|cert| crate::policy::Certificate::from(cert.cert()))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            certs.push(crate::policy::Certificate::from(inner.roots.get(root_idx))?);
+
+            Ok(Some((
+                ValidatedChain {
+                    path,
+                    root_idx,
+                    certs,
+                },
+                path_certs,
+            )))
+        };
+
+        let mut accepted = validate(&bundle)?;
+
+        if accepted.is_none() {
+            if let Some((fetcher, limits)) = &self.fetcher {
+                let missing_issuers =
+                    |bundle: &[Arc<InternalParsedCertificate>], indices: &[usize]| {
+                        let inner = self.inner();
+                        indices
+                            .iter()
+                            .copied()
+                            .filter(|idx| !has_issuer(&inner, bundle, *idx))
+                            .collect::<Vec<_>>()
+                    };
+
+                let fetched =
+                    fetch_intermediates(fetcher.as_ref(), limits, &mut bundle, missing_issuers);
+                if fetched != 0 {
+                    accepted = validate(&bundle)?;
+                }
+            }
+        }
+
+        if let (Some((_, path_certs)), Some(cache)) = (&accepted, &self.cache) {
+            for cert in &path_certs[1..] {
+                cache.insert_internal(cert.clone());
+            }
+        }
+
+        Ok(accepted.map(|(chain, _)| chain))
+    }
+
+    /// Validates the certificate chain from `leaf`, using `extra_certs`
+    /// as an unordered pool of intermediate certificates (e.g., a bundle
+    /// sent out of order by the server, or extracted from PKCS#7).
+    ///
+    /// If `leaf` is `None`, it is detected from `extra_certs` as the
+    /// only non-CA certificate issued for the peer in `task`; it is
+    /// an error if there is no such certificate or more than one.
+    pub fn validate_bundle(
+        &self,
+        leaf: Option<&ParsedCertificate>,
+        extra_certs: &[ParsedCertificate],
+        task: &Task,
+    ) -> Result<bool> {
+        let certs = leaf
+            .into_iter()
+            .chain(extra_certs)
+            .map(|c| c.0.clone())
+            .collect::<Vec<_>>();
+        let bundle = certs.iter().map(|c| c.as_ref()).collect::<Vec<_>>();
+
+        let leaf = match leaf {
+            Some(..) => 0,
+            None => InternalValidator::<BoxDynInternalPolicy<'a>>::find_leaf(&bundle, &task.0)
+                .ok_or(error::ValidationError::LeafNotFound)?,
+        };
+
+        if self.fetcher.is_some() || self.cache.is_some() {
+            return self.validate_extended(certs, leaf, task);
+        }
+
+        Ok(self.inner().validate_parsed_bundle(&bundle, leaf, &task.0)?)
+    }
+
+    /// Same as [`Validator::validate_pem`], but also checks stapled OCSP
+    /// responses in ASN.1 DER format, where the `i`-th item of `ocsp_der`
+    /// (if present) is the response for the `i`-th certificate in the chain.
+    /// The intermediate fetcher and cache are not used.
+    pub fn validate_pem_stapled<'b, R: BufRead>(
+        &self,
+        pem: R,
+        ocsp_der: impl Iterator<Item = Option<&'b [u8]>>,
+        task: &Task,
+    ) -> Result<bool> {
+        let chain_der = read_pem_as_base64(pem)
+            .map(|res| Ok(decode_base64(res?.as_bytes())?))
+            .collect::<Result<Vec<_>>>()?;
+        self.validate_der_stapled(chain_der.iter().map(|c| c.as_slice()), ocsp_der, task)
+    }
+
+    /// Same as [`Validator::validate_der`], but also checks stapled
+    /// OCSP responses (see [`Validator::validate_pem_stapled`]).
+    /// The intermediate fetcher and cache are not used.
+    pub fn validate_der_stapled<'b, 'c>(
+        &self,
+        chain_der: impl Iterator<Item = &'b [u8]>,
+        ocsp_der: impl Iterator<Item = Option<&'c [u8]>>,
+        task: &Task,
+    ) -> Result<bool> {
+        let chain = chain_der
+            .map(|c| parse_x509_der(c))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let ocsp = ocsp_der
+            .map(|resp| match resp {
+                Some(der) => parse_ocsp_response_der(der).map(OptionDeep::Some),
+                None => Ok(OptionDeep::None),
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(self.inner().validate_stapled(
+            &VecDeep::from_vec(chain),
+            &VecDeep::from_vec(ocsp),
+            &task.0,
+        )?)
+    }
+
+    /// Same as [`Validator::validate_pem`], but returns the accepted
+    /// chain if the validation succeeds, or `None` otherwise.
+    pub fn validate_pem_with_path<R: BufRead>(
+        &self,
+        pem: R,
+        task: &Task,
+    ) -> Result<Option<ValidatedChain>> {
+        let chain_base64 = read_pem_as_base64(pem)
+            .collect::<std::result::Result<Vec<_>, PEMParseError>>()?;
+        self.validate_base64_with_path(chain_base64.iter().map(|c| c.as_bytes()), task)
+    }
+
+    /// Same as [`Validator::validate_base64`], but returns the accepted
+    /// chain if the validation succeeds, or `None` otherwise.
+    pub fn validate_base64_with_path(
+        &self,
+        chain_base64: impl Iterator<Item = impl AsRef<[u8]>>,
+        task: &Task,
+    ) -> Result<Option<ValidatedChain>> {
+        let chain_der = chain_base64
+            .map(|c| decode_base64(c.as_ref()))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        self.validate_der_with_path(chain_der.iter().map(|c| c.as_slice()), task)
+    }
+
+    /// Same as [`Validator::validate_der`], but returns the accepted
+    /// chain if the validation succeeds, or `None` otherwise.
+    pub fn validate_der_with_path<'b>(
+        &self,
+        chain_der: impl Iterator<Item = &'b [u8]>,
+        task: &Task,
+    ) -> Result<Option<ValidatedChain>> {
+        if self.fetcher.is_some() || self.cache.is_some() {
+            let bundle = chain_der
+                .map(|der| Ok(Arc::new(InternalParsedCertificate::from_der(der.to_vec())?)))
+                .collect::<Result<Vec<_>>>()?;
+            return self.validate_extended_with_path(bundle, 0, task);
+        }
+
+        let chain = VecDeep::from_vec(
+            chain_der
+                .map(|c| parse_x509_der(c))
+                .collect::<std::result::Result<Vec<_>, _>>()?,
+        );
+
+        // Hold the same snapshot of roots while looking up `root_idx`
+        let inner = self.inner();
+        let Some((path, root_idx)) = inner.validate_with_path(&chain, &task.0)? else {
+            return Ok(None);
+        };
+
+        let mut certs = path
+            .iter()
+            .map(|i| crate::policy::Certificate::from(chain.get(*i)))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        certs.push(crate::policy::Certificate::from(inner.roots.get(root_idx))?);
+
+        Ok(Some(ValidatedChain {
+            path,
+            root_idx,
+            certs,
+        }))
+    }
+
+    /// Same as [`Validator::validate_der_with_path`], but returns every
+    /// accepted chain instead of the first one (e.g., through both the
+    /// old and the new root during a root rotation), or an empty list
+    /// if the chain is not valid. The intermediate fetcher and cache
+    /// are not used, so every path is within the input chain.
+    pub fn validate_all<'b>(
+        &self,
+        chain_der: impl Iterator<Item = &'b [u8]>,
+        task: &Task,
+    ) -> Result<Vec<ValidatedChain>> {
+        let chain = VecDeep::from_vec(
+            chain_der
+                .map(|c| parse_x509_der(c))
+                .collect::<std::result::Result<Vec<_>, _>>()?,
+        );
+
+        // Hold the same snapshot of roots while looking up each `root_idx`
+        let inner = self.inner();
+
+        inner
+            .validate_all(&chain, &task.0)?
+            .into_iter()
+            .map(|(path, root_idx)| {
+                let mut certs = path
+                    .iter()
+                    .map(|i| crate::policy::Certificate::from(chain.get(*i)))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                certs.push(crate::policy::Certificate::from(inner.roots.get(root_idx))?);
+
+                Ok(ValidatedChain {
+                    path,
+                    root_idx,
+                    certs,
+                })
+            })
+            .collect()
+    }
+
+    /// Same as [`Validator::validate_pem`], but also explains why
+    /// each candidate path explored before the verdict is rejected.
+    /// NOTE: [`ValidationReport::valid`] is the same verified verdict
+    /// (without the intermediate fetcher and cache), but the explanations
+    /// are not verified.
+    pub fn validate_pem_with_report<R: BufRead>(
+        &self,
+        pem: R,
+        task: &Task,
+    ) -> Result<ValidationReport> {
+        let chain_base64 = read_pem_as_base64(pem)
+            .collect::<std::result::Result<Vec<_>, PEMParseError>>()?;
+        self.validate_base64_with_report(chain_base64.iter().map(|c| c.as_bytes()), task)
+    }
+
+    /// Same as [`Validator::validate_base64`], but also explains why
+    /// each candidate path explored before the verdict is rejected.
+    /// NOTE: [`ValidationReport::valid`] is the same verified verdict
+    /// (without the intermediate fetcher and cache), but the explanations
+    /// are not verified.
+    pub fn validate_base64_with_report(
+        &self,
+        chain_base64: impl Iterator<Item = impl AsRef<[u8]>>,
+        task: &Task,
+    ) -> Result<ValidationReport> {
+        let chain_der = chain_base64
+            .map(|c| decode_base64(c.as_ref()))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        self.validate_der_with_report(chain_der.iter().map(|c| c.as_slice()), task)
+    }
+
+    /// Same as [`Validator::validate_der`], but also explains why
+    /// each candidate path explored before the verdict is rejected.
+    /// NOTE: [`ValidationReport::valid`] is the same verified verdict
+    /// (without the intermediate fetcher and cache), but the explanations
+    /// are not verified.
+    pub fn validate_der_with_report<'b>(
+        &self,
+        chain_der: impl Iterator<Item = &'b [u8]>,
+        task: &Task,
+    ) -> Result<ValidationReport> {
         let chain = chain_der
             .map(|c| parse_x509_der(c))
             .collect::<std::result::Result<Vec<_>, _>>()?;
-        Ok(self.0.validate(&VecDeep::from_vec(chain), &task.0)?)
+        Ok(self
+            .inner()
+            .validate_with_report(&VecDeep::from_vec(chain), &task.0)?)
     }
 
     /// Prints some debug information about a certificate chain.
     /// NOTE: this function is subject to change.
     pub fn print_debug_info(&self, chain_base64: &Vec<Vec<u8>>, task: &Task) -> Result<()> {
-        self.0.print_debug_info(chain_base64, &task.0)?;
+        self.inner().print_debug_info(chain_base64, &task.0)?;
         Ok(())
     }
 
     /// Returns the number of root certificates.
     pub fn num_roots(&self) -> usize {
-        self.0.roots.len()
+        self.inner().roots.len()
     }
 }
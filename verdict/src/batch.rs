@@ -0,0 +1,317 @@
+//! Batch and asynchronous validation on a pool of worker threads
+//! sharing one [`Validator`] (and hence one snapshot of parsed roots)
+
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread::{self, JoinHandle};
+
+use crate::api::{Result, Task, ValidationError, Validator};
+
+type Work = Box<dyn FnOnce(&Validator<'static>) + Send>;
+
+/// Validates many certificate chains in parallel on a fixed pool of
+/// worker threads, all sharing the same [`Validator`].
+///
+/// Each chain is given as a list of certificates in ASN.1 DER format,
+/// with the leaf certificate first (as in [`Validator::validate_der`]).
+/// The worker threads are stopped when the [`BatchValidator`] is dropped,
+/// after finishing the jobs already submitted.
+pub struct BatchValidator {
+    validator: Arc<Validator<'static>>,
+    queue: Option<Sender<Work>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl BatchValidator {
+    /// Starts `threads` worker threads sharing `validator`, or one
+    /// thread per available CPU if `threads` is 0.
+    pub fn new(validator: Validator<'static>, threads: usize) -> Self {
+        let threads = if threads == 0 {
+            thread::available_parallelism().map_or(1, |n| n.get())
+        } else {
+            threads
+        };
+
+        let validator = Arc::new(validator);
+        let (queue, jobs) = channel::<Work>();
+        let jobs = Arc::new(Mutex::new(jobs));
+
+        let workers = (0..threads)
+            .map(|_| {
+                let validator = validator.clone();
+                let jobs = jobs.clone();
+
+                thread::spawn(move || loop {
+                    // The lock is released before running the job
+                    let work = jobs.lock().unwrap_or_else(|e| e.into_inner()).recv();
+                    match work {
+                        Ok(work) => work(&validator),
+                        // The queue is closed
+                        Err(..) => break,
+                    }
+                })
+            })
+            .collect();
+
+        BatchValidator {
+            validator,
+            queue: Some(queue),
+            workers,
+        }
+    }
+
+    /// The shared validator, e.g., to replace the roots
+    /// for subsequent jobs with [`Validator::set_roots`].
+    pub fn validator(&self) -> &Validator<'static> {
+        &self.validator
+    }
+
+    /// Returns the number of worker threads.
+    pub fn num_threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Validates all `(chain, task)` jobs and returns
+    /// the results in the same order as the input.
+    pub fn validate_batch<I, C>(&self, jobs: I) -> Vec<Result<bool>>
+    where
+        I: IntoIterator<Item = (C, Task)>,
+        C: IntoIterator,
+        C::Item: Into<Vec<u8>>,
+    {
+        let stream = self.validate_stream(jobs);
+        let mut results = (0..stream.len()).map(|_| None).collect::<Vec<_>>();
+
+        for (i, res) in stream {
+            results[i] = Some(res);
+        }
+
+        results
+            .into_iter()
+            .map(|res| res.unwrap_or(Err(ValidationError::WorkerPanicked)))
+            .collect()
+    }
+
+    /// Submits all `(chain, task)` jobs and returns an iterator over
+    /// `(index of the job, result)` in the order the jobs finish.
+    pub fn validate_stream<I, C>(&self, jobs: I) -> BatchResults
+    where
+        I: IntoIterator<Item = (C, Task)>,
+        C: IntoIterator,
+        C::Item: Into<Vec<u8>>,
+    {
+        let (tx, rx) = channel();
+        let mut remaining = 0;
+
+        for (i, (chain, task)) in jobs.into_iter().enumerate() {
+            let chain = chain.into_iter().map(Into::into).collect::<Vec<Vec<u8>>>();
+            let tx = tx.clone();
+
+            self.submit(chain, task, move |res| {
+                // The receiver may have been dropped
+                let _ = tx.send((i, res));
+            });
+            remaining += 1;
+        }
+
+        BatchResults { rx, remaining }
+    }
+
+    /// Validates a chain on the worker pool without blocking the
+    /// current thread; the returned future resolves to the result.
+    ///
+    /// The future does not depend on any particular async runtime.
+    pub fn validate_async<C>(&self, chain: C, task: Task) -> ValidationFuture
+    where
+        C: IntoIterator,
+        C::Item: Into<Vec<u8>>,
+    {
+        let state = Arc::new(Mutex::new(FutureState {
+            result: None,
+            waker: None,
+        }));
+        let chain = chain.into_iter().map(Into::into).collect::<Vec<Vec<u8>>>();
+        let worker_state = state.clone();
+
+        self.submit(chain, task, move |res| {
+            let mut state = worker_state.lock().unwrap_or_else(|e| e.into_inner());
+            state.result = Some(res);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+
+        ValidationFuture { state }
+    }
+
+    /// Queues a job, and calls `done` with the result on a worker thread.
+    /// A panic during validation is reported as [`ValidationError::WorkerPanicked`].
+    fn submit(
+        &self,
+        chain: Vec<Vec<u8>>,
+        task: Task,
+        done: impl FnOnce(Result<bool>) + Send + 'static,
+    ) {
+        let work: Work = Box::new(move |validator| {
+            let res = catch_unwind(AssertUnwindSafe(|| {
+                validator.validate_der(chain.iter().map(|c| c.as_slice()), &task)
+            }));
+            done(res.unwrap_or(Err(ValidationError::WorkerPanicked)));
+        });
+
+        // The queue is only closed on drop, and the workers
+        // do not exit while it is open, so this never fails
+        if let Some(queue) = &self.queue {
+            let _ = queue.send(work);
+        }
+    }
+}
+
+impl Drop for BatchValidator {
+    fn drop(&mut self) {
+        // Close the queue so that workers exit after the remaining jobs
+        drop(self.queue.take());
+
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Results of [`BatchValidator::validate_stream`],
+/// as `(index of the job, result)` in completion order.
+pub struct BatchResults {
+    rx: Receiver<(usize, Result<bool>)>,
+    remaining: usize,
+}
+
+impl Iterator for BatchResults {
+    type Item = (usize, Result<bool>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let item = self.rx.recv().ok()?;
+        self.remaining -= 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for BatchResults {}
+
+struct FutureState {
+    result: Option<Result<bool>>,
+    waker: Option<Waker>,
+}
+
+/// The result of [`BatchValidator::validate_async`].
+pub struct ValidationFuture {
+    state: Arc<Mutex<FutureState>>,
+}
+
+impl Future for ValidationFuture {
+    type Output = Result<bool>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        match state.result.take() {
+            Some(res) => Poll::Ready(res),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{chrome_validator, google_chain_der, TIMESTAMP};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::task::Wake;
+
+    fn batch_validator(threads: usize) -> BatchValidator {
+        BatchValidator::new(chrome_validator(), threads)
+    }
+
+    #[test]
+    fn test_validate_batch() {
+        let batch = batch_validator(4);
+        let chain = google_chain_der();
+
+        let hostnames = (0..32)
+            .map(|i| if i % 3 == 0 { "example.com" } else { "google.com" })
+            .collect::<Vec<_>>();
+        let jobs = hostnames
+            .iter()
+            .map(|hostname| (chain.clone(), Task::new_server_auth(Some(hostname), TIMESTAMP)));
+
+        let results = batch.validate_batch(jobs);
+
+        assert_eq!(results.len(), hostnames.len());
+        for (hostname, res) in hostnames.iter().zip(results) {
+            assert_eq!(res.unwrap(), *hostname == "google.com");
+        }
+    }
+
+    struct ThreadWaker {
+        thread: thread::Thread,
+        woken: AtomicBool,
+    }
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.woken.store(true, Ordering::SeqCst);
+            self.thread.unpark();
+        }
+    }
+
+    /// A minimal executor for a single future
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut fut = Box::pin(fut);
+        let waker = Arc::new(ThreadWaker {
+            thread: thread::current(),
+            woken: AtomicBool::new(false),
+        });
+        let cx_waker = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&cx_waker);
+
+        loop {
+            if let Poll::Ready(res) = fut.as_mut().poll(&mut cx) {
+                return res;
+            }
+            while !waker.woken.swap(false, Ordering::SeqCst) {
+                thread::park();
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_async() {
+        let batch = batch_validator(1);
+
+        let valid = batch.validate_async(
+            google_chain_der(),
+            Task::new_server_auth(Some("google.com"), TIMESTAMP),
+        );
+        let invalid = batch.validate_async(
+            google_chain_der(),
+            Task::new_server_auth(Some("example.com"), TIMESTAMP),
+        );
+
+        assert!(block_on(valid).unwrap());
+        assert!(!block_on(invalid).unwrap());
+    }
+}
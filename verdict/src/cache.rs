@@ -0,0 +1,197 @@
+//! A cache of intermediate certificates seen in valid chains,
+//! which are used as additional untrusted candidates in later
+//! validations (e.g., when a server omits an intermediate that
+//! another server sent earlier)
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::api::ParsedCertificate;
+use crate::validator::ParsedCertificate as InternalParsedCertificate;
+
+/// A bounded, thread-safe cache of intermediate certificates that can be
+/// shared by multiple [`Validator`](crate::Validator)s (see
+/// [`Validator::set_intermediate_cache`](crate::Validator::set_intermediate_cache)).
+///
+/// When the cache is full, the least recently seen certificate is evicted,
+/// and each certificate is dropped once it expires (at its `notAfter`,
+/// in wall-clock time). Validations only use cached certificates that
+/// are within their validity period at the time of their task.
+/// Cached certificates are never trusted by themselves, so the
+/// verified guarantees of validation hold over the chain together
+/// with the cached certificates.
+pub struct IntermediateCache {
+    capacity: usize,
+
+    /// Cached certificates from the least to the most recently seen
+    entries: Mutex<VecDeque<Arc<InternalParsedCertificate>>>,
+}
+
+impl IntermediateCache {
+    /// Creates an empty cache holding at most `capacity` certificates.
+    pub fn new(capacity: usize) -> Self {
+        IntermediateCache {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// A poisoned lock is still usable since each
+    /// operation leaves the entries consistent.
+    fn entries(&self) -> MutexGuard<'_, VecDeque<Arc<InternalParsedCertificate>>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns the number of cached certificates.
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    /// Returns `true` if no certificate is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries().is_empty()
+    }
+
+    /// Removes all cached certificates.
+    pub fn clear(&self) {
+        self.entries().clear();
+    }
+
+    /// Adds a certificate to the cache (or marks it as the most
+    /// recently seen if it is already cached), evicting the least
+    /// recently seen certificate if the cache is full.
+    pub fn insert(&self, cert: &ParsedCertificate) {
+        self.insert_internal(cert.0.clone());
+    }
+
+    pub(crate) fn insert_internal(&self, cert: Arc<InternalParsedCertificate>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.entries();
+
+        if let Some(pos) = entries
+            .iter()
+            .position(|other| other.abs().fingerprint == cert.abs().fingerprint)
+        {
+            entries.remove(pos);
+        } else if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+
+        entries.push_back(cert);
+    }
+
+    /// Returns the cached certificates that are valid at `now`
+    /// (the validation time of the task) as candidate intermediates.
+    /// Certificates that already expired in wall-clock time are dropped,
+    /// but those only expired (or not yet valid) at `now` are kept for
+    /// other tasks (e.g., validating at a different time than the current one).
+    pub(crate) fn candidates(&self, now: u64) -> Vec<Arc<InternalParsedCertificate>> {
+        // A wall-clock time before the epoch does not evict anything
+        let wall_clock = u64::try_from(chrono::Utc::now().timestamp()).unwrap_or(0);
+        self.candidates_at(now, wall_clock)
+    }
+
+    /// Same as [`IntermediateCache::candidates`], but with the
+    /// given wall-clock time (as a UNIX timestamp)
+    fn candidates_at(&self, now: u64, wall_clock: u64) -> Vec<Arc<InternalParsedCertificate>> {
+        let mut entries = self.entries();
+        entries.retain(|cert| cert.abs().not_after >= wall_clock);
+        entries
+            .iter()
+            .filter(|cert| cert.abs().not_before <= now && now <= cert.abs().not_after)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::Task;
+    use crate::test_fixtures::{chrome_validator, google_chain, TIMESTAMP};
+
+    #[test]
+    fn test_lru_and_expiry() {
+        let chain = google_chain();
+        let cache = IntermediateCache::new(2);
+
+        cache.insert(&chain[0]);
+        cache.insert(&chain[1]);
+        cache.insert(&chain[0]);
+        assert_eq!(cache.len(), 2);
+
+        // chain[1] is the least recently seen
+        cache.insert(&chain[2]);
+        let fingerprints = cache
+            .candidates_at(TIMESTAMP, TIMESTAMP)
+            .iter()
+            .map(|cert| cert.abs().fingerprint.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fingerprints,
+            vec![chain[0].fingerprint().to_string(), chain[2].fingerprint().to_string()],
+        );
+
+        // The leaf expired in Oct, 2024, while GTS Root R1 (cross-signed) expires in Jan, 2028.
+        // Expiry at the task time only filters the candidates
+        assert_eq!(cache.candidates_at(1800000000, TIMESTAMP).len(), 1);
+        assert!(cache.candidates_at(u64::MAX, TIMESTAMP).is_empty());
+        // Nor are they candidates before their notBefore
+        assert!(cache.candidates_at(0, TIMESTAMP).is_empty());
+        assert_eq!(cache.len(), 2);
+
+        // while expiry at the wall-clock time also evicts them
+        assert_eq!(cache.candidates_at(TIMESTAMP, 1800000000).len(), 1);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.candidates_at(TIMESTAMP, u64::MAX).is_empty());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_cached_intermediates() {
+        let cache = Arc::new(IntermediateCache::new(16));
+        let chain = google_chain();
+        let task = Task::new_server_auth(Some("google.com"), TIMESTAMP);
+
+        let leaf_only = [Arc::new(chain[0].clone())];
+        let full_chain = chain.iter().cloned().map(Arc::new).collect::<Vec<_>>();
+
+        let mut validator = chrome_validator();
+        validator.set_intermediate_cache(cache.clone());
+
+        // Nothing is cached yet
+        assert!(!validator.validate_parsed(&leaf_only, &task).unwrap());
+        assert!(cache.is_empty());
+
+        // Only intermediates on the accepted path are cached: not the leaf,
+        // nor GTS Root R1 cross-signed by GlobalSign, which is not needed
+        // since GTS Root R1 itself is in roots.pem
+        assert!(validator.validate_parsed(&full_chain, &task).unwrap());
+        assert_eq!(cache.len(), 1);
+        assert_eq!(
+            cache.candidates_at(TIMESTAMP, TIMESTAMP)[0].abs().fingerprint,
+            chain[1].fingerprint(),
+        );
+
+        // Another validator sharing the cache
+        let mut other = chrome_validator();
+        other.set_intermediate_cache(cache.clone());
+        assert!(other.validate_parsed(&leaf_only, &task).unwrap());
+
+        // The accepted path continues past the input chain into the cache
+        let accepted = other
+            .validate_der_with_path([chain[0].der()].into_iter(), &task)
+            .unwrap()
+            .unwrap();
+        assert_eq!(accepted.path, vec![0, 1]);
+        assert_eq!(accepted.certs[1].fingerprint, chain[1].fingerprint());
+        assert_eq!(accepted.certs.len(), 3);
+
+        // Cached intermediates do not bypass the policy
+        let wrong_host = Task::new_server_auth(Some("example.com"), TIMESTAMP);
+        assert!(!other.validate_parsed(&leaf_only, &wrong_host).unwrap());
+    }
+}
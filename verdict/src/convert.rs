@@ -74,10 +74,12 @@ impl policy::Certificate {
                 sig_alg_outer: policy::SignatureAlgorithm {
                     id: Self::spec_oid_to_string(c.sig_alg.id),
                     bytes: hash::spec_to_hex_upper(sig_alg_outer),
+                    pss: policy::PSSParams::spec_from(c.sig_alg),
                 },
                 sig_alg_inner: policy::SignatureAlgorithm {
                     id: Self::spec_oid_to_string(c.cert.signature.id),
                     bytes: hash::spec_to_hex_upper(sig_alg_inner),
+                    pss: policy::PSSParams::spec_from(c.cert.signature),
                 },
 
                 not_after: not_after as u64,
@@ -113,6 +115,8 @@ impl policy::Certificate {
                 } else {
                     None
                 },
+
+                trust_anchor: None,
             })
         }
     }
@@ -196,17 +200,19 @@ impl policy::Certificate {
             sig_alg_outer: policy::ExecSignatureAlgorithm {
                 id: Self::oid_to_string(&c.get().sig_alg.get().id),
                 bytes: hash::to_hex_upper(c.get().sig_alg.serialize()),
+                pss: policy::PSSParams::from(&c.get().sig_alg.get()),
             },
             sig_alg_inner: policy::ExecSignatureAlgorithm {
                 id: Self::oid_to_string(&c.get().cert.get().signature.get().id),
                 bytes: hash::to_hex_upper(c.get().cert.get().signature.serialize()),
+                pss: policy::PSSParams::from(&c.get().cert.get().signature.get()),
             },
 
             not_after: not_after as u64,
             not_before: not_before as u64,
 
             issuer: policy::DistinguishedName::from(&c.get().cert.get().issuer),
-            subject: policy::DistinguishedName::from(&c.get().cert.get().subject),
+            subject: policy::DistinguishedName::from(c.get().cert.get().subject.get()),
             subject_key,
 
             issuer_uid: if let OptionDeep::Some(uid) = &c.get().cert.get().issuer_uid {
@@ -235,6 +241,8 @@ impl policy::Certificate {
             } else {
                 None
             },
+
+            trust_anchor: None,
         })
     }
 
@@ -521,6 +529,7 @@ impl policy::ExtendedKeyUsage {
     pub fn from(ext: &ExtensionValue<'_>) -> (res: Result<policy::ExecExtendedKeyUsage, ValidationError>)
         ensures
             res matches Ok(res) ==> Some(res.deep_view()) =~= Self::spec_from(ext@),
+            res is Err ==> Self::spec_from(ext@) is None,
     {
         if let ExtensionParamValue::ExtendedKeyUsage(usages) = &ext.param {
             let usage_types = vec_map(usages.to_vec(), |oid| -> (res: policy::ExecExtendedKeyUsageType)
@@ -789,10 +798,12 @@ impl policy::CertificatePolicies {
 impl policy::AuthorityInfoAccess {
     pub open spec fn spec_from(ext: SpecExtensionValue) -> Option<policy::AuthorityInfoAccess> {
         if_let! {
-            let SpecExtensionParamValue::AuthorityInfoAccess(..) = ext.param;
+            let SpecExtensionParamValue::AuthorityInfoAccess(descs) = ext.param;
 
             Some(policy::AuthorityInfoAccess {
                 critical: ext.critical.to_opt(),
+                ca_issuers: Self::spec_access_uris(descs, spec_oid!(AD_CA_ISSUERS)),
+                ocsp: Self::spec_access_uris(descs, spec_oid!(AD_OCSP)),
             })
         }
     }
@@ -801,14 +812,66 @@ impl policy::AuthorityInfoAccess {
         ensures
             res matches Ok(res) ==> Some(res.deep_view()) =~= Self::spec_from(ext@),
     {
-        if let ExtensionParamValue::AuthorityInfoAccess(..) = &ext.param {
+        if let ExtensionParamValue::AuthorityInfoAccess(descs) = &ext.param {
             Ok(policy::ExecAuthorityInfoAccess {
                 critical: PolyfillClone::clone(&ext.critical).to_opt(),
+                ca_issuers: Self::access_uris(descs, &oid!(AD_CA_ISSUERS)),
+                ocsp: Self::access_uris(descs, &oid!(AD_OCSP)),
             })
         } else {
             Err(ValidationError::UnexpectedExtParam)
         }
     }
+
+    /// URIs in the access descriptions with the given access method
+    /// (other kinds of locations are ignored)
+    pub open spec fn spec_access_uris(descs: SpecAuthorityInfoAccessValue, method: SpecObjectIdentifierValue) -> Seq<SpecString>
+        decreases descs.len()
+    {
+        if descs.len() == 0 {
+            seq![]
+        } else {
+            let rest = Self::spec_access_uris(descs.drop_first(), method);
+
+            if let SpecGeneralNameValue::URI(uri) = descs.first().location {
+                if descs.first().method == method {
+                    seq![uri] + rest
+                } else {
+                    rest
+                }
+            } else {
+                rest
+            }
+        }
+    }
+
+    /// Exec version of spec_access_uris
+    pub fn access_uris(descs: &AuthorityInfoAccessValue<'_>, method: &ObjectIdentifierValue) -> (res: Vec<String>)
+        ensures res.deep_view() =~= Self::spec_access_uris(descs@, method@),
+    {
+        let len = descs.len();
+        let mut uris = Vec::new();
+
+        assert(descs@.skip(0) == descs@);
+
+        for i in 0..len
+            invariant
+                len == descs@.len(),
+                Self::spec_access_uris(descs@, method@) =~= uris.deep_view() + Self::spec_access_uris(descs@.skip(i as int), method@),
+        {
+            let desc = descs.get(i);
+
+            if let GeneralNameValue::URI(uri) = &desc.location {
+                if desc.method.polyfill_eq(method) {
+                    uris.push((*uri).to_string());
+                }
+            }
+
+            assert(descs@.skip(i + 1) == descs@.skip(i as int).drop_first());
+        }
+
+        uris
+    }
 }
 
 /// Conversions from/to GeneralName and related structures
@@ -822,6 +885,8 @@ impl policy::GeneralName {
                 policy::GeneralName::DirectoryName(policy::DistinguishedName::spec_from(dir_names)),
             SpecGeneralNameValue::IP(addr) =>
                 policy::GeneralName::IPAddr(addr),
+            SpecGeneralNameValue::RFC822(s) =>
+                policy::GeneralName::RFC822Name(s),
             SpecGeneralNameValue::Other(..) => policy::GeneralName::OtherName,
             _ => policy::GeneralName::Unsupported,
         }
@@ -841,6 +906,8 @@ impl policy::GeneralName {
                 assert(copied.deep_view() =~= addr@);
                 policy::ExecGeneralName::IPAddr(copied)
             }
+            GeneralNameValue::RFC822(s) =>
+                policy::ExecGeneralName::RFC822Name((*s).to_string()),
             GeneralNameValue::Other(..) => policy::ExecGeneralName::OtherName,
             _ => policy::ExecGeneralName::Unsupported,
         }
@@ -927,7 +994,8 @@ impl policy::SubjectKey {
                 })
             }
 
-            SpecAlgorithmParamValue::RSAEncryption(..) => {
+            SpecAlgorithmParamValue::RSAEncryption(..) |
+            SpecAlgorithmParamValue::RSASSAPSS(..) => {
                 // Parse the public key field to get the modulus length
                 let pub_key = BitStringValue::spec_bytes(spki.pub_key);
 
@@ -935,10 +1003,19 @@ impl policy::SubjectKey {
                     let Ok((_, parsed)) = ASN1(RSAParam).view().spec_parse(pub_key);
                     Some(policy::SubjectKey::RSA {
                         mod_length: ((parsed.modulus.len() - 1) as usize * 8) as usize,
+                        exponent: hash::spec_to_hex_upper(parsed.exponent),
                     })
                 }
             }
 
+            SpecAlgorithmParamValue::ECPublicKey(curve) => Some(policy::SubjectKey::EC {
+                curve: policy::Certificate::spec_oid_to_string(curve),
+                point_len: BitStringValue::spec_bytes(spki.pub_key).len() as usize,
+            }),
+
+            SpecAlgorithmParamValue::Ed25519(..) => Some(policy::SubjectKey::Ed25519),
+            SpecAlgorithmParamValue::Ed448(..) => Some(policy::SubjectKey::Ed448),
+
             _ => Some(policy::SubjectKey::Other),
         }
     }
@@ -967,7 +1044,8 @@ impl policy::SubjectKey {
                 })
             }
 
-            AlgorithmParamValue::RSAEncryption(..) => {
+            AlgorithmParamValue::RSAEncryption(..) |
+            AlgorithmParamValue::RSASSAPSS(..) => {
                 let pub_key = spki.pub_key.bytes();
                 let parsed = match ASN1(RSAParam).parse(pub_key) {
                     Ok((_, parsed)) => parsed,
@@ -982,14 +1060,97 @@ impl policy::SubjectKey {
 
                 Ok(policy::ExecSubjectKey::RSA {
                     mod_length: mod_len * 8,
+                    exponent: hash::to_hex_upper(parsed.exponent.bytes()),
                 })
             }
 
+            AlgorithmParamValue::ECPublicKey(curve) => Ok(policy::ExecSubjectKey::EC {
+                curve: policy::Certificate::oid_to_string(curve),
+                point_len: spki.pub_key.bytes().len(),
+            }),
+
+            AlgorithmParamValue::Ed25519(..) => Ok(policy::ExecSubjectKey::Ed25519),
+            AlgorithmParamValue::Ed448(..) => Ok(policy::ExecSubjectKey::Ed448),
+
             _ => Ok(policy::ExecSubjectKey::Other),
         }
     }
 }
 
+impl policy::PSSParams {
+    /// Convert RSASSA-PSS parameters (if any), filling in the defaults
+    /// (SHA-1 for both the digest and MGF1, and a salt length of 20),
+    /// including when the parameters are absent altogether (RFC 4055)
+    pub open spec fn spec_from(alg: SpecAlgorithmIdentifierValue) -> Option<policy::PSSParams> {
+        match alg.param {
+            SpecAlgorithmParamValue::RSASSAPSS(Either::Left(param)) => {
+                Some(policy::PSSParams {
+                    hash: match param.hash_alg {
+                        OptionDeep::Some(hash_alg) => policy::Certificate::spec_oid_to_string(hash_alg.id),
+                        OptionDeep::None => policy::Certificate::spec_oid_to_string(spec_oid!(HASH_SHA1)),
+                    },
+                    mgf1_hash: match param.mask_gen_alg {
+                        OptionDeep::Some(mgf) =>
+                            if mgf.id == spec_oid!(MGF1) {
+                                Some(policy::Certificate::spec_oid_to_string(mgf.hash_alg.id))
+                            } else {
+                                None
+                            },
+                        OptionDeep::None => Some(policy::Certificate::spec_oid_to_string(spec_oid!(HASH_SHA1))),
+                    },
+                    salt_len: param.salt_len,
+                })
+            }
+
+            SpecAlgorithmParamValue::RSASSAPSS(Either::Right(..)) => {
+                Some(policy::PSSParams {
+                    hash: policy::Certificate::spec_oid_to_string(spec_oid!(HASH_SHA1)),
+                    mgf1_hash: Some(policy::Certificate::spec_oid_to_string(spec_oid!(HASH_SHA1))),
+                    salt_len: 20,
+                })
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Exec version of spec_from
+    pub fn from(alg: &AlgorithmIdentifierValue<'_>) -> (res: Option<policy::ExecPSSParams>)
+        ensures res.deep_view() =~= Self::spec_from(alg@),
+    {
+        match &alg.param {
+            AlgorithmParamValue::RSASSAPSS(Either::Left(param)) => {
+                Some(policy::ExecPSSParams {
+                    hash: match &param.hash_alg {
+                        OptionDeep::Some(hash_alg) => policy::Certificate::oid_to_string(&hash_alg.id),
+                        OptionDeep::None => policy::Certificate::oid_to_string(&oid!(HASH_SHA1)),
+                    },
+                    mgf1_hash: match &param.mask_gen_alg {
+                        OptionDeep::Some(mgf) =>
+                            if mgf.id.polyfill_eq(&oid!(MGF1)) {
+                                Some(policy::Certificate::oid_to_string(&mgf.hash_alg.id))
+                            } else {
+                                None
+                            },
+                        OptionDeep::None => Some(policy::Certificate::oid_to_string(&oid!(HASH_SHA1))),
+                    },
+                    salt_len: param.salt_len,
+                })
+            }
+
+            AlgorithmParamValue::RSASSAPSS(Either::Right(..)) => {
+                Some(policy::ExecPSSParams {
+                    hash: policy::Certificate::oid_to_string(&oid!(HASH_SHA1)),
+                    mgf1_hash: Some(policy::Certificate::oid_to_string(&oid!(HASH_SHA1))),
+                    salt_len: 20,
+                })
+            }
+
+            _ => None,
+        }
+    }
+}
+
 /// Directory Name (`Name` in X.509) is essentially `Seq<Seq<{ type, value }>>`
 impl policy::DistinguishedName {
     pub closed spec fn spec_from(name: SpecNameValue) -> policy::DistinguishedName {
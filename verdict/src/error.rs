@@ -9,6 +9,8 @@ verus! {
 pub enum ValidationError {
     IntegerOverflow,
     EmptyChain,
+    LeafNotFound,
+    ResourceLimitExceeded,
     ProofFailure,
     TimeParseError,
     RSAPubKeyParseError,
@@ -0,0 +1,201 @@
+//! Completing chains with intermediate certificates fetched from
+//! the caIssuers URIs in the Authority Information Access extension
+//! (RFC 5280, Section 4.2.2.1), for servers that send incomplete chains.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::policy::{IssuerLookup, Policy};
+use crate::validator::{ParsedCertificate, Validator};
+
+/// Source of intermediate certificates missing from a chain
+/// (see [`Validator::set_intermediate_fetcher`](crate::Validator::set_intermediate_fetcher)).
+///
+/// Fetched certificates are only used as untrusted intermediates,
+/// so a misbehaving fetcher can make a validation fail, but it cannot
+/// make a chain valid without a path to a trusted root.
+pub trait IntermediateFetcher: Send + Sync {
+    /// Returns the certificates (in ASN.1 DER) available at the
+    /// caIssuers `uri`, or an empty list if the fetch fails.
+    fn fetch(&self, uri: &str) -> Vec<Vec<u8>>;
+}
+
+/// An in-memory fetcher from URIs to certificates
+impl IntermediateFetcher for HashMap<String, Vec<Vec<u8>>> {
+    fn fetch(&self, uri: &str) -> Vec<Vec<u8>> {
+        self.get(uri).cloned().unwrap_or_default()
+    }
+}
+
+/// Limits on fetching intermediates within one validation.
+#[derive(Debug, Clone, Copy)]
+pub struct FetchLimits {
+    /// Maximum number of rounds of fetching, i.e., the number of
+    /// consecutive intermediates that can be missing in a path
+    pub max_depth: usize,
+
+    /// Maximum number of fetched certificates
+    pub max_certs: usize,
+}
+
+impl Default for FetchLimits {
+    fn default() -> Self {
+        FetchLimits {
+            max_depth: 3,
+            max_certs: 8,
+        }
+    }
+}
+
+/// Checks if any other certificate in `bundle` or any root
+/// likely issued `bundle[idx]` (signatures are not checked)
+pub(crate) fn has_issuer<P: Policy>(
+    validator: &Validator<'_, P>,
+    bundle: &[Arc<ParsedCertificate>],
+    idx: usize,
+) -> bool {
+    let subject = bundle[idx].abs();
+
+    if bundle
+        .iter()
+        .enumerate()
+        .any(|(i, cert)| i != idx && validator.policy.likely_issued(cert.abs(), subject))
+    {
+        return true;
+    }
+
+    let root_issued = |i: &usize| validator.policy.likely_issued(validator.roots.abs(*i), subject);
+
+    match validator.policy.issuer_lookup() {
+        IssuerLookup::Linear => (0..validator.roots.len()).any(|i| root_issued(&i)),
+        _ => validator.roots.candidates(&subject.issuer).iter().any(root_issued),
+    }
+}
+
+/// For each certificate in `bundle` without a likely issuer,
+/// appends the certificates fetched from its caIssuers URIs,
+/// and then repeats on the fetched certificates, within `limits`.
+/// Returns the number of certificates appended.
+///
+/// `missing_issuers(bundle, indices)` returns the certificates among
+/// `bundle[indices]` without a likely issuer (see [`has_issuer`]), so that
+/// the caller can avoid holding the validator (e.g., its lock) while fetching.
+///
+/// NOTE: this is not verified; the extended bundle
+/// still goes through the verified validation.
+pub(crate) fn fetch_intermediates(
+    fetcher: &dyn IntermediateFetcher,
+    limits: &FetchLimits,
+    bundle: &mut Vec<Arc<ParsedCertificate>>,
+    missing_issuers: impl Fn(&[Arc<ParsedCertificate>], &[usize]) -> Vec<usize>,
+) -> usize {
+    let mut fetched = 0;
+    let mut seen_uris = HashSet::new();
+    let mut frontier = (0..bundle.len()).collect::<Vec<_>>();
+
+    for _ in 0..limits.max_depth {
+        let mut next = Vec::new();
+
+        for idx in missing_issuers(bundle, &frontier) {
+            let uris = match &bundle[idx].abs().ext_authority_info_access {
+                Some(aia) => aia.ca_issuers.clone(),
+                None => continue,
+            };
+
+            for uri in uris {
+                if !seen_uris.insert(uri.clone()) {
+                    continue;
+                }
+
+                for der in fetcher.fetch(&uri) {
+                    if fetched >= limits.max_certs {
+                        return fetched;
+                    }
+
+                    // Skip certificates that fail to parse or are already in the bundle
+                    let Ok(cert) = ParsedCertificate::from_der(der) else {
+                        continue;
+                    };
+                    if bundle
+                        .iter()
+                        .any(|other| other.abs().fingerprint == cert.abs().fingerprint)
+                    {
+                        continue;
+                    }
+
+                    fetched += 1;
+                    next.push(bundle.len());
+                    bundle.push(Arc::new(cert));
+                }
+            }
+        }
+
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+
+    fetched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{Task, Validator};
+    use crate::test_fixtures::{chrome_validator, google_chain_der, TIMESTAMP};
+
+    /// caIssuers URI in the leaf of `google.pem`
+    const WR2_URI: &str = "http://i.pki.goog/wr2.crt";
+
+    fn validator(fetcher: Option<(HashMap<String, Vec<Vec<u8>>>, FetchLimits)>) -> Validator<'static> {
+        let mut validator = chrome_validator();
+        if let Some((fetcher, limits)) = fetcher {
+            validator.set_intermediate_fetcher(fetcher, limits);
+        }
+        validator
+    }
+
+    #[test]
+    fn test_aia_uris() {
+        let chain = google_chain_der();
+        let leaf = ParsedCertificate::from_der(chain[0].clone()).unwrap();
+        let aia = leaf.abs().ext_authority_info_access.as_ref().unwrap();
+
+        assert_eq!(aia.ca_issuers, vec![WR2_URI.to_string()]);
+        assert_eq!(aia.ocsp, vec!["http://o.pki.goog/wr2".to_string()]);
+    }
+
+    #[test]
+    fn test_fetch_intermediates() {
+        let chain = google_chain_der();
+        let leaf_only = [chain[0].as_slice()];
+        let task = Task::new_server_auth(Some("google.com"), TIMESTAMP);
+
+        // The leaf alone does not reach a root
+        assert!(!validator(None).validate_der(leaf_only.into_iter(), &task).unwrap());
+
+        let fetcher = HashMap::from([(WR2_URI.to_string(), vec![chain[1].clone()])]);
+        let with_fetcher = validator(Some((fetcher.clone(), FetchLimits::default())));
+        assert!(with_fetcher.validate_der(leaf_only.into_iter(), &task).unwrap());
+
+        // Fetched certificates are still subject to the policy
+        let wrong_host = Task::new_server_auth(Some("example.com"), TIMESTAMP);
+        assert!(!with_fetcher.validate_der(leaf_only.into_iter(), &wrong_host).unwrap());
+
+        // No fetching allowed
+        let no_certs = FetchLimits {
+            max_depth: 3,
+            max_certs: 0,
+        };
+        assert!(!validator(Some((fetcher, no_certs)))
+            .validate_der(leaf_only.into_iter(), &task)
+            .unwrap());
+
+        // The fetched certificate is not an issuer of the leaf
+        let fetcher = HashMap::from([(WR2_URI.to_string(), vec![chain[2].clone()])]);
+        assert!(!validator(Some((fetcher, FetchLimits::default())))
+            .validate_der(leaf_only.into_iter(), &task)
+            .unwrap());
+    }
+}
@@ -19,6 +19,20 @@ pub fn sha256_digest(data: &[u8]) -> (res: Vec<u8>)
     ).as_ref().to_vec()
 }
 
+/// NOTE: SHA-1 is only used to match OCSP CertIDs (RFC 6960, Section 4.1.1),
+/// not for any signature verification
+pub uninterp spec fn spec_sha1_digest(data: Seq<u8>) -> Seq<u8>;
+
+#[verifier::external_body]
+pub fn sha1_digest(data: &[u8]) -> (res: Vec<u8>)
+    ensures res@ == spec_sha1_digest(data@)
+{
+    aws_lc_rs::digest::digest(
+        &aws_lc_rs::digest::SHA1_FOR_LEGACY_USE_ONLY,
+        data,
+    ).as_ref().to_vec()
+}
+
 const HEX_UPPER: [char; 16] = [ '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F' ];
 
 /// Convert any sequence of bytes to its upper-case hex encoding
@@ -43,73 +43,210 @@ pub open spec fn spec_normalize_string_helper(s: Seq<char>, seen_nw: bool, seen_
     }
 }
 
-/// Verify the subject cert's signature using issuer's public key
+/// Algorithm of a public key, as far as signature verification is concerned
+#[derive(Copy, Clone)]
+pub enum KeyAlg {
+    RSAEncryption,
+    RSASSAPSS,
+    ECP256,
+    ECP384,
+    ECP521,
+    Ed25519,
+    Unsupported,
+}
+
+/// Spec version of [`IssuerKey`]: the key algorithm and the raw public key
+pub type SpecIssuerKey = (KeyAlg, Seq<u8>);
+
+/// The public key of an issuer (i.e., the parts of its subject public key info
+/// used for signature verification), which can be kept without the
+/// parsed certificate (e.g., for root certificates, see `Roots`)
+pub struct IssuerKey<'a> {
+    pub alg: KeyAlg,
+    pub pub_key: &'a [u8],
+}
+
+impl<'a> View for IssuerKey<'a> {
+    type V = SpecIssuerKey;
+
+    open spec fn view(&self) -> Self::V {
+        (self.alg, self.pub_key@)
+    }
+}
+
+/// The public key of `issuer` used for signature verification
+pub open spec fn spec_issuer_key(issuer: SpecCertificateValue) -> SpecIssuerKey {
+    let alg = match issuer.cert.subject_key.alg.param {
+        SpecAlgorithmParamValue::RSAEncryption(..) => KeyAlg::RSAEncryption,
+        SpecAlgorithmParamValue::RSASSAPSS(..) => KeyAlg::RSASSAPSS,
+        SpecAlgorithmParamValue::ECPublicKey(curve) =>
+            if curve == spec_oid!(EC_P_256) {
+                KeyAlg::ECP256
+            } else if curve == spec_oid!(EC_P_384) {
+                KeyAlg::ECP384
+            } else if curve == spec_oid!(EC_P_521) {
+                KeyAlg::ECP521
+            } else {
+                KeyAlg::Unsupported
+            },
+        SpecAlgorithmParamValue::Ed25519(..) => KeyAlg::Ed25519,
+        _ => KeyAlg::Unsupported,
+    };
+
+    (alg, BitStringValue::spec_bytes(issuer.cert.subject_key.pub_key))
+}
+
+/// Verify a signature over `tbs` using issuer's public key (see [`spec_issuer_key`])
 ///
 /// Currently we support these signature schemes:
 /// - RSA PKCS#1 v1.5 with SHA-224, SHA-256, SHA-384, SHA-512 (Evercrypt through libcrux)
+/// - RSASSA-PSS with SHA-256, SHA-384, SHA-512 (Evercrypt through libcrux)
 /// - P-256 with SHA-256, SHA-384, SHA-512 (Evercrypt through libcrux)
 /// - P-384 with SHA-256, SHA-384 (AWS-LC)
+/// - P-521 with SHA-256, SHA-384, SHA-512 (AWS-LC, unverified; not available with `verified-crypto`)
+/// - Ed25519 (HACL* through libcrux)
 ///
 /// NOTE: P-384 + SHA-256 is not yet verified in AWS-LC
 /// Also P-384 + SHA-384 is only verified for Intel CPUs (SandyBridge+)
 /// See https://github.com/awslabs/aws-lc-verification
-///
-/// NOTE: Comparison of subject.sig_alg == subject.cert.signature is done in the policy
-pub open spec fn spec_verify_signature(issuer: SpecCertificateValue, subject: SpecCertificateValue) -> bool
+pub open spec fn spec_verify_signed_data(
+    issuer: SpecIssuerKey,
+    sig_alg: SpecAlgorithmIdentifierValue,
+    sig: Seq<u8>,
+    tbs: Seq<u8>,
+) -> bool
 {
-    &&& ASN1(TBSCertificate)@.spec_serialize(subject.cert) matches Ok(tbs_cert)
-    &&& {
-        // RSA
-        ||| {
-            &&& issuer.cert.subject_key.alg.param is RSAEncryption
-            &&& {
-                ||| subject.sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA224)
-                ||| subject.sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA256)
-                ||| subject.sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA384)
-                ||| subject.sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA512)
-            }
-            &&& rsa::spec_pkcs1_v1_5_load_pub_key(BitStringValue::spec_bytes(issuer.cert.subject_key.pub_key)) matches Some(pub_key)
-            &&& rsa::spec_pkcs1_v1_5_verify(
-                subject.sig_alg,
-                pub_key,
-                BitStringValue::spec_bytes(subject.sig),
-                tbs_cert,
-            )
+    let (alg, pub_key) = issuer;
+
+    // RSA
+    ||| {
+        &&& alg is RSAEncryption
+        &&& {
+            ||| sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA224)
+            ||| sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA256)
+            ||| sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA384)
+            ||| sig_alg.id == spec_oid!(RSA_SIGNATURE_SHA512)
         }
+        &&& rsa::spec_pkcs1_v1_5_load_pub_key(pub_key) matches Some(rsa_key)
+        &&& rsa::spec_pkcs1_v1_5_verify(
+            sig_alg,
+            rsa_key,
+            sig,
+            tbs,
+        )
+    }
 
-        // ECDSA P-256
-        ||| {
-            &&& issuer.cert.subject_key.alg.param matches SpecAlgorithmParamValue::ECPublicKey(curve)
-            &&& curve == spec_oid!(EC_P_256)
-            &&& {
-                ||| subject.sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA256)
-                ||| subject.sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA384)
-                ||| subject.sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA512)
-            }
-            &&& ecdsa::spec_p256_verify(
-                subject.sig_alg,
-                BitStringValue::spec_bytes(issuer.cert.subject_key.pub_key),
-                BitStringValue::spec_bytes(subject.sig),
-                tbs_cert,
-            )
+    // RSASSA-PSS
+    // NOTE: RFC 4055 allows the issuer key to be either rsaEncryption
+    // or id-RSASSA-PSS, and the latter can only be used for PSS
+    ||| {
+        &&& {
+            ||| alg is RSAEncryption
+            ||| alg is RSASSAPSS
         }
+        &&& sig_alg.id == spec_oid!(RSASSA_PSS)
+        &&& spec_verify_rsa_pss(pub_key, sig_alg, sig, tbs)
+    }
 
-        // ECDSA P-384
-        ||| {
-            &&& issuer.cert.subject_key.alg.param matches SpecAlgorithmParamValue::ECPublicKey(curve)
-            &&& curve == spec_oid!(EC_P_384)
-            &&& {
-                ||| subject.sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA256)
-                ||| subject.sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA384)
-            }
-            &&& ecdsa::spec_p384_verify(
-                subject.sig_alg,
-                BitStringValue::spec_bytes(issuer.cert.subject_key.pub_key),
-                BitStringValue::spec_bytes(subject.sig),
-                tbs_cert,
-            )
+    // ECDSA P-256
+    ||| {
+        &&& alg is ECP256
+        &&& {
+            ||| sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA256)
+            ||| sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA384)
+            ||| sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA512)
         }
+        &&& ecdsa::spec_p256_verify(
+            sig_alg,
+            pub_key,
+            sig,
+            tbs,
+        )
     }
+
+    // ECDSA P-384
+    ||| {
+        &&& alg is ECP384
+        &&& {
+            ||| sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA256)
+            ||| sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA384)
+        }
+        &&& ecdsa::spec_p384_verify(
+            sig_alg,
+            pub_key,
+            sig,
+            tbs,
+        )
+    }
+
+    // ECDSA P-521
+    ||| {
+        &&& alg is ECP521
+        &&& {
+            ||| sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA256)
+            ||| sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA384)
+            ||| sig_alg.id == spec_oid!(ECDSA_SIGNATURE_SHA512)
+        }
+        &&& ecdsa::spec_p521_verify(
+            sig_alg,
+            pub_key,
+            sig,
+            tbs,
+        )
+    }
+
+    // Ed25519
+    // NOTE: Ed448 keys are parsed but not yet supported for verification
+    ||| {
+        &&& alg is Ed25519
+        &&& sig_alg.id == spec_oid!(ED25519)
+        &&& eddsa::spec_ed25519_verify(
+            pub_key,
+            sig,
+            tbs,
+        )
+    }
+}
+
+/// RSASSA-PSS verification, where `pub_key` is encoded as RSAPublicKey
+/// (for both rsaEncryption and id-RSASSA-PSS keys)
+pub open spec fn spec_verify_rsa_pss(
+    pub_key: Seq<u8>,
+    sig_alg: SpecAlgorithmIdentifierValue,
+    sig: Seq<u8>,
+    tbs: Seq<u8>,
+) -> bool
+{
+    &&& rsa::spec_pkcs1_v1_5_load_pub_key(pub_key) matches Some(pub_key)
+    &&& rsa::spec_pss_verify(sig_alg, pub_key, sig, tbs)
+}
+
+/// Verify the subject cert's signature using issuer's public key
+///
+/// NOTE: Comparison of subject.sig_alg == subject.cert.signature is done in the policy
+pub open spec fn spec_verify_signature(issuer: SpecIssuerKey, subject: SpecCertificateValue) -> bool
+{
+    &&& ASN1(TBSCertificate)@.spec_serialize(subject.cert) matches Ok(tbs_cert)
+    &&& spec_verify_signed_data(issuer, subject.sig_alg, BitStringValue::spec_bytes(subject.sig), tbs_cert)
+}
+
+/// Verify the CRL's signature using issuer's public key
+///
+/// NOTE: Comparison of crl.sig_alg == crl.cert_list.signature is done here,
+/// since CRLs are not checked by any policy
+pub open spec fn spec_verify_crl_signature(issuer: SpecIssuerKey, crl: SpecCertificateListValue) -> bool
+{
+    &&& crl.sig_alg == crl.cert_list.signature
+    &&& ASN1(TBSCertList)@.spec_serialize(crl.cert_list) matches Ok(tbs_cert_list)
+    &&& spec_verify_signed_data(issuer, crl.sig_alg, BitStringValue::spec_bytes(crl.sig), tbs_cert_list)
+}
+
+/// Verify the signature of a basic OCSP response using the signer's public key
+/// (either the issuer of the certificate in question, or a delegated responder)
+pub open spec fn spec_verify_ocsp_signature(signer: SpecIssuerKey, resp: SpecBasicOCSPResponseValue) -> bool
+{
+    &&& ASN1(ResponseData)@.spec_serialize(resp.tbs_response_data) matches Ok(tbs_response_data)
+    &&& spec_verify_signed_data(signer, resp.sig_alg, BitStringValue::spec_bytes(resp.sig), tbs_response_data)
 }
 
 /// Exec version of spec_normalize_string
@@ -174,53 +311,146 @@ fn char_lower(c: char) -> (res: String)
     c.to_lowercase().to_string()
 }
 
-pub fn verify_signature(issuer: &CertificateValue<'_>, subject: &CertificateValue<'_>) -> (res: bool)
-    ensures res == spec_verify_signature(issuer@, subject@)
-{
-    let tbs_cert = subject.get().cert.serialize();
+impl<'a> IssuerKey<'a> {
+    /// Extracts the public key of a parsed certificate
+    pub fn from_cert(issuer: &'a CertificateValue<'_>) -> (res: IssuerKey<'a>)
+        ensures res@ == spec_issuer_key(issuer@)
+    {
+        let alg = match &issuer.get().cert.get().subject_key.alg.param {
+            AlgorithmParamValue::RSAEncryption(..) => KeyAlg::RSAEncryption,
+            AlgorithmParamValue::RSASSAPSS(..) => KeyAlg::RSASSAPSS,
+            AlgorithmParamValue::ECPublicKey(curve) =>
+                if curve.polyfill_eq(&oid!(EC_P_256)) {
+                    KeyAlg::ECP256
+                } else if curve.polyfill_eq(&oid!(EC_P_384)) {
+                    KeyAlg::ECP384
+                } else if curve.polyfill_eq(&oid!(EC_P_521)) {
+                    KeyAlg::ECP521
+                } else {
+                    KeyAlg::Unsupported
+                },
+            AlgorithmParamValue::Ed25519(..) => KeyAlg::Ed25519,
+            _ => KeyAlg::Unsupported,
+        };
+
+        IssuerKey {
+            alg,
+            pub_key: issuer.get().cert.get().subject_key.pub_key.bytes(),
+        }
+    }
+}
 
-    let sig_alg = &subject.get().sig_alg.get();
-    let pub_key = issuer.get().cert.get().subject_key.pub_key.bytes();
-    let sig = subject.get().sig.bytes();
+pub fn verify_signed_data(
+    issuer: &IssuerKey<'_>,
+    sig_alg: &AlgorithmIdentifierValue<'_>,
+    sig: &[u8],
+    tbs: &[u8],
+) -> (res: bool)
+    ensures res == spec_verify_signed_data(issuer@, sig_alg@, sig@, tbs@)
+{
+    let pub_key = issuer.pub_key;
 
-    match &issuer.get().cert.get().subject_key.alg.param {
-        // RSA PKCS#1 v1.5
-        AlgorithmParamValue::RSAEncryption(..) => {
+    match issuer.alg {
+        // RSA PKCS#1 v1.5 and RSASSA-PSS
+        KeyAlg::RSAEncryption => {
             if sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA224)) ||
                sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA256)) ||
                sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA384)) ||
                sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA512)) {
                 return match rsa::pkcs1_v1_5_load_pub_key(pub_key) {
-                    Ok(pub_key) => rsa::pkcs1_v1_5_verify(sig_alg, &pub_key, sig, tbs_cert).is_ok(),
+                    Ok(pub_key) => rsa::pkcs1_v1_5_verify(sig_alg, &pub_key, sig, tbs).is_ok(),
                     Err(..) => false,
                 }
             }
+
+            if sig_alg.id.polyfill_eq(&oid!(RSASSA_PSS)) {
+                return verify_rsa_pss(pub_key, sig_alg, sig, tbs);
+            }
         }
 
-        // ECDSA P-256 and P-384
-        AlgorithmParamValue::ECPublicKey(curve) => {
-            if curve.polyfill_eq(&oid!(EC_P_256)) && (
-                sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA256)) ||
-                sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA384)) ||
-                sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA512))
-            ) {
-                return ecdsa::p256_verify(sig_alg, pub_key, sig, tbs_cert).is_ok();
+        // RSASSA-PSS only
+        KeyAlg::RSASSAPSS => {
+            if sig_alg.id.polyfill_eq(&oid!(RSASSA_PSS)) {
+                return verify_rsa_pss(pub_key, sig_alg, sig, tbs);
             }
+        }
 
-            if curve.polyfill_eq(&oid!(EC_P_384)) && (
-                sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA256)) ||
-                sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA384))
-            ) {
-                return ecdsa::p384_verify(sig_alg, pub_key, sig, tbs_cert).is_ok();
+        // ECDSA P-256, P-384 and P-521
+        KeyAlg::ECP256 => {
+            if sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA256)) ||
+               sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA384)) ||
+               sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA512)) {
+                return ecdsa::p256_verify(sig_alg, pub_key, sig, tbs).is_ok();
             }
         }
 
-        _ => {}
+        KeyAlg::ECP384 => {
+            if sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA256)) ||
+               sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA384)) {
+                return ecdsa::p384_verify(sig_alg, pub_key, sig, tbs).is_ok();
+            }
+        }
+
+        KeyAlg::ECP521 => {
+            if sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA256)) ||
+               sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA384)) ||
+               sig_alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA512)) {
+                return ecdsa::p521_verify(sig_alg, pub_key, sig, tbs).is_ok();
+            }
+        }
+
+        // Ed25519
+        KeyAlg::Ed25519 => {
+            if sig_alg.id.polyfill_eq(&oid!(ED25519)) {
+                return eddsa::ed25519_verify(pub_key, sig, tbs).is_ok();
+            }
+        }
+
+        KeyAlg::Unsupported => {}
     }
 
     false
 }
 
+fn verify_rsa_pss(
+    pub_key: &[u8],
+    sig_alg: &AlgorithmIdentifierValue<'_>,
+    sig: &[u8],
+    tbs: &[u8],
+) -> (res: bool)
+    ensures res == spec_verify_rsa_pss(pub_key@, sig_alg@, sig@, tbs@)
+{
+    match rsa::pkcs1_v1_5_load_pub_key(pub_key) {
+        Ok(pub_key) => rsa::pss_verify(sig_alg, &pub_key, sig, tbs).is_ok(),
+        Err(..) => false,
+    }
+}
+
+pub fn verify_signature(issuer: &IssuerKey<'_>, subject: &CertificateValue<'_>) -> (res: bool)
+    ensures res == spec_verify_signature(issuer@, subject@)
+{
+    let tbs_cert = subject.get().cert.serialize();
+    verify_signed_data(issuer, &subject.get().sig_alg.get(), subject.get().sig.bytes(), tbs_cert)
+}
+
+pub fn verify_crl_signature(issuer: &IssuerKey<'_>, crl: &CertificateListValue<'_>) -> (res: bool)
+    ensures res == spec_verify_crl_signature(issuer@, crl@)
+{
+    if !crl.get().sig_alg.get().polyfill_eq(&crl.get().cert_list.get().signature) {
+        return false;
+    }
+
+    let tbs_cert_list = crl.get().cert_list.serialize();
+    verify_signed_data(issuer, &crl.get().sig_alg.get(), crl.get().sig.bytes(), tbs_cert_list)
+}
+
+pub fn verify_ocsp_signature(signer: &IssuerKey<'_>, resp: &BasicOCSPResponseValue<'_>) -> (res: bool)
+    ensures res == spec_verify_ocsp_signature(signer@, resp@)
+{
+    let tbs_response_data = resp.tbs_response_data.serialize();
+    verify_signed_data(signer, &resp.sig_alg, resp.sig.bytes(), tbs_response_data)
+}
+
 }
 
 #[cfg(test)]
@@ -29,19 +29,32 @@
 //!
 //! We have also modeled the X.509 validation policies in
 //! Firefox ([`FirefoxPolicy`]) and OpenSSL ([`OpenSSLPolicy`]).
+//! To enforce additional rules from RFC 5280 and CA/B BRs
+//! on top of these (or from scratch), see [`PolicyBuilder`].
 
 #![deny(unsafe_code)]
 #![warn(rust_2018_idioms)]
 
+mod anchors;
 mod convert;
 mod hash;
 mod issue;
 mod signature;
 
 mod api;
+mod batch;
+mod cache;
 mod error;
+mod fetch;
 mod policy;
+mod report;
 mod utils;
 mod validator;
 
+#[cfg(test)]
+mod test_fixtures;
+
 pub use api::*;
+pub use batch::{BatchResults, BatchValidator, ValidationFuture};
+pub use cache::IntermediateCache;
+pub use fetch::{FetchLimits, IntermediateFetcher};
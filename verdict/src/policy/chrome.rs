@@ -32,6 +32,11 @@ impl Policy for ChromePolicy {
     fn valid_chain(&self, chain: &Vec<&ExecCertificate>, task: &ExecTask) -> bool {
         internal::exec_valid_chain(self, chain, task)
     }
+
+    fn issuer_lookup(&self) -> (res: IssuerLookup) {
+        // Chrome does not check the AKID here
+        IssuerLookup::Name
+    }
 }
 
 // Automatically prove some standard requirements
@@ -75,6 +80,8 @@ standard::auto_std! {
     ChromePolicy => standard::NoDSA {}
 
     // ChromePolicy => standard::RSA2048 {}
+
+    ChromePolicy => standard::AllowedCurves {}
 }
 
 impl ChromePolicy {
@@ -88,14 +95,6 @@ impl ChromePolicy {
 
             known_roots: strs_to_strings(&["5A2FC03F0C83B090BBFA40604B0988446C7636183DF9846E17101A447FB8EFD6", "125609AA301DA0A249B97A8239CB6A34216F44DCAC9F3954B14292F2E8C8608F", "BC4D809B15189D78DB3E1D8CF4F9726A795DA1643CA5F1358E1DDB0EDC0D7EB3", "86A1ECBA089C4A8D3BBE2734C612BA341D813E043CF9E8A862CD5C57A36BBE6B", "9A6EC012E1A7DA9DBE34194D478AD7C0DB1822FB071DF12981496ED104384113", "55926084EC963A64B96E2ABE01CE0BA86A64FBFEBCC7AAB5AFC155B37FD76066", "0376AB1D54C5F9803CE4B2E201A0EE7EEF7B57B636E8A93C9B8D4860C96F5FA7", "0A81EC5A929777F145904AF38D5D509F66B5E2C58FCDB531058B0E17F3F0B41B", "70A73F7F376B60074248904534B11482D5BF0E698ECC498DF52577EBF2E93B9A", "BD71FDF6DA97E4CF62D1647ADD2581B07D79ADF8397EB4ECBA9C5E8488821423", "F356BEA244B7A91EB35D53CA9AD7864ACE018E2D35D5F8F96DDF68A6F41AA474", "04048028BF1F2864D48F9AD4D83294366A828856553F3B14303F90147F5D40EF", "16AF57A9F676B0AB126095AA5EBADEF22AB31119D644AC95CD4B93DBF3F26AEB", "9A114025197C5BB95D94E63D55CD43790847B646B23CDF11ADA4A00EFF15FB48", "EDF7EBBCA27A2A384D387B7D4010C666E2EDB4843E4C29B4AE1D5B9332E6B24D", "E23D4A036D7B70E9F595B1422079D2B91EDFBB1FB651A0633EAA8A9DC5F80703", "E3B6A2DB2ED7CE48842F7AC53241C7B71D54144BFB40C11F3F1D0B42F5EEA12D", "2A99F5BC1174B73CBB1D620884E01C34E51CCB3978DA125F0E33268883BF4158", "0F993C8AEF97BAAF5687140ED59AD1821BB4AFACF0AA9A58B5D57A338A3AFBCB", "EAA962C4FA4A6BAFEBE415196D351CCD888D4F53F3FA8AE6D7C466A94E6042BB", "5C58468D55F58E497E743982D2B50010B6D165374ACF83A7D4A32DB768C4408E", "5CC3D78E4E1D5E45547A04E6873E64F90CF9536D1CCC2EF800F355C4C5FD70FD", "063E4AFAC491DFD332F3089B8542E94617D893D7FE944E10A7937EE29D9693C0", "D7A7A0FB5D7E2731D771E9484EBCDEF71D5F0C3E0A2948782BC83EE0EA699EF4", "0C2CD63DF7806FA399EDE809116B575BF87989F06518F9808C860503178BAF66", "1793927A0614549789ADCE2F8F34F7F0B66D0F3AE3A3B84D21EC15DBBA4FADC7", "52F0E1C4E58EC629291B60317F074671B85D7EA80D5B07273463534B32B40234", "960ADF0063E96356750C2965DD0A0867DA0B9CBD6E77714AEAFB2349AB393DA3", "B6191A50D0C3977F7DA99BCDAAC86A227DAEB9679EC70BA3B0C9D92271C170D3", "3E9099B5015E8F486C00BCEA9D111EE721FABA355A89BCF1DF69561E3DC6325C", "7D05EBB682339F8C9451EE094EEBFEFA7953A114EDB2F44949452FAB7D2FC185", "7E37CB8B4C47090CAB36551BA6F45DB840680FBA166A952DB100717F43053FC2", "4348A0E9444C78CB265E058D5E8944B4D84F9662BD26DB257F8934A443C70161", "CB3CCBB76031E5E0138F8DD39A23F9DE47FFC35E43C1144CEA27D46A5AB1CB5F", "31AD6648F8104138C738F39EA4320133393E3A18CC02296EF97C2AC9EF6731D0", "7431E5F4C3C1CE4690774F0B61E05440883BA9A01ED00BA6ABD7806ED3B118CF", "552F7BDCF1A7AF9E6CE672017F4F12ABF77240C78E761AC203D1D9D20AC89988", "0687260331A72403D909F105E69BCF0D32E1BD2493FFC6D9206D11BCD6770739", "49E7A442ACF0EA6287050054B52564B650E4F49E42E348D6AA38E039E957B1C1", "EEC5496B988CE98625B934092EEC2908BED0B0F316C2D4730C84EAF1F3D34881", "88497F01602F3154246AE28C4D5AEF10F1D87EBB76626F4AE0B7F95BA7968799", "3E84BA4342908516E77573C0992F0979CA084E4685681FF195CCBA8A229B8A76", "6DC47172E01CBCB0BF62580D895FE2B8AC9AD4F873801E0C10B9C837D21EB177", "73C176434F1BC6D5ADF45B0E76E727287C8DE57616C1E6E6141A2B2CBC7D8E4C", "02ED0EB28C14DA45165C566791700D6451D7FB56F0B2AB1D3B8EB070E56EDFF5", "43DF5774B03E7FEF5FE40D931A7BEDF1BB2E6B42738C4E6D3841103D3AA7F339", "C0A6F4DC63A24BFDCF54EF2A6A082A0A72DE35803E2FF5FF527AE5D87206DFD5", "B0BFD52BB0D7D9BD92BF5D4DC13DA255C02C542F378365EA893911F55E55F23C", "FF856A2D251DCD88D36656F450126798CFABAADE40799C722DE4D2B5DB36A73A", "37D51006C512EAAB626421F1EC8C92013FC5F82AE98EE533EB4619B8DEB4D06C", "5EDB7AC43B82A06A8761E8D7BE4979EBF2611F7DD79BF91C1C6B566A219ED766", "B478B812250DF878635C2AA7EC7D155EAA625EE82916E2CD294361886CD1FBD4", "A0234F3BC8527CA5628EEC81AD5D69895DA5680DC91D1CB8477F33F878B95B0B", "A0459B9F63B22559F5FA5D4C6DB3F9F72FF19342033578F073BF1D1B46CBB912", "136335439334A7698016A0D324DE72284E079D7B5220BB8FBD747816EEBEBACA", "BEC94911C2955676DB6C0A550986D76E3BA005667C442C9762B4FBB773DE228C", "179FBC148A3DD00FD24EA13458CC43BFA7F59C8182D783A513F6EBEC100C8924", "EBD41040E4BB3EC742C9E381D31EF2A41A48B6685C96E7CEF3C1DF6CD4331C99", "CA42DD41745FD0B81EB902362CF9D8BF719DA1BD1B1EFC946F5B4C99F42C1B9E", "CBB522D7B7F127AD6A0113865BDF1CD4102E7D0759AF635A7CF4720DC963C53B", "C3846BF24B9E93CA64274C0EC67C1ECC5E024FFCACD2D74019350E81FE546AE4", "45140B3247EB9CC8C5B4F0D7B53091F73292089E6E5A63E2749DD3ACA9198EDA", "BC104F15A48BE709DCA542A7E1D4B9DF6F054527E802EAA92D595444258AFE71", "F9E67D336C51002AC054C632022D66DDA2E7E3FFF10AD061ED31D8BBB410CFB2", "5D56499BE4D2E08BCFCAD08A3E38723D50503BDE706948E42F55603019E528AE", "30D0895A9A448A262091635522D1F52010B5867ACAE12C78EF958FD4F4389F2F", "2530CC8E98321502BAD96F9B1FBA1B099E2D299E0F4548BB914F363BC0D4531F", "3C5F81FEA5FAB82C64BFA2EAECAFCDE8E077FC8620A7CAE537163DF36EDBF378", "6C61DAC3A2DEF031506BE036D2A6FE401994FBD13DF9C8D466599274C446EC98", "15F0BA00A3AC7AF3AC884C072B1011A077BD77C097F40164B2F8598ABD83860C", "41C923866AB4CAD6B7AD578081582E020797A6CBDF4FFF78CE8396B38937D7F5", "6B9C08E86EB0F767CFAD65CD98B62149E5494A67F5845E7BD1ED019F27B86BD6", "8A866FD1B276B57E578E921C65828A2BED58E9F2F288054134B7F1F4BFC9CC74", "85A0DD7DD720ADB7FF05F83D542B209DC7FF4528F7D677B18389FEA5E5C49E86", "8FE4FB0AF93A4D0D67DB0BEBB23E37C71BF325DCBCDD240EA04DAF58B47E1840", "18F1FC7F205DF8ADDDEB7FE007DD57E3AF375A9C4D8D73546BF4F1FED1E18D35", "88EF81DE202EB018452E43F864725CEA5FBD1FC2D9D205730709C5D8B8690F46", "A45EDE3BBBF09C8AE15C72EFC07268D693A21C996FD51E67CA079460FD6D8873", "4200F5043AC8590EBB527D209ED1503029FBCBD41CA1B506EC27F15ADE7DAC69", "BF0FEEFB9E3A581AD5F9E9DB7589985743D261085C4D314F6F5D7259AA421612", "F1C1B50AE5A20DD8030EC9F6BC24823DD367B5255759B4E71B61FCE9F7375D73", "513B2CECB810D4CDE5DD85391ADFC6C2DD60D87BB736D2B521484AA47A0EBEF6", "E75E72ED9F560EEC6EB4800073A43FC3AD19195A392282017895974A99026B6C", "7908B40314C138100B518D0735807FFBFCF8518A0095337105BA386B153DD927", "4D2491414CFE956746EC4CEFA6CF6F72E28A1329432F9D8A907AC4CB5DADC15A", "668C83947DA63B724BECE1743C31A0E6AED0DB8EC5B31BE377BB784F91B6716F", "3C4FB0B95AB8B30032F432B86F535FE172C185D0FD39865837CF36187FA6F428", "1465FA205397B876FAA6F0A9958E5590E40FCC7FAA4FB7C2C8677521FB5FB658", "2CE1CB0BF9D2F9E102993FBE215152C3B2DD0CABDE1C68E5319B839154DBB7F5", "568D6905A2C88708A4B3025190EDCFEDB1974A606A13C6E5290FCB2AE63EDAB5", "62DD0BE9B9F50A163EA0F8E75C053B1ECA57EA55C8688F647C6881F2C8357B95", "BE6C4DA2BBB9BA59B6F3939768374246C3C005993FA98F020D1DEDBED48A81D5", "7600295EEFE85B9E1FD624DB76062AAAAE59818A54D2774CD4C0B2C01131E1B3", "DD6936FE21F8F077C123A1A521C12224F72255B73E03A7260693E8A24B0FA389", "8D722F81A9C113C0791DF136A2966DB26C950A971DB46B4199F4EA54B78BFB9F", "A4310D50AF18A6447190372A86AFAF8B951FFB431D837F1E5688B45971ED1557", "4B03F45807AD70F21BFC2CAE71C9FDE4604C064CF5FFB686BAE5DBAAD7FDD34C", "C1B48299ABA5208FE9630ACE55CA68A03EDA5A519C8802A0D3A673BE8F8E557D", "91E2F5788D5810EBA7BA58737DE1548A8ECACD014598BC0B143E041B17052552", "FD73DAD31C644FF1B43BEF0CCDDA96710B9CD9875ECA7E31707AF3E96D522BBD", "59769007F7685D0FCD50872F9F95D5755A5B2B457D81F3692B610A98672F0E1B", "BFD88FE1101C41AE3E801BF8BE56350EE9BAD1A6B9BD515EDC5C6D5B8711AC44", "4FF460D54B9C86DABFBCFC5712E0400D2BED3FBC4D4FBDAA86E06ADCD2A9AD7A", "E793C9B02FD8AA13E21C31228ACCB08119643B749C898964B1746D46C3D4CBD2", "EB04CF5EB1F39AFA762F2BB120F296CBA520C1B97DB1589565B81CB9A17B7244", "69DDD7EA90BB57C93E135DC85EA6FCD5480B603239BDC454FC758B2A26CF7F79", "9ACFAB7E43C8D880D06B262A94DEEEE4B4659989C3D0CAF19BAF6405E41AB7DF", "2399561127A57125DE8CEFEA610DDF2FA078B5C8067F4E828290BFB860E84B3C", "CECDDC905099D8DADFC5B1D209B737CBE2C18CFB2C10C0FF0BCF0D3286FC1AA2", "EBC5570C29018C4D67B1AA127BAF12F703B4611EBC17B7DAB5573894179B93FA", "8ECDE6884F3D87B1125BA31AC3FCB13D7016DE7F57CC904FE1CB97C6AE98196E", "1BA5B2AA8C65401A82960118F80BEC4F62304D83CEC4713A19C39C011EA46DB4", "18CE6CFE7BF14E60B2E347B8DFE868CB31D02EBB3ADA271569F50343B46DB3A4", "E35D28419ED02025CFA69038CD623962458DA5C695FBDEA3C22B0BFB25897092", "B676F2EDDAE8775CD36CB0F63CD1D4603961F49E6265BA013A2F0307B6D0B804", "44B545AA8A25E65A73CA15DC27FC36D24C1CB9953A066539B11582DC487B4833", "A040929A02CE53B4ACF4F2FFC6981CE4496F755E6D45FE0B2A692BCD52523F36", "96BCEC06264976F37460779ACF28C5A7CFE8A3C0AAE11A8FFCEE05C0BDDF08C6", "54455F7129C20B1447C418F997168F24C58FC5023BF5DA5BE2EB6E1DD8902ED5", "A1339D33281A0B56E557D3D32B1CE7F9367EB094BD5FA72A7E5004C8DED7CAFE", "46EDC3689046D53A453FB3104AB80DCAEC658B2660EA1629DD7E867990648716", "BFFF8FD04433487D6A8AA60C1A29767A9FC2BBB05E420F713A13B992891D3893", "22A2C1F7BDED704CC1E701B5F408C310880FE956B5DE2A4A44F99C873A25A7C8", "2E7BF16CC22485A7BBE2AA8696750761B0AE39BE3B2FE9D0CC6D4EF73491425C", "3417BB06CC6007DA1B961C920B8AB4CE3FAD820E4AA30B9ACBC4A74EBDCEBC65", "85666A562EE0BE5CE925C1D8890A6F76A87EC16D4D7D5F29EA7419CF20123B69", "5A885DB19C01D912C5759388938CAFBBDF031AB2D48E91EE15589B42971D039C", "D40E9C86CD8FE468C1776959F49EA774FA548684B6C406F3909261F4DCE2575C", "0753E940378C1BD5E3836E395DAEA5CB839E5046F1BD0EAE1951CF10FEC7C965", "2CABEAFE37D06CA22ABA7391C0033D25982952C453647349763A3AB5AD6CCF69", "8560F91C3624DABA9570B5FEA0DBE36FF11A8323BE9486854FB3F34A5571198D"]),
 
-            symantec_roots: strs_to_strings(&["9ACFAB7E43C8D880D06B262A94DEEEE4B4659989C3D0CAF19BAF6405E41AB7DF", "FF856A2D251DCD88D36656F450126798CFABAADE40799C722DE4D2B5DB36A73A", "5EDB7AC43B82A06A8761E8D7BE4979EBF2611F7DD79BF91C1C6B566A219ED766", "023C81CCE8E7C64FA942D3C15048707D35D9BB5B87F4F544C5BF1BC5643AF2FA", "0999BF900BD5C297865E21E1AADE6CF6BB3A94D11AE5EA798442A4E2F813241F", "0BDD5ABE940CAAABE8B2BBA88348FB6F4AA4CC84436F880BECE66B48BDA913D8", "16A9E012D32329F282B10BBF57C7C0B42AE80F6AC9542EB409BC1C2CDE50D322", "17755A5C295F3D2D72E6F031A1F07F400C588B9E582B22F17EAE31A1590D1185", "1906C6124DBB438578D00E066D5054C6C37F0FA6028C05545E0994EDDAEC8629", "1916F3508EC3FAD795F8DC4BD316F9C6085A64DE3C4153AC6D62D5EA19515D39", "1D75D0831B9E0885394D32C7A1BFDB3DBC1C28E2B0E8391FB135981DBC5BA936", "22076E5AEF44BB9A416A28B7D1C44322D7059F60FEFFA5CAF6C5BE8447891303", "25B41B506E4930952823A6EB9F1D31DEF645EA38A5C6C6A96D71957E384DF058", "26C18DC6EEA6F632F676BCEBA1D8C2B48352F29C2D5FCDA878E09DCB832DD6E5", "2DC9470BE63EF4ACF1BD828609402BB7B87BD99638A643934E88682D1BE8C308", "2DEE5171596AB8F3CD3C7635FEA8E6C3006AA9E31DB39D03A7480DDB2428A33E", "3027A298FA57314DC0E3DD1019411B8F404C43C3F934CE3BDF856512C80AA15C", "31512680233F5F2A1F29437F56D4988CF0AFC41CC6C5DA6275928E9C0BEADE27", "43B3107D7342165D406CF975CD79B36ED1645048F05D7FF6EA0096E427B7DB84", "463DBB9B0A26ED2616397B643125FBD29B66CF3A46FDB4384B209E78237A1AFF", "479D130BF3FC61DC2F1D508D239A13276AE7B3C9841011A02C1402C7E677BD5F", "4905466623AB4178BE92AC5CBD6584F7A1E17F27652D5A85AF89504EA239AAAA", "495A96BA6BAD782407BD521A00BACE657BB355555E4BB7F8146C71BBA57E7ACE", "4BA6031CA305B09E53BDE3705145481D0332B651FE30370DD5254CC4D2CB32F3", "5192438EC369D7EE0CE71F5C6DB75F941EFBF72E58441715E99EAB04C2C8ACEE", "567B8211FD20D3D283EE0CD7CE0672CB9D99BC5B487A58C9D54EC67F77D4A8F5", "5C4F285388F38336269A55C7C12C0B3CA73FEF2A5A4DF82B89141E841A6C4DE4", "67DC4F32FA10E7D01A79A073AA0C9E0212EC2FFC3D779E0AA7F9C0F0E1C2C893", "6B86DE96A658A56820A4F35D90DB6C3EFDD574CE94B909CB0D7FF17C3C189D83", "7006A38311E58FB193484233218210C66125A0E4A826AED539AC561DFBFBD903", "781F1C3A6A42E3E915222DB4967702A2E577AEB017075FA3C159851FDDD0535E", "7CAA03465124590C601E567E52148E952C0CFFE89000530FE0D95B6D50EAAE41", "809F2BAAE35AFB4F36BD6476CE75C2001077901B6AF5C4DAB82E188C6B95C1A1", "81A98FC788C35F557645A95224E50CD1DAC8FFB209DC1E5688AA29205F132218", "860A7F19210D5EAD057A78532B80951453CB2907315F3BA7AA47B69897D70F3F", "87AF34D66FB3F2FDF36E09111E9ABA2F6F44B207F3863F3D0B54B25023909AA5", "95735473BD67A3B95A8D5F90C5A21ACE1E0D7947320674D4AB847972B91544D2", "967B0CD93FCEF7F27CE2C245767AE9B05A776B0649F9965B6290968469686872", "9699225C5DE52E56CDD32DF2E96D1CFEA5AA3CA0BB52CD8933C23B5C27443820", "9C6F6A123CBAA4EE34DBECEEE24C97D738878CB423F3C2273903424F5D1F6DD5", "A6F1F9BF8A0A9DDC080FB49B1EFC3D1A1C2C32DC0E136A5B00C97316F2A3DC11", "AB3876C3DA5DE0C9CF6736868EE5B88BF9BA1DFF9C9D72D2FE5A8D2F78302166", "AB39A4B025955691A40269F353FA1D5CB94EAF6C7EA9808484BBBB62FD9F68F3", "AB5CDB3356397356D6E691973C25B8618B65D76A90486EA7A8A5C17767F4673A", "AB98495276ADF1ECAFF28F35C53048781E5C1718DAB9C8E67A504F4F6A51328F", "ACF65E1D62CB58A2BAFD6FFAB40FB88699C47397CF5CB483D42D69CAD34CD48B", "AF207C61FD9C7CF92C2AFE8154282DC3F2CBF32F75CD172814C52B03B7EBC258", "B1124142A5A1A5A28819C735340EFF8C9E2F8168FEE3BA187F253BC1A392D7E2", "B2DEF5362AD3FACD04BD29047A43844F767034EA4892F80E56BEE690243E2502", "BCFB44AAB9AD021015706B4121EA761C81C9E88967590F6F94AE744DC88B78FB", "C07135F6B452398264A4776DBD0A6A307C60A36F967BD26321DCB817B5C0C481", "CAB482CD3E820C5CE72AA3B6FDBE988BB8A4F0407ECAFD8C926E36824EAB92DD", "D2F91A04E3A61D4EAD7848C8D43B5E1152D885727489BC65738B67C0A22785A7", "D3A25DA80DB7BAB129A066AB41503DDDFFA02C768C0589F99FD71193E69916B6", "D4AF6C0A482310BD7C54BB7AB121916F86C0C07CD52FCAC32D3844C26005115F", "DA800B80B2A87D399E66FA19D72FDF49983B47D8CF322C7C79503A0C7E28FEAF", "F15F1D323ED9CA98E9EA95B33EC5DDA47EA4C329F952C16F65AD419E64520476", "F2E9365EA121DF5EEBD8DE2468FDC171DC0A9E46DADC1AB41D52790BA980A7C2", "F53C22059817DD96F400651639D2F857E21070A59ABED9079400D9F695506900", "F6B59C8E2789A1FD5D5B253742FEADC6925CB93EDC345E53166E12C52BA2A601", "FF5680CD73A5703DA04817A075FD462506A73506C4B81A1583EF549478D26476"]),
-            symantec_exceptions: strs_to_strings(&["17F96609AC6AD0A2D6AB0A21B2D1B5B2946BD04DBF120703D1DEF6FB62F4B661", "3DB76D1DD7D3A759DCCC3F8FA7F68675C080CB095E4881063A6B850FDD68B8BC", "6115F06A338A649E61585210E76F2ECE3989BCA65A62B066040CD7C5F408EDD0", "904FB5A437754B1B32B80EBAE7416DB63D05F56A9939720B7C8E3DCC54F6A3D1", "AC2B922ECFD5E01711772FEA8ED372DE9D1E2245FCE3F57A9CDBEC77296A424B", "A4FE7C7F15155F3F0AEF7AAA83CF6E06DEB97CA3F909DF920AC1490882D488ED", "8BB593A93BE1D0E8A822BB887C547890C3E706AAD2DAB76254F97FB36B82FC26", "B94C198300CEC5C057AD0727B70BBE91816992256439A7B32F4598119DDA9C97", "C3F697A92A293D86F9A3EE7CCB970E20E0050B8728CC83ED1B996CE9005D4C36", "7CAC9A0FF315387750BA8BAFDB1C2BC29B3F0BBA16362CA93A90F84DA2DF5F3E", "AC50B5FB738AED6CB781CC35FBFFF7786F77109ADA7C08867C04A573FD5CF9EE"]),
-
-            india_trusted: strs_to_strings(&["F375E2F77A108BACC4234894A9AF308EDECA1ACD8FBDE0E7AAA9634E9DAF7E1C", "2D66A702AE81BA03AF8CFF55AB318AFA919039D9F31B4D64388680F81311B65A", "60109BC6C38328598A112C7A25E38B0F23E5A7511CB815FB64E0C4FF05DB7DF7"]),
-            india_domains: strs_to_strings(&["*.gov.in", "*.nic.in", "*.ac.in", "*.rbi.org.in", "*.bankofindia.co.in", "*.ncode.in", "*.tcs.co.in"]),
-
-            anssi_trusted: strs_to_strings(&["B9BEA7860A962EA3611DAB97AB6DA3E21C1068B97D55575ED0E11279C11C8932"]),
-            anssi_domains: strs_to_strings(&["*.fr", "*.gp", "*.gf", "*.mq", "*.re", "*.yt", "*.pm", "*.bl", "*.mf", "*.wf", "*.pf", "*.nc", "*.tf"]),
         }
     }
 }
@@ -117,6 +116,8 @@ use ExecSubjectAltName as SubjectAltName;
 use ExecNameConstraints as NameConstraints;
 use ExecCertificatePolicies as CertificatePolicies;
 use ExecCertificate as Certificate;
+use ExecPurpose as Purpose;
+use ExecPeerName as PeerName;
 use ExecTask as Task;
 use ExecPolicyError as PolicyError;
 use ExecDistinguishedName as DistinguishedName;
@@ -135,6 +136,14 @@ use exec_has_directory_name_constraint as has_directory_name_constraint;
 use exec_has_dns_name_constraint as has_dns_name_constraint;
 use exec_has_ip_addr_name_constraint as has_ip_addr_name_constraint;
 use exec_check_duplicate_extensions as check_duplicate_extensions;
+use exec_eku_permits_purpose as eku_permits_purpose;
+use exec_match_email as match_email;
+use exec_match_ip_addr as match_ip_addr;
+use exec_chain_not_revoked as chain_not_revoked;
+use exec_chain_ocsp_ok as chain_ocsp_ok;
+use exec_common_pss_params as common_pss_params;
+use exec_anchor_permits_task as anchor_permits_task;
+use exec_check_validity as check_validity;
 
 #[allow(unused)]
 pub struct Policy {
@@ -148,22 +157,28 @@ pub struct Policy {
     /// Known root certificates
     pub known_roots: Seq<SpecString>,
 
-    pub symantec_roots: Seq<SpecString>,
-    pub symantec_exceptions: Seq<SpecString>,
+    // NOTE: indiaFingerprint/Domain and anssiFingerprint/Domain in Hammurabi
+    // are attached to the roots as `TrustAnchor::permitted_dns_names`
+    // (by default when validating with this policy, see `RootStore`)
 
-    // indiaFingerprint/Domain
-    pub india_trusted: Seq<SpecString>,
-    pub india_domains: Seq<SpecString>,
-
-    // anssiFingerprint/Domain
-    pub anssi_trusted: Seq<SpecString>,
-    pub anssi_domains: Seq<SpecString>,
+    // NOTE: symantecFingerprint and symantecExceptions in Hammurabi (badSymantec)
+    // are attached to the roots as `TrustAnchor::server_distrust_after` and
+    // `TrustAnchor::server_distrust_exceptions` (by default, see `RootStore`)
 }
 
 pub open spec fn is_valid_pki(cert: &Certificate) -> bool {
     match cert.subject_key {
-        SubjectKey::RSA { mod_length } => mod_length >= 1024,
+        SubjectKey::RSA { mod_length, .. } => mod_length >= 1024,
         SubjectKey::DSA { .. } => false,
+        // Only P-256, P-384 and P-521 are accepted by Chrome's path builder
+        SubjectKey::EC { curve, .. } => {
+            ||| curve == "1.2.840.10045.3.1.7"@
+            ||| curve == "1.3.132.0.34"@
+            ||| curve == "1.3.132.0.35"@
+        }
+        // Only RSA and EC keys are accepted by Chrome's path builder
+        SubjectKey::Ed25519 => false,
+        SubjectKey::Ed448 => false,
         SubjectKey::Other => true,
     }
 }
@@ -203,46 +218,62 @@ pub open spec fn not_in_crl(env: &Policy, cert: &Certificate) -> bool {
     forall |i: usize| 0 <= i < env.crl.len() ==> &cert.fingerprint != env.crl[i as int]
 }
 
-pub open spec fn strong_signature(alg: &SpecString) -> bool {
+pub open spec fn strong_signature(alg: &SignatureAlgorithm) -> bool {
     // ECDSA + SHA512
-    ||| alg == "1.2.840.10045.4.3.2"@
+    ||| &alg.id == "1.2.840.10045.4.3.2"@
     // ECDSA + SHA384
-    ||| alg == "1.2.840.10045.4.3.3"@
+    ||| &alg.id == "1.2.840.10045.4.3.3"@
     // ECDSA + SHA512
-    ||| alg == "1.2.840.10045.4.3.4"@
+    ||| &alg.id == "1.2.840.10045.4.3.4"@
     // RSA + SHA256
-    ||| alg == "1.2.840.113549.1.1.11"@
+    ||| &alg.id == "1.2.840.113549.1.1.11"@
     // RSA + SHA384
-    ||| alg == "1.2.840.113549.1.1.12"@
+    ||| &alg.id == "1.2.840.113549.1.1.12"@
     // RSA + SHA512
-    ||| alg == "1.2.840.113549.1.1.13"@
-    // RSA-PSS + SHA256
-    ||| alg == "1.2.840.113549.1.1.10"@
+    ||| &alg.id == "1.2.840.113549.1.1.13"@
+    // RSA-PSS + SHA256, SHA384 or SHA512
+    ||| &alg.id == "1.2.840.113549.1.1.10"@ && (&alg.pss matches Some(pss) && common_pss_params(pss))
 }
 
-pub open spec fn key_usage_valid(cert: &Certificate) -> bool {
+pub open spec fn key_usage_valid(task: &Task, cert: &Certificate) -> bool {
     &cert.ext_key_usage matches Some(key_usage)
     ==>
         if &cert.ext_basic_constraints matches Some(bc) && bc.is_ca {
             key_usage.key_cert_sign
         } else {
-            !key_usage.key_cert_sign && {
-                ||| key_usage.digital_signature
-                ||| key_usage.key_encipherment
-                ||| key_usage.key_agreement
+            !key_usage.key_cert_sign && match task.purpose {
+                Purpose::ServerAuth => {
+                    ||| key_usage.digital_signature
+                    ||| key_usage.key_encipherment
+                    ||| key_usage.key_agreement
+                }
+
+                // TLS client keys are only used for signing (or key agreement)
+                Purpose::ClientAuth => {
+                    ||| key_usage.digital_signature
+                    ||| key_usage.key_agreement
+                }
+
+                Purpose::CodeSigning => key_usage.digital_signature,
+
+                // S/MIME keys can be used for signing or encryption
+                Purpose::EmailProtection => {
+                    ||| key_usage.digital_signature
+                    ||| key_usage.non_repudiation
+                    ||| key_usage.key_encipherment
+                    ||| key_usage.key_agreement
+                }
             }
         }
 }
 
-pub open spec fn extended_key_usage_valid(cert: &Certificate) -> bool {
+pub open spec fn extended_key_usage_valid(task: &Task, cert: &Certificate) -> bool {
     match &cert.ext_extended_key_usage {
         Some(key_usage) =>
-            exists |i: usize| 0 <= i < key_usage.usages.len() &&
-                match #[trigger] key_usage.usages[i as int] {
-                    ExtendedKeyUsageType::ServerAuth => true,
-                    ExtendedKeyUsageType::Any => true,
-                    _ => false,
-                },
+            exists |i: usize| 0 <= i < key_usage.usages.len() && {
+                ||| eku_permits_purpose(#[trigger] &key_usage.usages[i as int], &task.purpose)
+                ||| key_usage.usages[i as int] matches ExtendedKeyUsageType::Any
+            },
         None => true,
     }
 }
@@ -319,6 +350,15 @@ pub open spec fn check_ext_critical(cert: &Certificate) -> bool {
         ==> (&all_exts[i as int].critical matches Some(t) ==> *t)
 }
 
+/// Check the leaf against the reference identifier of the peer
+pub open spec fn match_peer_name(env: &Policy, cert: &Certificate, peer: &PeerName) -> bool {
+    match peer {
+        PeerName::Dns(domain) => match_san_domain(env, cert, &str_lower(domain)),
+        PeerName::Ip(addr) => match_ip_addr(cert, addr),
+        PeerName::Email(email) => match_email(cert, email),
+    }
+}
+
 pub open spec fn cert_verified_leaf(env: &Policy, task: &Task, cert: &Certificate, root: &Certificate) -> bool {
     &&& cert.version == 2
     &&& is_valid_pki(cert)
@@ -330,10 +370,9 @@ pub open spec fn cert_verified_leaf(env: &Policy, task: &Task, cert: &Certificat
 
     // Time comparison is inclusive
     // https://github.com/chromium/chromium/blob/0590dcf7b036e15c133de35213be8fe0986896aa/net/cert/internal/verify_certificate_chain.cc#L104
-    &&& cert.not_before <= task.now
-    &&& cert.not_after >= task.now
+    &&& check_validity(task, cert, cert, true)
 
-    &&& &task.hostname matches Some(domain) ==> match_san_domain(env, cert, &str_lower(domain))
+    &&& &task.peer matches Some(peer) ==> match_peer_name(env, cert, peer)
     &&& check_duplicate_extensions(cert)
 
     // Per x509-limbo::rfc5280::ee-critical-aia-invalid
@@ -349,9 +388,9 @@ pub open spec fn cert_verified_leaf(env: &Policy, task: &Task, cert: &Certificat
         ==> (&cert.ext_subject_alt_name matches Some(san) && (san.critical matches Some(c) && c))
 
     &&& not_in_crl(env, cert)
-    &&& strong_signature(&cert.sig_alg_inner.id)
-    &&& key_usage_valid(cert)
-    &&& extended_key_usage_valid(cert)
+    &&& strong_signature(&cert.sig_alg_inner)
+    &&& key_usage_valid(task, cert)
+    &&& extended_key_usage_valid(task, cert)
     &&& check_ext_critical(cert)
     &&& check_unhandled_extensions(cert)
 
@@ -497,7 +536,7 @@ pub open spec fn check_unhandled_extensions(cert: &Certificate) -> bool {
         }
 }
 
-pub open spec fn cert_verified_intermediate(env: &Policy, task: &Task, cert: &Certificate, depth: usize) -> bool {
+pub open spec fn cert_verified_intermediate(env: &Policy, task: &Task, cert: &Certificate, leaf: &Certificate, depth: usize) -> bool {
     &&& cert_verified_non_leaf(cert, depth)
 
     &&& &cert.ext_basic_constraints matches Some(bc)
@@ -505,47 +544,38 @@ pub open spec fn cert_verified_intermediate(env: &Policy, task: &Task, cert: &Ce
 
     // Time comparison is inclusive
     // https://github.com/chromium/chromium/blob/0590dcf7b036e15c133de35213be8fe0986896aa/net/cert/internal/verify_certificate_chain.cc#L104
-    &&& cert.not_before <= task.now
-    &&& cert.not_after >= task.now
+    &&& check_validity(task, cert, leaf, true)
 
     &&& &cert.ext_authority_info_access matches Some(aia)
         ==> (aia.critical matches Some(c) ==> !c)
 
     &&& not_in_crl(env, cert)
-    &&& strong_signature(&cert.sig_alg_inner.id)
-    &&& key_usage_valid(cert)
-    &&& extended_key_usage_valid(cert)
+    &&& strong_signature(&cert.sig_alg_inner)
+    &&& key_usage_valid(task, cert)
+    &&& extended_key_usage_valid(task, cert)
     &&& check_unhandled_extensions(cert)
 }
 
-/// NOTE: badSymantec in Hammurabi
-pub open spec fn is_bad_symantec_root(env: &Policy, root: &Certificate, interm: &Certificate) -> bool {
-    &&& exists |i: usize| 0 <= i < env.symantec_roots.len() && &root.fingerprint == &env.symantec_roots[i as int]
-    &&& forall |i: usize| 0 <= i < env.symantec_exceptions.len() ==> &interm.fingerprint != &env.symantec_exceptions[i as int]
-
-    // See IsUntrustedSymantecCert in Chromium
-    // NOTE: removed from Hammurabi, since this is only enabled if kLegacySymantecPKIEnforcement is false, which is not the case by default
-    // &&& {
-    //     ||| root.not_before < 1464739200 // June 2016
-    //     ||| root.not_before > 1512086400 // Dec 2017
-    // }
-}
+/// Constraints from the root program (see `TrustAnchor`)
+/// NOTE: generalizes fingerprintValid in Hammurabi
+pub open spec fn valid_trust_anchor(task: &Task, root: &Certificate, interm: &Certificate, leaf: &Certificate) -> bool {
+    &root.trust_anchor matches Some(anchor) ==> {
+        &&& anchor_permits_task(anchor, task, interm, leaf)
 
-/// NOTE: fingerprintValid in Hammurabi
-pub open spec fn valid_root_fingerprint(env: &Policy, task: &Task, cert: &Certificate) -> bool {
-    // &&& exists |i: usize| 0 <= i < env.trusted.len() && &cert.fingerprint == &env.trusted[i as int]
+        &&& &anchor.permitted_dns_names matches Some(names) ==>
+            (&task.peer matches Some(peer) ==> match peer {
+                PeerName::Dns(domain) => {
+                    let domain = str_lower(domain);
+                    exists |i: usize| #![auto] 0 <= i < names.len() && match_name(&names[i as int], &domain)
+                }
 
-    let is_india_fingerprint = exists |i: usize| 0 <= i < env.india_trusted.len() && &cert.fingerprint == &env.india_trusted[i as int];
-    let is_anssi_fingerprint = exists |i: usize| 0 <= i < env.anssi_trusted.len() && &cert.fingerprint == &env.anssi_trusted[i as int];
-
-    &task.hostname matches Some(domain) ==> {
-        let domain = str_lower(domain);
-        &&& is_india_fingerprint ==> exists |i: usize| #![auto] 0 <= i < env.india_domains.len() && match_name(&env.india_domains[i as int], &domain)
-        &&& is_anssi_fingerprint ==> exists |i: usize| #![auto] 0 <= i < env.anssi_domains.len() && match_name(&env.anssi_domains[i as int], &domain)
+                // Restricted roots are only trusted for the listed domains
+                _ => false,
+            })
     }
 }
 
-pub open spec fn cert_verified_root(env: &Policy, task: &Task, cert: &Certificate, interm: &Certificate, depth: usize) -> bool {
+pub open spec fn cert_verified_root(task: &Task, cert: &Certificate, interm: &Certificate, leaf: &Certificate, depth: usize) -> bool {
     // NOTE: many checks are not done for root in chrome
     // https://github.com/chromium/chromium/blob/0590dcf7b036e15c133de35213be8fe0986896aa/net/cert/internal/verify_certificate_chain.cc#L1271
 
@@ -564,8 +594,7 @@ pub open spec fn cert_verified_root(env: &Policy, task: &Task, cert: &Certificat
         &&& (aki.issuer matches None) == (aki.serial matches None)
     }
 
-    &&& valid_root_fingerprint(env, task, cert)
-    &&& !is_bad_symantec_root(env, cert, interm)
+    &&& valid_trust_anchor(task, cert, interm, leaf)
 }
 
 /// Check name constraints of each certificate against all certs down the path
@@ -586,9 +615,13 @@ pub open spec fn valid_chain(env: &Policy, chain: &Seq<ExecRef<Certificate>>, ta
         let root = &chain[chain.len() - 1];
 
         &&& cert_verified_leaf(env, task, leaf, root)
-        &&& forall |i: usize| 1 <= i < chain.len() - 1 ==> cert_verified_intermediate(&env, &task, #[trigger] &chain[i as int], (i - 1) as usize)
-        &&& cert_verified_root(env, task, root, &chain[chain.len() - 2], (chain.len() - 2) as usize)
+        &&& forall |i: usize| 1 <= i < chain.len() - 1 ==> cert_verified_intermediate(&env, &task, #[trigger] &chain[i as int], leaf, (i - 1) as usize)
+        &&& cert_verified_root(task, root, &chain[chain.len() - 2], leaf, (chain.len() - 2) as usize)
         &&& check_all_name_constraints(chain)
+        &&& chain_not_revoked(task, chain)
+
+        // Unknown OCSP statuses are soft failures
+        &&& chain_ocsp_ok(task, chain, true)
     }
 }
 
@@ -603,3 +636,95 @@ pub open spec fn likely_issued(issuer: &Certificate, subject: &Certificate) -> b
 } // mod internal
 
 } // verus!
+
+impl DiagnosePolicy for ChromePolicy {
+    fn diagnose_chain(
+        &self,
+        chain: &Vec<&ExecCertificate>,
+        task: &ExecTask,
+    ) -> Option<PolicyFailure> {
+        use internal::*;
+
+        if chain.len() < 2 {
+            return Some(PolicyFailure::chain(predicate!(exec_valid_chain)));
+        }
+
+        let leaf = chain[0];
+        let root = chain[chain.len() - 1];
+
+        if !exec_cert_verified_leaf(self, task, leaf, root) {
+            let predicate = first_failure(
+                &named_checks![
+                    exec_is_valid_pki(leaf),
+                    exec_check_validity(task, leaf, leaf, true),
+                    exec_match_peer_name => match &task.peer {
+                        Some(peer) => exec_match_peer_name(self, leaf, peer),
+                        None => true,
+                    },
+                    exec_leaf_duration_valid(leaf) if exec_is_known_root(self, root),
+                    exec_not_in_crl(self, leaf),
+                    exec_strong_signature(&leaf.sig_alg_inner),
+                    exec_key_usage_valid(task, leaf),
+                    exec_extended_key_usage_valid(task, leaf),
+                    exec_check_ext_critical(leaf),
+                    exec_check_unhandled_extensions(leaf),
+                ],
+                predicate!(exec_cert_verified_leaf),
+            );
+            return Some(PolicyFailure::at(predicate, 0));
+        }
+
+        for i in 1..chain.len() - 1 {
+            let cert = chain[i];
+
+            if !exec_cert_verified_intermediate(self, task, cert, leaf, i - 1) {
+                let predicate = first_failure(
+                    &named_checks![
+                        exec_cert_verified_non_leaf(cert, i - 1),
+                        exec_check_validity(task, cert, leaf, true),
+                        exec_not_in_crl(self, cert),
+                        exec_strong_signature(&cert.sig_alg_inner),
+                        exec_key_usage_valid(task, cert),
+                        exec_extended_key_usage_valid(task, cert),
+                        exec_check_unhandled_extensions(cert),
+                    ],
+                    predicate!(exec_cert_verified_intermediate),
+                );
+                return Some(PolicyFailure::at(predicate, i));
+            }
+        }
+
+        let depth = chain.len() - 2;
+
+        if !exec_cert_verified_root(task, root, chain[depth], leaf, depth) {
+            let predicate = first_failure(
+                &named_checks![
+                    exec_cert_verified_non_leaf(root, depth),
+                    exec_valid_trust_anchor(task, root, chain[depth], leaf),
+                ],
+                predicate!(exec_cert_verified_root),
+            );
+            return Some(PolicyFailure::at(predicate, chain.len() - 1));
+        }
+
+        if !exec_check_all_name_constraints(chain) {
+            return Some(PolicyFailure::chain(predicate!(
+                exec_check_all_name_constraints
+            )));
+        }
+
+        for i in 0..chain.len() - 1 {
+            if !exec_not_revoked(task, chain[i], chain[i + 1]) {
+                return Some(PolicyFailure::at(predicate!(exec_not_revoked), i));
+            }
+        }
+
+        for i in 0..chain.len() - 1 {
+            if !exec_ocsp_status_ok(task, chain[i], chain[i + 1], true) {
+                return Some(PolicyFailure::at(predicate!(exec_ocsp_status_ok), i));
+            }
+        }
+
+        None
+    }
+}
@@ -35,38 +35,79 @@ pub trait Policy: Send + Sync {
     /// and `chain.last()` is a trusted root certificate.
     fn valid_chain(&self, chain: &Vec<&ExecCertificate>, task: &ExecTask) -> (res: bool)
         ensures res.deep_view() == self.spec_valid_chain(chain.deep_view(), task.deep_view());
+
+    /// How the validator may look up candidate issuers
+    /// among the root certificates (see [`IssuerLookup`])
+    fn issuer_lookup(&self) -> (res: IssuerLookup)
+        ensures
+            !(res is Linear) ==>
+                forall |issuer: Certificate, subject: Certificate|
+                    #[trigger] self.spec_likely_issued(issuer, subject) ==>
+                    may_issue(&issuer, &subject, res is NameAndKeyId);
+}
+
+/// Over-approximations of `likely_issued` guaranteed by a policy,
+/// so that only roots with a matching subject name (and SKID)
+/// need to be checked as issuers, instead of every root
+#[derive(Debug, Clone, Copy)]
+pub enum IssuerLookup {
+    /// No guarantee, every root is checked
+    Linear,
+
+    /// `likely_issued` implies [`may_issue`] (without key identifiers)
+    Name,
+
+    /// `likely_issued` implies [`may_issue`] (with key identifiers)
+    NameAndKeyId,
 }
 
 rspec! {
 
 /// Corresponds to `AttributeTypeAndValue` in X.509
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub struct Attribute {
     pub oid: SpecString,
     pub value: SpecString,
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub struct DistinguishedName(pub Seq<Seq<Attribute>>);
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub enum GeneralName {
     DNSName(SpecString),
     DirectoryName(DistinguishedName),
     IPAddr(Seq<u8>),
+    RFC822Name(SpecString),
     OtherName,
     Unsupported,
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub enum SubjectKey {
     RSA {
         mod_length: usize,
+        /// Public exponent in upper-case hex (same encoding as the serial)
+        exponent: SpecString,
     },
     DSA {
         p_len: usize,
         q_len: usize,
         g_len: usize,
     },
+    EC {
+        /// OID of the named curve
+        curve: SpecString,
+        /// Length of the encoded EC point in bytes
+        /// (e.g. 65 for an uncompressed P-256 point)
+        point_len: usize,
+    },
+    Ed25519,
+    Ed448,
     Other,
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub struct AuthorityKeyIdentifier {
     pub critical: Option<bool>,
     pub key_id: Option<SpecString>,
@@ -74,11 +115,13 @@ pub struct AuthorityKeyIdentifier {
     pub serial: Option<SpecString>,
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub struct SubjectKeyIdentifier {
     pub critical: Option<bool>,
     pub key_id: SpecString,
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub enum ExtendedKeyUsageType {
     ServerAuth,
     ClientAuth,
@@ -90,17 +133,20 @@ pub enum ExtendedKeyUsageType {
     Other(SpecString),
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub struct ExtendedKeyUsage {
     pub critical: Option<bool>,
     pub usages: Seq<ExtendedKeyUsageType>,
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub struct BasicConstraints {
     pub critical: Option<bool>,
     pub is_ca: bool,
     pub path_len: Option<i64>,
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub struct KeyUsage {
     pub critical: Option<bool>,
     pub digital_signature: bool,
@@ -114,37 +160,66 @@ pub struct KeyUsage {
     pub decipher_only: bool,
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub struct SubjectAltName {
     pub critical: Option<bool>,
     pub names: Seq<GeneralName>,
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub struct NameConstraints {
     pub critical: Option<bool>,
     pub permitted: Seq<GeneralName>,
     pub excluded: Seq<GeneralName>,
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub struct CertificatePolicies {
     pub critical: Option<bool>,
     pub policies: Seq<SpecString>,
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub struct AuthorityInfoAccess {
     pub critical: Option<bool>,
-    // Other info is not encoded
+
+    /// URIs of the caIssuers access descriptions, where
+    /// certificates issued to the issuer can be fetched
+    pub ca_issuers: Seq<SpecString>,
+
+    /// URIs of the OCSP responders for this certificate
+    pub ocsp: Seq<SpecString>,
+}
+
+/// Parameters of RSASSA-PSS (RFC 4055), with defaults filled in
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
+pub struct PSSParams {
+    /// OID of the message digest
+    pub hash: SpecString,
+
+    /// OID of the hash function used in MGF1
+    /// (None if the mask generation function is not MGF1)
+    pub mgf1_hash: Option<SpecString>,
+
+    pub salt_len: i64,
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub struct SignatureAlgorithm {
     pub id: SpecString,
     pub bytes: SpecString,
+
+    /// Only present for RSASSA-PSS (with the defaults if the parameters are absent)
+    pub pss: Option<PSSParams>,
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub struct Extension {
     pub oid: SpecString,
     pub critical: Option<bool>,
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub struct Certificate {
     pub fingerprint: SpecString,
     pub version: u32,
@@ -173,25 +248,327 @@ pub struct Certificate {
 
     // All extensions without parameters
     pub all_exts: Option<Seq<Extension>>,
+
+    /// Constraints from the root program if this is a trusted root
+    /// (always None for certificates parsed from the chain)
+    pub trust_anchor: Option<TrustAnchor>,
 }
 
 #[derive(Copy, Clone)]
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub enum Purpose {
     ServerAuth,
+    ClientAuth,
+    CodeSigning,
+    EmailProtection,
+}
+
+/// Reference identifier of the peer (RFC 6125)
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
+pub enum PeerName {
+    Dns(SpecString),
+
+    /// 4 (IPv4) or 16 (IPv6) octets in network byte order
+    Ip(Seq<u8>),
+
+    /// RFC 822 mailbox, e.g. for `Purpose::EmailProtection`
+    Email(SpecString),
+}
+
+/// A revoked certificate, identified by the fingerprint
+/// of its issuer and its serial number (same format as
+/// `Certificate::fingerprint` and `Certificate::serial`)
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
+pub struct RevokedSerial {
+    pub issuer_fingerprint: SpecString,
+    pub serial: SpecString,
+}
+
+#[derive(Copy, Clone)]
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
+pub enum OCSPStatus {
+    Good,
+    Revoked,
+    Unknown,
 }
 
+/// A single response in a stapled OCSP response (RFC 6960),
+/// identified in the same way as `RevokedSerial`
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
+pub struct OCSPResult {
+    pub issuer_fingerprint: SpecString,
+    pub serial: SpecString,
+    pub status: OCSPStatus,
+
+    /// thisUpdate and nextUpdate as UNIX timestamps
+    pub this_update: u64,
+    pub next_update: Option<u64>,
+}
+
+/// Constraints imposed by a root program on a trusted root,
+/// in addition to those in the root certificate itself
+/// (e.g. NSS trust bits, or domain restrictions on some government CAs)
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
+pub struct TrustAnchor {
+    /// Purposes that the root is trusted for
+    pub server_auth: bool,
+    pub client_auth: bool,
+    pub code_signing: bool,
+    pub email_protection: bool,
+
+    /// If present, the root is only trusted for DNS names
+    /// matching one of these patterns (e.g. "*.fr", see `match_name`)
+    pub permitted_dns_names: Option<Seq<SpecString>>,
+
+    /// Leaf certificates with a later notBefore (as UNIX timestamps)
+    /// are not trusted for TLS or email protection, respectively
+    /// (CKA_NSS_SERVER_DISTRUST_AFTER and CKA_NSS_EMAIL_DISTRUST_AFTER in NSS)
+    pub server_distrust_after: Option<u64>,
+    pub email_distrust_after: Option<u64>,
+
+    /// Fingerprints of CAs issued directly by the root that are
+    /// exempt from `server_distrust_after` (e.g. the subordinate CAs
+    /// of Apple and Google under the distrusted Symantec roots)
+    pub server_distrust_exceptions: Seq<SpecString>,
+}
+
+/// How the validity period of each certificate is checked
+#[derive(Copy, Clone)]
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
+pub enum TimeCheck {
+    /// Valid at `Task::now`
+    Strict,
+
+    /// Valid at `Task::now`, tolerating a clock skew of
+    /// the given number of seconds at both ends of the period,
+    /// i.e., `now` is in `[not_before - skew, not_after + skew]`
+    Skew(u64),
+
+    /// Valid at the `not_before` of the leaf, ignoring `Task::now`
+    /// (e.g., when verifying signatures made in the past)
+    AsOfIssuance,
+
+    /// The validity period is not checked
+    Skip,
+}
+
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub struct Task {
-    pub hostname: Option<SpecString>,
+    pub peer: Option<PeerName>,
     pub purpose: Purpose,
     pub now: u64,
+    pub time_check: TimeCheck,
+
+    /// If set, reject chains containing any certificate in `revoked`
+    pub check_revocation: bool,
+
+    /// Revoked certificates collected from CRLs signed by
+    /// the issuers in the candidate chain (filled by the validator)
+    pub revoked: Seq<RevokedSerial>,
+
+    /// Statuses from stapled OCSP responses signed by (or on behalf of)
+    /// the issuers in the candidate chain (filled by the validator)
+    pub ocsp: Seq<OCSPResult>,
 }
 
+#[exec_attr(cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize)))]
 pub enum PolicyError {
     UnsupportedTask,
 }
 
 use exec_starts_with as starts_with;
 use exec_ends_with as ends_with;
+use exec_str_lower as str_lower;
+use exec_email_lower as email_lower;
+
+/// `time` is in `[cert.not_before - skew, cert.not_after + skew]`,
+/// or `[cert.not_before - skew, cert.not_after + skew)` if not `inclusive`
+pub open spec fn valid_at(cert: &Certificate, time: u64, skew: u64, inclusive: bool) -> bool {
+    &&& cert.not_before <= time || cert.not_before - time <= skew
+    &&& if inclusive {
+        time <= cert.not_after || time - cert.not_after <= skew
+    } else {
+        time < cert.not_after || time - cert.not_after < skew
+    }
+}
+
+/// Check the validity period of `cert` according to `task.time_check`,
+/// where `leaf` is the leaf of the chain (for `TimeCheck::AsOfIssuance`)
+pub open spec fn check_validity(task: &Task, cert: &Certificate, leaf: &Certificate, inclusive: bool) -> bool {
+    match &task.time_check {
+        TimeCheck::Strict => valid_at(cert, task.now, 0, inclusive),
+        TimeCheck::Skew(skew) => valid_at(cert, task.now, *skew, inclusive),
+        TimeCheck::AsOfIssuance => valid_at(cert, leaf.not_before, 0, inclusive),
+        TimeCheck::Skip => true,
+    }
+}
+
+/// `leaf` is issued after the server distrust-after date of `anchor`,
+/// and `interm` (the certificate issued by the root) is not exempted
+pub open spec fn server_distrusted(anchor: &TrustAnchor, interm: &Certificate, leaf: &Certificate) -> bool {
+    &&& (anchor.server_distrust_after matches Some(t) && leaf.not_before > t)
+    &&& forall |i: usize| 0 <= i < anchor.server_distrust_exceptions.len() ==>
+            &interm.fingerprint != &anchor.server_distrust_exceptions[i as int]
+}
+
+/// The root program trusts `anchor` for the purpose of `task`, and
+/// `leaf` is not issued after the distrust-after date for that purpose,
+/// where `interm` is the certificate issued by the root
+pub open spec fn anchor_permits_task(anchor: &TrustAnchor, task: &Task, interm: &Certificate, leaf: &Certificate) -> bool {
+    match task.purpose {
+        Purpose::ServerAuth => {
+            &&& anchor.server_auth
+            &&& !server_distrusted(anchor, interm, leaf)
+        }
+
+        Purpose::ClientAuth => {
+            &&& anchor.client_auth
+            &&& !server_distrusted(anchor, interm, leaf)
+        }
+
+        Purpose::CodeSigning => anchor.code_signing,
+
+        Purpose::EmailProtection => {
+            &&& anchor.email_protection
+            &&& anchor.email_distrust_after matches Some(t) ==> leaf.not_before <= t
+        }
+    }
+}
+
+/// Check if an EKU entry (other than anyExtendedKeyUsage)
+/// explicitly permits the given purpose
+pub open spec fn eku_permits_purpose(usage: &ExtendedKeyUsageType, purpose: &Purpose) -> bool {
+    match purpose {
+        Purpose::ServerAuth => usage matches ExtendedKeyUsageType::ServerAuth,
+        Purpose::ClientAuth => usage matches ExtendedKeyUsageType::ClientAuth,
+        Purpose::CodeSigning => usage matches ExtendedKeyUsageType::CodeSigning,
+        Purpose::EmailProtection => usage matches ExtendedKeyUsageType::EmailProtection,
+    }
+}
+
+/// Check if `cert` is issued for the RFC 822 address `email`,
+/// using the rfc822Name entries in SAN if present,
+/// or the emailAddress attributes in the subject otherwise.
+/// NOTE: only the domain part is case-insensitive (see `email_lower`)
+pub open spec fn match_email(cert: &Certificate, email: &SpecString) -> bool {
+    match &cert.ext_subject_alt_name {
+        Some(san) =>
+            exists |i: usize| 0 <= i < san.names.len() && {
+                &&& #[trigger] &san.names[i as int] matches GeneralName::RFC822Name(name)
+                &&& &email_lower(name) == &email_lower(email)
+            },
+
+        None =>
+            exists |i: usize| #![trigger cert.subject.0[i as int]]
+                0 <= i < cert.subject.0.len() &&
+            exists |j: usize| #![trigger cert.subject.0[i as int][j as int]]
+                0 <= j < cert.subject.0[i as int].len() && {
+                    let attr = &cert.subject.0[i as int][j as int];
+                    &&& &attr.oid == "1.2.840.113549.1.9.1"@ // emailAddress
+                    &&& &email_lower(&attr.value) == &email_lower(email)
+                },
+    }
+}
+
+/// Check if `cert` is issued for the IP address `addr`.
+/// Only iPAddress entries in SAN are considered (RFC 6125, Section 6.2.1),
+/// and addresses of different lengths never match.
+pub open spec fn match_ip_addr(cert: &Certificate, addr: &Seq<u8>) -> bool {
+    &cert.ext_subject_alt_name matches Some(san) &&
+    exists |i: usize| 0 <= i < san.names.len() && {
+        &&& #[trigger] &san.names[i as int] matches GeneralName::IPAddr(ip)
+        &&& ip.len() == addr.len()
+        &&& forall |j: usize| 0 <= j < addr.len() ==> ip[j as int] == addr[j as int]
+    }
+}
+
+/// Check if any dNSName entry in `san` matches `name` (case-insensitive),
+/// allowing wildcards in `san` if `wildcard` is set
+pub open spec fn match_san_dns_name(san: &SubjectAltName, name: &SpecString, wildcard: bool) -> bool {
+    exists |i: usize| 0 <= i < san.names.len() && {
+        &&& #[trigger] &san.names[i as int] matches GeneralName::DNSName(dns_name)
+        &&& if wildcard {
+            match_name(&str_lower(dns_name), &str_lower(name))
+        } else {
+            &str_lower(dns_name) == &str_lower(name)
+        }
+    }
+}
+
+/// Check if `leaf` is issued for the peer in `task` (if any)
+pub open spec fn match_task_peer(task: &Task, leaf: &Certificate, wildcard: bool) -> bool {
+    match &task.peer {
+        Some(PeerName::Dns(name)) =>
+            &leaf.ext_subject_alt_name matches Some(san) && match_san_dns_name(san, name, wildcard),
+        Some(PeerName::Ip(addr)) => match_ip_addr(leaf, addr),
+        Some(PeerName::Email(email)) => match_email(leaf, email),
+        None => true,
+    }
+}
+
+/// A certificate in an unordered bundle that may be the leaf:
+/// a non-CA certificate issued for the peer in `task`
+pub open spec fn is_leaf_candidate(task: &Task, cert: &Certificate) -> bool {
+    &&& &cert.ext_basic_constraints matches Some(bc) ==> !bc.is_ca
+    &&& match_task_peer(task, cert, true)
+}
+
+/// Check that `cert` (issued by `issuer`) is not revoked
+pub open spec fn not_revoked(task: &Task, cert: &Certificate, issuer: &Certificate) -> bool {
+    task.check_revocation ==>
+    forall |i: usize| #![trigger task.revoked[i as int]] 0 <= i < task.revoked.len() ==> {
+        let entry = &task.revoked[i as int];
+        !(&entry.issuer_fingerprint == &issuer.fingerprint && &entry.serial == &cert.serial)
+    }
+}
+
+/// Check that no non-root certificate in the chain is revoked,
+/// where chain[i] is issued by chain[i + 1]
+pub open spec fn chain_not_revoked(task: &Task, chain: &Seq<ExecRef<Certificate>>) -> bool {
+    &&& chain.len() > 0
+    &&& forall |i: usize| #![trigger chain[i as int]] 0 <= i < chain.len() - 1 ==>
+        not_revoked(task, &chain[i as int], &chain[(i + 1) as int])
+}
+
+/// Check that every stapled OCSP response about `cert` from `issuer`
+/// is fresh at `task.now` and does not report `cert` as revoked
+/// (or as unknown, unless `allow_unknown` is set)
+pub open spec fn ocsp_status_ok(task: &Task, cert: &Certificate, issuer: &Certificate, allow_unknown: bool) -> bool {
+    forall |i: usize| #![trigger task.ocsp[i as int]] 0 <= i < task.ocsp.len() ==> {
+        let entry = &task.ocsp[i as int];
+
+        (&entry.issuer_fingerprint == &issuer.fingerprint && &entry.serial == &cert.serial) ==> {
+            &&& entry.this_update <= task.now
+            &&& entry.next_update matches Some(next_update) ==> task.now <= next_update
+            &&& !(&entry.status matches OCSPStatus::Revoked)
+            &&& allow_unknown || !(&entry.status matches OCSPStatus::Unknown)
+        }
+    }
+}
+
+/// Check the OCSP statuses of all non-root certificates in the chain,
+/// where chain[i] is issued by chain[i + 1]
+pub open spec fn chain_ocsp_ok(task: &Task, chain: &Seq<ExecRef<Certificate>>, allow_unknown: bool) -> bool {
+    &&& chain.len() > 0
+    &&& forall |i: usize| #![trigger chain[i as int]] 0 <= i < chain.len() - 1 ==>
+        ocsp_status_ok(task, &chain[i as int], &chain[(i + 1) as int], allow_unknown)
+}
+
+/// RSASSA-PSS parameters accepted by both Chrome and Firefox:
+/// SHA-256, SHA-384 or SHA-512, MGF1 with the same hash function,
+/// and the salt length equal to the digest length
+pub open spec fn common_pss_params(params: &PSSParams) -> bool {
+    &&& &params.mgf1_hash matches Some(mgf1_hash)
+    &&& mgf1_hash == &params.hash
+    &&& {
+        // SHA-256
+        ||| &params.hash == "2.16.840.1.101.3.4.2.1"@ && params.salt_len == 32
+        // SHA-384
+        ||| &params.hash == "2.16.840.1.101.3.4.2.2"@ && params.salt_len == 48
+        // SHA-512
+        ||| &params.hash == "2.16.840.1.101.3.4.2.3"@ && params.salt_len == 64
+    }
+}
 
 /// Match a pattern with wildcard (e.g. "*.example.com") against a string
 pub open spec fn match_name(pattern: &SpecString, name: &SpecString) -> bool {
@@ -377,6 +754,102 @@ pub fn exec_str_lower(s: &String) -> (res: String)
     s.to_lowercase()
 }
 
+/// Lowercases the domain part (after the last '@') of an RFC 822
+/// address, since the local part is case-sensitive (RFC 5280, 7.5)
+/// NOTE: unspecified
+pub uninterp spec fn email_lower(s: &SpecString) -> SpecString;
+
+#[verifier::external_body]
+pub fn exec_email_lower(s: &String) -> (res: String)
+    ensures res.deep_view() == email_lower(&s.deep_view())
+{
+    match s.rsplit_once('@') {
+        Some((local, domain)) => format!("{}@{}", local, domain.to_lowercase()),
+        None => s.clone(),
+    }
+}
+
+/// A policy-independent condition for `issuer` to issue `subject`:
+/// the names match after normalization, and if `by_key_id` is set,
+/// the AKID of `subject` (if any) matches `issuer`
+pub open spec fn may_issue(issuer: &Certificate, subject: &Certificate, by_key_id: bool) -> bool {
+    &&& same_dn(&issuer.subject, &subject.issuer, true)
+    &&& by_key_id ==> check_auth_key_id(issuer, subject)
+}
+
+/// `name` with all attribute values normalized
+pub open spec fn normalize_dn(name: &DistinguishedName) -> DistinguishedName {
+    DistinguishedName(name.0.map_values(|rdn: Seq<Attribute>|
+        rdn.map_values(|attr: Attribute| Attribute {
+            oid: attr.oid,
+            value: normalize_string(&attr.value),
+        })))
+}
+
+/// NOTE: unspecified
+pub uninterp spec fn hash_dn(name: DistinguishedName) -> u64;
+
+/// Key of a name for indexing, which only depends
+/// on the name after normalization
+pub open spec fn name_key(name: &DistinguishedName) -> u64 {
+    hash_dn(normalize_dn(name))
+}
+
+#[verifier::external_body]
+pub fn exec_name_key(name: &ExecDistinguishedName) -> (res: u64)
+    ensures res == name_key(&name.deep_view())
+{
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+
+    name.0.len().hash(&mut hasher);
+    for rdn in &name.0 {
+        rdn.len().hash(&mut hasher);
+        for attr in rdn {
+            attr.oid.hash(&mut hasher);
+            exec_normalize_string(&attr.value).hash(&mut hasher);
+        }
+    }
+
+    hasher.finish()
+}
+
+/// Names that are the same after normalization have the same key
+pub proof fn lemma_same_dn_name_key(name1: &DistinguishedName, name2: &DistinguishedName)
+    requires same_dn(name1, name2, true)
+    ensures name_key(name1) == name_key(name2)
+{
+    let norm1 = normalize_dn(name1);
+    let norm2 = normalize_dn(name2);
+
+    assert forall |i: usize| 0 <= i < name1.0.len() implies
+        #[trigger] norm1.0[i as int] =~= norm2.0[i as int]
+    by {
+        assert(same_rdn(&name1.0[i as int], &name2.0[i as int], true));
+
+        assert forall |j: usize| 0 <= j < name1.0[i as int].len() implies
+            #[trigger] norm1.0[i as int][j as int] == norm2.0[i as int][j as int]
+        by {
+            assert(same_attr(&name1.0[i as int][j as int], &name2.0[i as int][j as int], true));
+        }
+    }
+
+    assert(norm1.0 =~= norm2.0);
+}
+
+/// Comparing names with normalization is weaker than without
+pub proof fn lemma_same_dn_normalize(name1: &DistinguishedName, name2: &DistinguishedName)
+    requires same_dn(name1, name2, false)
+    ensures same_dn(name1, name2, true)
+{
+    assert forall |i: usize| 0 <= i < name1.0.len() implies
+        same_rdn(#[trigger] &name1.0[i as int], &name2.0[i as int], true)
+    by {
+        assert(same_rdn(&name1.0[i as int], &name2.0[i as int], false));
+    }
+}
+
 impl Clone for ExecAttribute {
     fn clone(&self) -> (res: Self)
         ensures res.deep_view() == self.deep_view()
@@ -432,14 +905,212 @@ impl Clone for ExecTask {
         ensures res.deep_view() == self.deep_view()
     {
         ExecTask {
-            hostname: match &self.hostname {
-                Some(hostname) => Some(hostname.clone()),
+            peer: match &self.peer {
+                Some(peer) => Some(peer.clone()),
                 None => None,
             },
             purpose: self.purpose,
             now: self.now,
+            time_check: self.time_check,
+            check_revocation: self.check_revocation,
+            revoked: clone_revoked(&self.revoked),
+            ocsp: clone_ocsp(&self.ocsp),
+        }
+    }
+}
+
+impl Clone for ExecRevokedSerial {
+    fn clone(&self) -> (res: Self)
+        ensures res.deep_view() == self.deep_view()
+    {
+        ExecRevokedSerial {
+            issuer_fingerprint: self.issuer_fingerprint.clone(),
+            serial: self.serial.clone(),
+        }
+    }
+}
+
+fn clone_revoked(revoked: &Vec<ExecRevokedSerial>) -> (res: Vec<ExecRevokedSerial>)
+    ensures res.deep_view() =~= revoked.deep_view()
+{
+    let mut res = Vec::with_capacity(revoked.len());
+
+    for i in 0..revoked.len()
+        invariant
+            res.len() == i,
+            forall |j| 0 <= j < i ==> res.deep_view()[j] == revoked.deep_view()[j],
+    {
+        res.push(revoked[i].clone());
+    }
+
+    res
+}
+
+impl Clone for ExecOCSPResult {
+    fn clone(&self) -> (res: Self)
+        ensures res.deep_view() == self.deep_view()
+    {
+        ExecOCSPResult {
+            issuer_fingerprint: self.issuer_fingerprint.clone(),
+            serial: self.serial.clone(),
+            status: self.status,
+            this_update: self.this_update,
+            next_update: self.next_update,
+        }
+    }
+}
+
+fn clone_ocsp(ocsp: &Vec<ExecOCSPResult>) -> (res: Vec<ExecOCSPResult>)
+    ensures res.deep_view() =~= ocsp.deep_view()
+{
+    let mut res = Vec::with_capacity(ocsp.len());
+
+    for i in 0..ocsp.len()
+        invariant
+            res.len() == i,
+            forall |j| 0 <= j < i ==> res.deep_view()[j] == ocsp.deep_view()[j],
+    {
+        res.push(ocsp[i].clone());
+    }
+
+    res
+}
+
+impl Clone for ExecTrustAnchor {
+    fn clone(&self) -> (res: Self)
+        ensures res.deep_view() == self.deep_view()
+    {
+        ExecTrustAnchor {
+            server_auth: self.server_auth,
+            client_auth: self.client_auth,
+            code_signing: self.code_signing,
+            email_protection: self.email_protection,
+            permitted_dns_names: match &self.permitted_dns_names {
+                Some(names) => Some(clone_strings(names)),
+                None => None,
+            },
+            server_distrust_after: self.server_distrust_after,
+            email_distrust_after: self.email_distrust_after,
+            server_distrust_exceptions: clone_strings(&self.server_distrust_exceptions),
+        }
+    }
+}
+
+fn clone_strings(strs: &Vec<String>) -> (res: Vec<String>)
+    ensures res.deep_view() =~= strs.deep_view()
+{
+    let mut res = Vec::with_capacity(strs.len());
+
+    for i in 0..strs.len()
+        invariant
+            res.len() == i,
+            forall |j| 0 <= j < i ==> res.deep_view()[j] == strs.deep_view()[j],
+    {
+        res.push(strs[i].clone());
+    }
+
+    res
+}
+
+impl Clone for ExecPeerName {
+    fn clone(&self) -> (res: Self)
+        ensures res.deep_view() == self.deep_view()
+    {
+        match self {
+            ExecPeerName::Dns(name) => ExecPeerName::Dns(name.clone()),
+            ExecPeerName::Ip(addr) => {
+                let copied = addr.clone();
+                assert(copied.deep_view() =~= addr.deep_view());
+                ExecPeerName::Ip(copied)
+            }
+            ExecPeerName::Email(email) => ExecPeerName::Email(email.clone()),
         }
     }
 }
 
 } // verus!
+
+/// The first policy check that rejects a candidate chain.
+/// NOTE: this is only used for diagnostics and is not verified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PolicyFailure {
+    /// Name of the failing predicate (e.g., `cert_verified_leaf`)
+    pub predicate: &'static str,
+
+    /// Index of the certificate in the candidate chain (`0` for the leaf)
+    /// that the predicate failed on, or `None` if the predicate
+    /// is about the entire chain (e.g., name constraints)
+    pub depth: Option<usize>,
+}
+
+impl PolicyFailure {
+    /// A failure on the certificate at `depth` of the chain
+    pub fn at(predicate: &'static str, depth: usize) -> Self {
+        PolicyFailure {
+            predicate,
+            depth: Some(depth),
+        }
+    }
+
+    /// A failure not specific to any certificate
+    pub fn chain(predicate: &'static str) -> Self {
+        PolicyFailure {
+            predicate,
+            depth: None,
+        }
+    }
+}
+
+/// Unverified diagnostics explaining why a policy rejects a chain.
+/// Implementations should mirror the structure of `valid_chain`
+/// so that `diagnose_chain` returns `None` iff `valid_chain` holds.
+pub trait DiagnosePolicy {
+    fn diagnose_chain(
+        &self,
+        chain: &Vec<&ExecCertificate>,
+        task: &ExecTask,
+    ) -> Option<PolicyFailure>;
+}
+
+/// Returns the name of the first failing check in `checks`, or `default`
+/// if all of them pass (in which case some other unnamed conjunct failed).
+pub fn first_failure(checks: &[(&'static str, bool)], default: &'static str) -> &'static str {
+    checks
+        .iter()
+        .find(|(_, ok)| !ok)
+        .map(|(name, _)| *name)
+        .unwrap_or(default)
+}
+
+/// Strips the `exec_` prefix from the name of an exec predicate
+pub fn predicate_name(exec_name: &'static str) -> &'static str {
+    exec_name.strip_prefix("exec_").unwrap_or(exec_name)
+}
+
+/// Name of a predicate, given its exec version in scope (e.g.,
+/// `predicate!(exec_cert_verified_leaf)` is `"cert_verified_leaf"`),
+/// so that renaming the predicate breaks the build instead of the name
+macro_rules! predicate {
+    ($f:ident) => {{
+        let _ = $f;
+        $crate::policy::predicate_name(stringify!($f))
+    }};
+}
+
+/// A list of checks named by [`predicate!`] for [`first_failure`], each
+/// either a call `exec_f(args)`, a call only checked under some condition
+/// `exec_f(args) if <cond>`, or a named result `exec_f => <result>`
+macro_rules! named_checks {
+    (@acc [$($out:tt)*]) => { [$($out)*] };
+    (@acc [$($out:tt)*] $f:ident => $ok:expr $(, $($rest:tt)*)?) => {
+        named_checks!(@acc [$($out)* (predicate!($f), $ok),] $($($rest)*)?)
+    };
+    (@acc [$($out:tt)*] $f:ident($($arg:expr),* $(,)?) if $cond:expr $(, $($rest:tt)*)?) => {
+        named_checks!(@acc [$($out)* (predicate!($f), !$cond || $f($($arg),*)),] $($($rest)*)?)
+    };
+    (@acc [$($out:tt)*] $f:ident($($arg:expr),* $(,)?) $(, $($rest:tt)*)?) => {
+        named_checks!(@acc [$($out)* (predicate!($f), $f($($arg),*)),] $($($rest)*)?)
+    };
+    ($($checks:tt)*) => { named_checks!(@acc [] $($checks)*) };
+}
@@ -0,0 +1,454 @@
+//! Policies composed of one of the bundled models (or an empty base)
+//! and a selection of the rules in [`super::standard`]
+
+#![allow(unused_parens)]
+#![allow(unexpected_cfgs)]
+
+#[cfg(not(feature = "trace"))]
+use verdict_rspec::rspec;
+#[cfg(feature = "trace")]
+use verdict_rspec::rspec_trace as rspec;
+use verdict_rspec_lib::*;
+use vstd::prelude::*;
+
+use super::common::*;
+use super::standard::*;
+use super::{ChromePolicy, FirefoxPolicy, OpenSSLPolicy};
+
+verus! {
+
+pub use internal::ExecRules as Rules;
+pub use internal::ExecNameMatching as NameMatching;
+
+/// The model that a [`CustomPolicy`] starts from
+#[derive(Debug)]
+pub enum BasePolicy {
+    /// Only checks the issuing relation (same as OpenSSL)
+    /// and accepts any chain built by the validator, unless the
+    /// [`Rules`] say otherwise.
+    ///
+    /// WARNING: without [`Rules::minimal`] (or stronger rules), this
+    /// accepts expired, misnamed and revoked chains, and chains where
+    /// an end-entity certificate issues another certificate
+    Empty,
+    Chrome(ChromePolicy),
+    Firefox(FirefoxPolicy),
+    OpenSSL(OpenSSLPolicy),
+}
+
+impl Policy for BasePolicy {
+    closed spec fn spec_likely_issued(&self, issuer: Certificate, subject: Certificate) -> bool {
+        match self {
+            BasePolicy::Empty => internal::likely_issued(&issuer, &subject),
+            BasePolicy::Chrome(policy) => policy.spec_likely_issued(issuer, subject),
+            BasePolicy::Firefox(policy) => policy.spec_likely_issued(issuer, subject),
+            BasePolicy::OpenSSL(policy) => policy.spec_likely_issued(issuer, subject),
+        }
+    }
+
+    fn likely_issued(&self, issuer: &ExecCertificate, subject: &ExecCertificate) -> (res: bool) {
+        match self {
+            BasePolicy::Empty => internal::exec_likely_issued(issuer, subject),
+            BasePolicy::Chrome(policy) => policy.likely_issued(issuer, subject),
+            BasePolicy::Firefox(policy) => policy.likely_issued(issuer, subject),
+            BasePolicy::OpenSSL(policy) => policy.likely_issued(issuer, subject),
+        }
+    }
+
+    closed spec fn spec_valid_chain(&self, chain: Seq<Certificate>, task: Task) -> bool {
+        match self {
+            BasePolicy::Empty => true,
+            BasePolicy::Chrome(policy) => policy.spec_valid_chain(chain, task),
+            BasePolicy::Firefox(policy) => policy.spec_valid_chain(chain, task),
+            BasePolicy::OpenSSL(policy) => policy.spec_valid_chain(chain, task),
+        }
+    }
+
+    fn valid_chain(&self, chain: &Vec<&ExecCertificate>, task: &ExecTask) -> bool {
+        match self {
+            BasePolicy::Empty => true,
+            BasePolicy::Chrome(policy) => policy.valid_chain(chain, task),
+            BasePolicy::Firefox(policy) => policy.valid_chain(chain, task),
+            BasePolicy::OpenSSL(policy) => policy.valid_chain(chain, task),
+        }
+    }
+
+    fn issuer_lookup(&self) -> (res: IssuerLookup) {
+        match self {
+            BasePolicy::Empty => IssuerLookup::NameAndKeyId,
+            BasePolicy::Chrome(policy) => policy.issuer_lookup(),
+            BasePolicy::Firefox(policy) => policy.issuer_lookup(),
+            BasePolicy::OpenSSL(policy) => policy.issuer_lookup(),
+        }
+    }
+}
+
+/// A base policy extended with the enabled [`Rules`].
+/// Disabling a rule only removes the additional check;
+/// it does not relax the base policy.
+#[derive(Debug)]
+pub struct CustomPolicy {
+    pub base: BasePolicy,
+    pub rules: Rules,
+}
+
+impl Policy for CustomPolicy {
+    closed spec fn spec_likely_issued(&self, issuer: Certificate, subject: Certificate) -> bool {
+        self.base.spec_likely_issued(issuer, subject)
+    }
+
+    fn likely_issued(&self, issuer: &ExecCertificate, subject: &ExecCertificate) -> (res: bool) {
+        self.base.likely_issued(issuer, subject)
+    }
+
+    closed spec fn spec_valid_chain(&self, chain: Seq<Certificate>, task: Task) -> bool {
+        &&& self.base.spec_valid_chain(chain, task)
+        &&& internal::valid_chain(&self.rules.deep_view(), &chain, &task)
+    }
+
+    fn valid_chain(&self, chain: &Vec<&ExecCertificate>, task: &ExecTask) -> bool {
+        self.base.valid_chain(chain, task) && internal::exec_valid_chain(&self.rules, chain, task)
+    }
+
+    fn issuer_lookup(&self) -> (res: IssuerLookup) {
+        self.base.issuer_lookup()
+    }
+}
+
+impl Rules {
+    /// No additional rules
+    pub fn none() -> Self {
+        Rules {
+            no_expiration: false,
+            outer_inner_sig_match: false,
+            key_usage_non_empty: false,
+            issuer_subject_uid_version: false,
+            path_len_non_negative: false,
+            path_len_constraint: false,
+            non_leaf_must_be_ca: false,
+            non_leaf_has_key_cert_sign: false,
+            non_empty_san: false,
+            aki_non_critical: false,
+            non_root_has_aki: false,
+            non_leaf_has_ski: false,
+            empty_subject_implies_critical_san: false,
+            non_critical_root_ski: false,
+            root_ca_has_aki: false,
+            root_ca_aki_no_issuer_or_serial: false,
+            leaf_has_eku: false,
+            root_has_no_eku: false,
+            no_dsa: false,
+            rsa_2048: false,
+            allowed_curves: false,
+            allowed_sig_algs: None,
+            name_matching: NameMatching::Base,
+            revocation: false,
+            leaf_purpose: false,
+        }
+    }
+
+    /// The rules needed for `BasePolicy::Empty` to reject expired
+    /// or revoked chains, chains for other peer names or purposes,
+    /// and chains with a non-leaf certificate that is not a CA
+    /// (or not allowed to sign certificates)
+    pub fn minimal() -> Self {
+        Rules {
+            no_expiration: true,
+            non_leaf_must_be_ca: true,
+            non_leaf_has_key_cert_sign: true,
+            name_matching: NameMatching::Wildcard,
+            revocation: true,
+            leaf_purpose: true,
+            ..Rules::none()
+        }
+    }
+}
+
+impl CustomPolicy {
+    /// Each enabled rule holds for every chain accepted by the policy,
+    /// i.e., the same guarantee as the `conformance` lemma of the
+    /// corresponding trait in [`super::standard`]
+    pub proof fn lemma_enabled_rules(&self, chain: Seq<Certificate>, task: Task)
+        requires self.spec_valid_chain(chain, task)
+        ensures
+            self.rules.no_expiration ==> no_expiration(&chain, &task),
+            self.rules.outer_inner_sig_match ==> outer_inner_sig_match(&chain, &task),
+            self.rules.key_usage_non_empty ==> key_usage_non_empty(&chain, &task),
+            self.rules.issuer_subject_uid_version ==> issuer_subject_uid_version(&chain, &task),
+            self.rules.path_len_non_negative ==> path_len_non_negative(&chain, &task),
+            self.rules.path_len_constraint ==> path_len_constraint(&chain, &task),
+            self.rules.non_leaf_must_be_ca ==> non_leaf_must_be_ca(&chain, &task),
+            self.rules.non_leaf_has_key_cert_sign ==> non_leaf_has_key_cert_sign(&chain, &task),
+            self.rules.non_empty_san ==> non_empty_san(&chain, &task),
+            self.rules.aki_non_critical ==> aki_non_critical(&chain, &task),
+            self.rules.non_root_has_aki ==> non_root_has_aki(&chain, &task),
+            self.rules.non_leaf_has_ski ==> non_leaf_has_ski(&chain, &task),
+            self.rules.empty_subject_implies_critical_san ==> empty_subject_implies_critical_san(&chain, &task),
+            self.rules.non_critical_root_ski ==> non_critical_root_ski(&chain, &task),
+            self.rules.root_ca_has_aki ==> root_ca_has_aki(&chain, &task),
+            self.rules.root_ca_aki_no_issuer_or_serial ==> root_ca_aki_no_issuer_or_serial(&chain, &task),
+            self.rules.leaf_has_eku ==> leaf_has_eku(&chain, &task),
+            self.rules.root_has_no_eku ==> root_has_no_eku(&chain, &task),
+            self.rules.no_dsa ==> no_dsa(&chain, &task),
+            self.rules.rsa_2048 ==> rsa_2048(&chain, &task),
+            self.rules.allowed_curves ==> allowed_curves(&chain, &task),
+            self.rules.revocation ==> chain_not_revoked(&task, &chain) && chain_ocsp_ok(&task, &chain, true),
+    {}
+}
+
+mod internal {
+
+use super::*;
+
+rspec! {
+
+use ExecGeneralName as GeneralName;
+use ExecSubjectAltName as SubjectAltName;
+use ExecSignatureAlgorithm as SignatureAlgorithm;
+use ExecCertificate as Certificate;
+use ExecPeerName as PeerName;
+use ExecTask as Task;
+use ExecExtendedKeyUsageType as ExtendedKeyUsageType;
+
+use exec_str_lower as str_lower;
+use exec_match_name as match_name;
+use exec_match_email as match_email;
+use exec_match_ip_addr as match_ip_addr;
+use exec_same_dn as same_dn;
+use exec_check_auth_key_id as check_auth_key_id;
+
+use exec_no_expiration as no_expiration;
+use exec_outer_inner_sig_match as outer_inner_sig_match;
+use exec_key_usage_non_empty as key_usage_non_empty;
+use exec_issuer_subject_uid_version as issuer_subject_uid_version;
+use exec_path_len_non_negative as path_len_non_negative;
+use exec_path_len_constraint as path_len_constraint;
+use exec_non_leaf_must_be_ca as non_leaf_must_be_ca;
+use exec_non_leaf_has_key_cert_sign as non_leaf_has_key_cert_sign;
+use exec_non_empty_san as non_empty_san;
+use exec_aki_non_critical as aki_non_critical;
+use exec_non_root_has_aki as non_root_has_aki;
+use exec_non_leaf_has_ski as non_leaf_has_ski;
+use exec_empty_subject_implies_critical_san as empty_subject_implies_critical_san;
+use exec_non_critical_root_ski as non_critical_root_ski;
+use exec_root_ca_has_aki as root_ca_has_aki;
+use exec_root_ca_aki_no_issuer_or_serial as root_ca_aki_no_issuer_or_serial;
+use exec_leaf_has_eku as leaf_has_eku;
+use exec_root_has_no_eku as root_has_no_eku;
+use exec_no_dsa as no_dsa;
+use exec_rsa_2048 as rsa_2048;
+use exec_allowed_curves as allowed_curves;
+
+use exec_eku_permits_purpose as eku_permits_purpose;
+use exec_chain_not_revoked as chain_not_revoked;
+use exec_chain_ocsp_ok as chain_ocsp_ok;
+
+/// How the leaf is matched against the peer name of the task,
+/// in addition to the checks in the base policy
+#[derive(Copy, Clone)]
+pub enum NameMatching {
+    /// Leave name matching to the base policy
+    Base,
+
+    /// DNS names must be equal to a DNS name in SAN (case-insensitive);
+    /// IP and email addresses are matched as in `match_ip_addr`/`match_email`
+    Exact,
+
+    /// Same as `Exact`, but also allows a wildcard
+    /// in the left-most label (e.g., "*.example.com")
+    Wildcard,
+}
+
+/// Additional rules to enforce on top of the base policy,
+/// where each flag corresponds to a trait in `standard`
+pub struct Rules {
+    pub no_expiration: bool,
+    pub outer_inner_sig_match: bool,
+    pub key_usage_non_empty: bool,
+    pub issuer_subject_uid_version: bool,
+    pub path_len_non_negative: bool,
+    pub path_len_constraint: bool,
+    pub non_leaf_must_be_ca: bool,
+    pub non_leaf_has_key_cert_sign: bool,
+    pub non_empty_san: bool,
+    pub aki_non_critical: bool,
+    pub non_root_has_aki: bool,
+    pub non_leaf_has_ski: bool,
+    pub empty_subject_implies_critical_san: bool,
+    pub non_critical_root_ski: bool,
+    pub root_ca_has_aki: bool,
+    pub root_ca_aki_no_issuer_or_serial: bool,
+    pub leaf_has_eku: bool,
+    pub root_has_no_eku: bool,
+    pub no_dsa: bool,
+    pub rsa_2048: bool,
+    pub allowed_curves: bool,
+
+    /// If set, the signature algorithms (OIDs) allowed
+    /// on all certificates except for the root
+    pub allowed_sig_algs: Option<Seq<SpecString>>,
+
+    pub name_matching: NameMatching,
+
+    /// No certificate is revoked by the CRLs (if revocation checking is
+    /// enabled in the task) or the stapled OCSP responses, as in the
+    /// bundled policies (an unknown OCSP status is accepted)
+    pub revocation: bool,
+
+    /// The EKU of the leaf, if present, permits the purpose of the task
+    pub leaf_purpose: bool,
+}
+
+pub open spec fn sig_alg_allowed(rules: &Rules, alg: &SignatureAlgorithm) -> bool {
+    &rules.allowed_sig_algs matches Some(algs) ==>
+        exists |i: usize| 0 <= i < algs.len() && &algs[i as int] == &alg.id
+}
+
+/// The root is excluded since its self-signature is not checked
+pub open spec fn sig_algs_allowed(rules: &Rules, chain: &Seq<ExecRef<Certificate>>) -> bool {
+    chain.len() > 0 ==>
+    forall |i: usize| #![trigger chain[i as int]]
+        0 <= i < chain.len() - 1 ==> {
+            &&& sig_alg_allowed(rules, &chain[i as int].sig_alg_inner)
+            &&& sig_alg_allowed(rules, &chain[i as int].sig_alg_outer)
+        }
+}
+
+pub open spec fn leaf_purpose_valid(task: &Task, chain: &Seq<ExecRef<Certificate>>) -> bool {
+    chain.len() > 0 ==>
+    (&chain[0].ext_extended_key_usage matches Some(eku) ==>
+        exists |i: usize| 0 <= i < eku.usages.len() && {
+            ||| eku_permits_purpose(#[trigger] &eku.usages[i as int], &task.purpose)
+            ||| eku.usages[i as int] matches ExtendedKeyUsageType::Any
+        })
+}
+
+pub open spec fn name_matching_valid(mode: &NameMatching, task: &Task, leaf: &Certificate) -> bool {
+    match mode {
+        NameMatching::Base => true,
+        NameMatching::Exact => match_task_peer(task, leaf, false),
+        NameMatching::Wildcard => match_task_peer(task, leaf, true),
+    }
+}
+
+/// chain[0] is the leaf, and chain.last() is the root
+pub open spec fn valid_chain(rules: &Rules, chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    &&& rules.no_expiration ==> no_expiration(chain, task)
+    &&& rules.outer_inner_sig_match ==> outer_inner_sig_match(chain, task)
+    &&& rules.key_usage_non_empty ==> key_usage_non_empty(chain, task)
+    &&& rules.issuer_subject_uid_version ==> issuer_subject_uid_version(chain, task)
+    &&& rules.path_len_non_negative ==> path_len_non_negative(chain, task)
+    &&& rules.path_len_constraint ==> path_len_constraint(chain, task)
+    &&& rules.non_leaf_must_be_ca ==> non_leaf_must_be_ca(chain, task)
+    &&& rules.non_leaf_has_key_cert_sign ==> non_leaf_has_key_cert_sign(chain, task)
+    &&& rules.non_empty_san ==> non_empty_san(chain, task)
+    &&& rules.aki_non_critical ==> aki_non_critical(chain, task)
+    &&& rules.non_root_has_aki ==> non_root_has_aki(chain, task)
+    &&& rules.non_leaf_has_ski ==> non_leaf_has_ski(chain, task)
+    &&& rules.empty_subject_implies_critical_san ==> empty_subject_implies_critical_san(chain, task)
+    &&& rules.non_critical_root_ski ==> non_critical_root_ski(chain, task)
+    &&& rules.root_ca_has_aki ==> root_ca_has_aki(chain, task)
+    &&& rules.root_ca_aki_no_issuer_or_serial ==> root_ca_aki_no_issuer_or_serial(chain, task)
+    &&& rules.leaf_has_eku ==> leaf_has_eku(chain, task)
+    &&& rules.root_has_no_eku ==> root_has_no_eku(chain, task)
+    &&& rules.no_dsa ==> no_dsa(chain, task)
+    &&& rules.rsa_2048 ==> rsa_2048(chain, task)
+    &&& rules.allowed_curves ==> allowed_curves(chain, task)
+
+    &&& sig_algs_allowed(rules, chain)
+    &&& chain.len() > 0 ==> name_matching_valid(&rules.name_matching, task, &chain[0])
+
+    &&& rules.revocation ==> {
+        &&& chain_not_revoked(task, chain)
+        &&& chain_ocsp_ok(task, chain, true)
+    }
+    &&& rules.leaf_purpose ==> leaf_purpose_valid(task, chain)
+}
+
+/// Used by `BasePolicy::Empty`
+pub open spec fn likely_issued(issuer: &Certificate, subject: &Certificate) -> bool
+{
+    &&& same_dn(&issuer.subject, &subject.issuer, true)
+    &&& check_auth_key_id(issuer, subject)
+}
+
+} // rspec!
+
+} // mod internal
+
+} // verus!
+
+impl DiagnosePolicy for BasePolicy {
+    fn diagnose_chain(
+        &self,
+        chain: &Vec<&ExecCertificate>,
+        task: &ExecTask,
+    ) -> Option<PolicyFailure> {
+        match self {
+            BasePolicy::Empty => None,
+            BasePolicy::Chrome(policy) => policy.diagnose_chain(chain, task),
+            BasePolicy::Firefox(policy) => policy.diagnose_chain(chain, task),
+            BasePolicy::OpenSSL(policy) => policy.diagnose_chain(chain, task),
+        }
+    }
+}
+
+impl DiagnosePolicy for CustomPolicy {
+    fn diagnose_chain(
+        &self,
+        chain: &Vec<&ExecCertificate>,
+        task: &ExecTask,
+    ) -> Option<PolicyFailure> {
+        use internal::*;
+
+        if let Some(failure) = self.base.diagnose_chain(chain, task) {
+            return Some(failure);
+        }
+
+        if exec_valid_chain(&self.rules, chain, task) {
+            return None;
+        }
+
+        if !chain.is_empty()
+            && !exec_name_matching_valid(&self.rules.name_matching, task, chain[0])
+        {
+            return Some(PolicyFailure::at(predicate!(exec_name_matching_valid), 0));
+        }
+
+        let rules = &self.rules;
+        let predicate = first_failure(
+            &named_checks![
+                exec_no_expiration(chain, task) if rules.no_expiration,
+                exec_outer_inner_sig_match(chain, task) if rules.outer_inner_sig_match,
+                exec_key_usage_non_empty(chain, task) if rules.key_usage_non_empty,
+                exec_issuer_subject_uid_version(chain, task) if rules.issuer_subject_uid_version,
+                exec_path_len_non_negative(chain, task) if rules.path_len_non_negative,
+                exec_path_len_constraint(chain, task) if rules.path_len_constraint,
+                exec_non_leaf_must_be_ca(chain, task) if rules.non_leaf_must_be_ca,
+                exec_non_leaf_has_key_cert_sign(chain, task) if rules.non_leaf_has_key_cert_sign,
+                exec_non_empty_san(chain, task) if rules.non_empty_san,
+                exec_aki_non_critical(chain, task) if rules.aki_non_critical,
+                exec_non_root_has_aki(chain, task) if rules.non_root_has_aki,
+                exec_non_leaf_has_ski(chain, task) if rules.non_leaf_has_ski,
+                exec_empty_subject_implies_critical_san(chain, task)
+                    if rules.empty_subject_implies_critical_san,
+                exec_non_critical_root_ski(chain, task) if rules.non_critical_root_ski,
+                exec_root_ca_has_aki(chain, task) if rules.root_ca_has_aki,
+                exec_root_ca_aki_no_issuer_or_serial(chain, task)
+                    if rules.root_ca_aki_no_issuer_or_serial,
+                exec_leaf_has_eku(chain, task) if rules.leaf_has_eku,
+                exec_root_has_no_eku(chain, task) if rules.root_has_no_eku,
+                exec_no_dsa(chain, task) if rules.no_dsa,
+                exec_rsa_2048(chain, task) if rules.rsa_2048,
+                exec_allowed_curves(chain, task) if rules.allowed_curves,
+                exec_sig_algs_allowed(rules, chain),
+                exec_chain_not_revoked(task, chain) if rules.revocation,
+                exec_chain_ocsp_ok(task, chain, true) if rules.revocation,
+                exec_leaf_purpose_valid(task, chain) if rules.leaf_purpose,
+            ],
+            predicate!(exec_valid_chain),
+        );
+
+        Some(PolicyFailure::chain(predicate))
+    }
+}
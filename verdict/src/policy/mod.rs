@@ -1,10 +1,14 @@
-mod chrome;
+#[macro_use]
 mod common;
+
+mod chrome;
+mod custom;
 mod firefox;
 mod openssl;
 pub mod standard;
 
 pub use chrome::ChromePolicy;
 pub use common::*;
+pub use custom::{BasePolicy, CustomPolicy, NameMatching, Rules};
 pub use firefox::FirefoxPolicy;
 pub use openssl::OpenSSLPolicy;
@@ -30,6 +30,10 @@ impl Policy for OpenSSLPolicy {
     fn valid_chain(&self, chain: &Vec<&ExecCertificate>, task: &ExecTask) -> bool {
         internal::exec_valid_chain(self, chain, task)
     }
+
+    fn issuer_lookup(&self) -> (res: IssuerLookup) {
+        IssuerLookup::NameAndKeyId
+    }
 }
 
 // Automatically prove some standard requirements
@@ -65,6 +69,9 @@ standard::auto_std! {
 
     // This can be configured in OpenSSL, however
     // OpenSSLPolicy => standard::RSA2048 {}
+
+    // OpenSSL accepts any named curve it supports
+    // OpenSSLPolicy => standard::AllowedCurves {}
 }
 
 impl OpenSSLPolicy {
@@ -92,6 +99,7 @@ use ExecNameConstraints as NameConstraints;
 use ExecCertificatePolicies as CertificatePolicies;
 use ExecCertificate as Certificate;
 use ExecPurpose as Purpose;
+use ExecPeerName as PeerName;
 use ExecTask as Task;
 use ExecPolicyError as PolicyError;
 use ExecDistinguishedName as DistinguishedName;
@@ -107,11 +115,17 @@ use exec_same_dn as same_dn;
 use exec_ip_addr_in_range as ip_addr_in_range;
 use exec_check_duplicate_extensions as check_duplicate_extensions;
 use exec_starts_with as starts_with;
+use exec_eku_permits_purpose as eku_permits_purpose;
+use exec_match_email as match_email;
+use exec_match_ip_addr as match_ip_addr;
+use exec_chain_not_revoked as chain_not_revoked;
+use exec_chain_ocsp_ok as chain_ocsp_ok;
+use exec_check_validity as check_validity;
 
 pub struct Policy;
 
 // Some global assumptions/settings
-// - Purpose is set to X509_PURPOSE_SSL_SERVER
+// - Purpose is set according to `task.purpose` (X509_PURPOSE_SSL_SERVER, etc.)
 // - X509_V_FLAG_X509_STRICT is true
 // - X509_V_FLAG_POLICY_CHECK is false
 // - X509_V_FLAG_CRL_CHECK is false
@@ -130,15 +144,17 @@ pub open spec fn check_cert_key_level(cert: &Certificate) -> bool
     match cert.subject_key {
         // https://github.com/openssl/openssl/blob/5c5b8d2d7c59fc48981861629bb0b75a03497440/crypto/rsa/rsa_lib.c#L322
         // 1024 => 80 security bits
-        SubjectKey::RSA { mod_length } => mod_length >= 1024,
+        SubjectKey::RSA { mod_length, .. } => mod_length >= 1024,
 
+        // Ed25519 and Ed448 have 128 and 224 security bits, respectively
         // TODO: EC and DSA security levels
         _ => true,
     }
 }
 
 /// https://github.com/openssl/openssl/blob/5c5b8d2d7c59fc48981861629bb0b75a03497440/crypto/x509/x509_vfy.c#L1785
-pub open spec fn check_cert_time(cert: &Certificate, now: u64) -> bool
+/// `leaf` is only used in `TimeCheck::AsOfIssuance`
+pub open spec fn check_cert_time(task: &Task, cert: &Certificate, leaf: &Certificate) -> bool
 {
     // NOTE: X509_cmp_time(a, b) returns
     // 1 iff a > b
@@ -147,8 +163,7 @@ pub open spec fn check_cert_time(cert: &Certificate, now: u64) -> bool
     // ossl_x509_check_cert_time checks that
     // X509_cmp_time(not_before, now) == -1 ==> not_before <= now
     // X509_cmp_time(not_after, now) == 1 ==> not_after > now
-    &&& cert.not_before <= now
-    &&& cert.not_after > now
+    check_validity(task, cert, leaf, false)
 }
 
 /// https://github.com/openssl/openssl/blob/5c5b8d2d7c59fc48981861629bb0b75a03497440/crypto/x509/v3_purp.c#L653
@@ -359,22 +374,31 @@ pub open spec fn check_name_constraints(chain: &Seq<ExecRef<Certificate>>) -> bo
             check_name_constraints_helper(#[trigger] &chain[j as int], &nc, j == 0))
 }
 
-/// Check for purpose == X509_PURPOSE_SSL_SERVER, i.e. special case of the following calls
+/// Check for purpose == X509_PURPOSE_SSL_SERVER, X509_PURPOSE_SSL_CLIENT,
+/// X509_PURPOSE_CODE_SIGN or X509_PURPOSE_SMIME_SIGN, i.e. special case of the following calls
 /// - check_purpose: https://github.com/openssl/openssl/blob/5c5b8d2d7c59fc48981861629bb0b75a03497440/crypto/x509/x509_vfy.c#L462
 /// - X509_check_purpose: https://github.com/openssl/openssl/blob/5c5b8d2d7c59fc48981861629bb0b75a03497440/crypto/x509/v3_purp.c#L86
 /// - check_purpose_ssl_server: https://github.com/openssl/openssl/blob/5c5b8d2d7c59fc48981861629bb0b75a03497440/crypto/x509/v3_purp.c#L736
+/// - check_purpose_ssl_client in crypto/x509/v3_purp.c
+/// - check_purpose_code_sign in crypto/x509/v3_purp.c
+/// - check_purpose_smime_sign in crypto/x509/v3_purp.c
 /// Assuming X509_check_trust returns X509_TRUST_UNTRUSTED
-/// XKU_SGC is not supported
-pub open spec fn check_purpose(cert: &Certificate, is_leaf: bool) -> bool
+/// XKU_SGC and Netscape cert types are not supported
+pub open spec fn check_purpose(task: &Task, cert: &Certificate, is_leaf: bool) -> bool
 {
     &&& &cert.ext_extended_key_usage matches Some(eku) ==>
         exists |i: usize| 0 <= i < eku.usages.len() &&
-            (#[trigger] &eku.usages[i as int] matches ExtendedKeyUsageType::ServerAuth)
+            eku_permits_purpose(#[trigger] &eku.usages[i as int], &task.purpose)
 
     &&& if is_leaf {
-        // https://github.com/openssl/openssl/blob/5c5b8d2d7c59fc48981861629bb0b75a03497440/crypto/x509/v3_purp.c#L733
         &cert.ext_key_usage matches Some(ku) ==>
-            ku.digital_signature || ku.key_encipherment || ku.key_agreement
+            match task.purpose {
+                // https://github.com/openssl/openssl/blob/5c5b8d2d7c59fc48981861629bb0b75a03497440/crypto/x509/v3_purp.c#L733
+                Purpose::ServerAuth => ku.digital_signature || ku.key_encipherment || ku.key_agreement,
+                Purpose::ClientAuth => ku.digital_signature || ku.key_agreement,
+                Purpose::CodeSigning => ku.digital_signature,
+                Purpose::EmailProtection => ku.digital_signature || ku.non_repudiation,
+            }
     } else {
         // https://github.com/openssl/openssl/blob/5c5b8d2d7c59fc48981861629bb0b75a03497440/crypto/x509/v3_purp.c#L702
         check_ca(cert) == 1
@@ -410,13 +434,13 @@ pub open spec fn check_unhandled_extensions(cert: &Certificate) -> bool {
 
 /// Common checks for certificates, this includes checks in
 /// - check_extensions: https://github.com/openssl/openssl/blob/5c5b8d2d7c59fc48981861629bb0b75a03497440/crypto/x509/x509_vfy.c#L1785
-pub open spec fn valid_cert_common(_env: &Policy, task: &Task, cert: &Certificate, is_leaf: bool, is_root: bool, depth: usize) -> bool
+pub open spec fn valid_cert_common(_env: &Policy, task: &Task, cert: &Certificate, leaf: &Certificate, is_leaf: bool, is_root: bool, depth: usize) -> bool
 {
     // NOTE: unhandled critical extensions not checked
     // https://github.com/openssl/openssl/blob/5c5b8d2d7c59fc48981861629bb0b75a03497440/crypto/x509/x509_vfy.c#L543-L545
 
     &&& check_cert_key_level(cert)
-    &&& check_cert_time(cert, task.now)
+    &&& check_cert_time(task, cert, leaf)
     &&& check_basic_constraints(cert)
     &&& check_key_usage(cert)
 
@@ -443,7 +467,7 @@ pub open spec fn valid_cert_common(_env: &Policy, task: &Task, cert: &Certificat
     &&& &cert.sig_alg_inner.bytes == &cert.sig_alg_outer.bytes
 
     // https://github.com/openssl/openssl/blob/5c5b8d2d7c59fc48981861629bb0b75a03497440/crypto/x509/x509_vfy.c#L645-L647
-    &&& check_purpose(cert, is_leaf)
+    &&& check_purpose(task, cert, is_leaf)
 
     &&& check_auth_subject_key_id(cert, is_root, is_leaf)
 
@@ -509,15 +533,15 @@ pub open spec fn check_hostname(cert: &Certificate, hostname: &SpecString) -> bo
 }
 
 pub open spec fn valid_leaf(env: &Policy, task: &Task, cert: &Certificate) -> bool {
-    valid_cert_common(env, task, cert, true, false, 0)
+    valid_cert_common(env, task, cert, cert, true, false, 0)
 }
 
-pub open spec fn valid_intermediate(env: &Policy, task: &Task, cert: &Certificate, depth: usize) -> bool {
-    valid_cert_common(env, task, cert, false, false, depth)
+pub open spec fn valid_intermediate(env: &Policy, task: &Task, cert: &Certificate, leaf: &Certificate, depth: usize) -> bool {
+    valid_cert_common(env, task, cert, leaf, false, false, depth)
 }
 
-pub open spec fn valid_root(env: &Policy, task: &Task, cert: &Certificate, depth: usize) -> bool {
-    &&& valid_cert_common(env, task, cert, false, true, depth)
+pub open spec fn valid_root(env: &Policy, task: &Task, cert: &Certificate, leaf: &Certificate, depth: usize) -> bool {
+    &&& valid_cert_common(env, task, cert, leaf, false, true, depth)
 
     // // Per x509-limbo::webpki::aki::root-with-aki-*
     // &&& &cert.ext_authority_key_id matches Some(aki) ==>
@@ -530,10 +554,26 @@ pub open spec fn valid_chain(env: &Policy, chain: &Seq<ExecRef<Certificate>>, ta
 {
     chain.len() >= 2 && {
         &&& valid_leaf(env, task, &chain[0])
-        &&& forall |i: usize| 1 <= i < chain.len() - 1 ==> valid_intermediate(&env, &task, #[trigger] &chain[i as int], (i - 1) as usize)
-        &&& valid_root(env, task, &chain[chain.len() - 1], (chain.len() - 2) as usize)
+        &&& forall |i: usize| 1 <= i < chain.len() - 1 ==> valid_intermediate(&env, &task, #[trigger] &chain[i as int], &chain[0], (i - 1) as usize)
+        &&& valid_root(env, task, &chain[chain.len() - 1], &chain[0], (chain.len() - 2) as usize)
         &&& check_name_constraints(chain)
-        &&& &task.hostname matches Some(hostname) ==> check_hostname(&chain[0], hostname)
+        &&& &task.peer matches Some(peer) ==> check_peer_name(&chain[0], peer)
+        &&& chain_not_revoked(task, chain)
+
+        // Unknown OCSP statuses are left to the application
+        &&& chain_ocsp_ok(task, chain, true)
+    }
+}
+
+/// Check the leaf against the reference identifier of the peer
+/// - X509_check_host for DNS names
+/// - X509_check_ip for IP addresses (SAN only)
+/// - X509_check_email for email addresses
+pub open spec fn check_peer_name(cert: &Certificate, peer: &PeerName) -> bool {
+    match peer {
+        PeerName::Dns(hostname) => check_hostname(cert, hostname),
+        PeerName::Ip(addr) => match_ip_addr(cert, addr),
+        PeerName::Email(email) => match_email(cert, email),
     }
 }
 
@@ -569,3 +609,124 @@ fn exec_clone_string(name: &String) -> (res: String)
 } // mod internal
 
 }
+
+impl DiagnosePolicy for OpenSSLPolicy {
+    fn diagnose_chain(
+        &self,
+        chain: &Vec<&ExecCertificate>,
+        task: &ExecTask,
+    ) -> Option<PolicyFailure> {
+        use internal::*;
+
+        if chain.len() < 2 {
+            return Some(PolicyFailure::chain(predicate!(exec_valid_chain)));
+        }
+
+        let leaf = chain[0];
+        let root = chain[chain.len() - 1];
+
+        if !exec_valid_leaf(self, task, leaf) {
+            return Some(PolicyFailure::at(
+                Self::diagnose_cert_common(
+                    task,
+                    leaf,
+                    leaf,
+                    true,
+                    false,
+                    predicate!(exec_valid_leaf),
+                ),
+                0,
+            ));
+        }
+
+        for i in 1..chain.len() - 1 {
+            if !exec_valid_intermediate(self, task, chain[i], leaf, i - 1) {
+                return Some(PolicyFailure::at(
+                    Self::diagnose_cert_common(
+                        task,
+                        chain[i],
+                        leaf,
+                        false,
+                        false,
+                        predicate!(exec_valid_intermediate),
+                    ),
+                    i,
+                ));
+            }
+        }
+
+        if !exec_valid_root(self, task, root, leaf, chain.len() - 2) {
+            return Some(PolicyFailure::at(
+                Self::diagnose_cert_common(
+                    task,
+                    root,
+                    leaf,
+                    false,
+                    true,
+                    predicate!(exec_valid_root),
+                ),
+                chain.len() - 1,
+            ));
+        }
+
+        if !exec_check_name_constraints(chain) {
+            return Some(PolicyFailure::chain(predicate!(
+                exec_check_name_constraints
+            )));
+        }
+
+        if let Some(peer) = &task.peer {
+            if !exec_check_peer_name(leaf, peer) {
+                return Some(PolicyFailure::at(predicate!(exec_check_peer_name), 0));
+            }
+        }
+
+        for i in 0..chain.len() - 1 {
+            if !exec_not_revoked(task, chain[i], chain[i + 1]) {
+                return Some(PolicyFailure::at(predicate!(exec_not_revoked), i));
+            }
+        }
+
+        for i in 0..chain.len() - 1 {
+            if !exec_ocsp_status_ok(task, chain[i], chain[i + 1], true) {
+                return Some(PolicyFailure::at(predicate!(exec_ocsp_status_ok), i));
+            }
+        }
+
+        None
+    }
+}
+
+impl OpenSSLPolicy {
+    /// Find the failing conjunct of `valid_cert_common`
+    fn diagnose_cert_common(
+        task: &ExecTask,
+        cert: &ExecCertificate,
+        leaf: &ExecCertificate,
+        is_leaf: bool,
+        is_root: bool,
+        default: &'static str,
+    ) -> &'static str {
+        use internal::*;
+
+        first_failure(
+            &named_checks![
+                exec_check_cert_key_level(cert),
+                exec_check_cert_time(task, cert, leaf),
+                exec_check_basic_constraints(cert),
+                exec_check_key_usage(cert),
+                exec_check_ca => if is_leaf {
+                    exec_check_ca(cert) != 2
+                } else {
+                    exec_check_ca(cert) == 1
+                },
+                exec_check_san(cert),
+                exec_check_purpose(task, cert, is_leaf),
+                exec_check_auth_subject_key_id(cert, is_root, is_leaf),
+                exec_check_unhandled_extensions(cert),
+                exec_check_duplicate_extensions(cert),
+            ],
+            default,
+        )
+    }
+}
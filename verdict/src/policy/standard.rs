@@ -1,73 +1,227 @@
 //! Traits of `Policy` that specify selected rules from RFC 5280 and CA/B BRs
 
 #![allow(unused)]
+#![allow(unused_parens)]
+#![allow(unexpected_cfgs)]
 
 use super::common::*;
+#[cfg(not(feature = "trace"))]
+use verdict_rspec::rspec;
+#[cfg(feature = "trace")]
+use verdict_rspec::rspec_trace as rspec;
+use verdict_rspec_lib::*;
 use vstd::prelude::*;
 
 verus! {
 
-/// A validated chain should not contain expired certificates
+// Each rule is stated as a predicate on the chain (leaf first, root last)
+// so that it can also be checked at runtime via the generated exec version
+// (e.g., `exec_no_expiration`); see `super::custom::CustomPolicy`.
+rspec! {
+
+use ExecSubjectKey as SubjectKey;
+use ExecKeyUsage as KeyUsage;
+use ExecBasicConstraints as BasicConstraints;
+use ExecSubjectAltName as SubjectAltName;
+use ExecAuthorityKeyIdentifier as AuthorityKeyIdentifier;
+use ExecSubjectKeyIdentifier as SubjectKeyIdentifier;
+use ExecExtendedKeyUsage as ExtendedKeyUsage;
+use ExecCertificate as Certificate;
+use ExecTask as Task;
+use ExecTimeCheck as TimeCheck;
+
+/// Only required under `TimeCheck::Strict`, since the other modes
+/// deliberately accept certificates expired at `task.now`
+pub open spec fn no_expiration(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    task.time_check matches TimeCheck::Strict ==>
+    forall |i: usize| #![trigger chain[i as int]]
+        0 <= i < chain.len() ==>
+        chain[i as int].not_before <= task.now && task.now <= chain[i as int].not_after
+}
+
+pub open spec fn outer_inner_sig_match(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    forall |i: usize| #![trigger chain[i as int]]
+        0 <= i < chain.len() ==>
+        &chain[i as int].sig_alg_inner.bytes == &chain[i as int].sig_alg_outer.bytes
+}
+
+pub open spec fn key_usage_non_empty(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    forall |i: usize| #![trigger chain[i as int]]
+        0 <= i < chain.len() ==>
+        (&chain[i as int].ext_key_usage matches Some(key_usage) ==> {
+            ||| key_usage.digital_signature
+            ||| key_usage.non_repudiation
+            ||| key_usage.key_encipherment
+            ||| key_usage.data_encipherment
+            ||| key_usage.key_agreement
+            ||| key_usage.key_cert_sign
+            ||| key_usage.crl_sign
+            ||| key_usage.encipher_only
+            ||| key_usage.decipher_only
+        })
+}
+
+pub open spec fn issuer_subject_uid_version(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    forall |i: usize| #![trigger chain[i as int]]
+        0 <= i < chain.len() ==>
+        ((chain[i as int].issuer_uid matches Some(_) || chain[i as int].subject_uid matches Some(_)) ==>
+        chain[i as int].version == 2 || chain[i as int].version == 3)
+}
+
+pub open spec fn path_len_non_negative(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    forall |i: usize| #![trigger chain[i as int]]
+        0 <= i < chain.len() ==>
+        (&chain[i as int].ext_basic_constraints matches Some(bc) ==>
+        (bc.path_len matches Some(limit) ==> limit >= 0))
+}
+
+pub open spec fn path_len_constraint(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    forall |i: usize| #![trigger chain[i as int]]
+        1 <= i < chain.len() ==> {
+            &chain[i as int].ext_basic_constraints matches Some(bc) ==> {
+                bc.path_len matches Some(limit) ==> {
+                    bc.is_ca &&
+                    (&chain[i as int].ext_key_usage matches Some(key_usage) ==> key_usage.key_cert_sign)
+                    ==>
+                    (i - 1) <= limit as usize
+                }
+            }
+        }
+}
+
+pub open spec fn non_leaf_must_be_ca(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    forall |i: usize| #![trigger chain[i as int]]
+        1 <= i < chain.len() ==>
+        (&chain[i as int].ext_basic_constraints matches Some(bc) && bc.is_ca)
+}
+
+pub open spec fn non_leaf_has_key_cert_sign(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    forall |i: usize| #![trigger chain[i as int]]
+        1 <= i < chain.len() ==>
+        (&chain[i as int].ext_key_usage matches Some(key_usage) ==> key_usage.key_cert_sign)
+}
+
+pub open spec fn non_empty_san(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    forall |i: usize| #![trigger chain[i as int]]
+        0 <= i < chain.len() ==>
+        (&chain[i as int].ext_subject_alt_name matches Some(san) ==> san.names.len() > 0)
+}
+
+pub open spec fn aki_non_critical(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    chain.len() > 0 ==>
+    (&chain[chain.len() - 1].ext_authority_key_id matches Some(akid) ==>
+    (akid.critical matches Some(c) ==> !c))
+}
+
+pub open spec fn non_root_has_aki(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    chain.len() > 0 ==>
+    forall |i: usize| #![trigger chain[i as int]]
+        0 <= i < chain.len() - 1 ==>
+        (&chain[i as int].ext_authority_key_id matches Some(akid) &&
+        akid.key_id matches Some(..))
+}
+
+pub open spec fn non_leaf_has_ski(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    forall |i: usize| #![trigger chain[i as int]]
+        1 <= i < chain.len() ==>
+        chain[i as int].ext_subject_key_id matches Some(..)
+}
+
+pub open spec fn empty_subject_implies_critical_san(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    chain.len() > 0 ==>
+    (chain[0].subject.0.len() == 0 ==>
+    (&chain[0].ext_subject_alt_name matches Some(san) &&
+    (san.critical matches Some(c) && c)))
+}
+
+pub open spec fn non_critical_root_ski(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    chain.len() > 0 ==>
+    (&chain[chain.len() - 1].ext_subject_key_id matches Some(skid) ==>
+    (skid.critical matches Some(c) ==> !c))
+}
+
+pub open spec fn root_ca_has_aki(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    chain.len() > 0 ==>
+    (&chain[chain.len() - 1].ext_authority_key_id matches Some(akid) &&
+    akid.key_id matches Some(..))
+}
+
+pub open spec fn root_ca_aki_no_issuer_or_serial(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    chain.len() > 0 ==>
+    (&chain[chain.len() - 1].ext_authority_key_id matches Some(akid) ==>
+    akid.issuer matches None && akid.serial matches None)
+}
+
+pub open spec fn leaf_has_eku(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    chain.len() > 0 ==>
+    (&chain[0].ext_extended_key_usage matches Some(eku) &&
+    (eku.critical matches Some(c) ==> !c))
+}
+
+pub open spec fn root_has_no_eku(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    chain.len() > 0 ==>
+    chain[chain.len() - 1].ext_extended_key_usage matches None
+}
+
+pub open spec fn no_dsa(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    forall |i: usize| #![trigger chain[i as int]]
+        0 <= i < chain.len() ==>
+        !(&chain[i as int].subject_key matches SubjectKey::DSA { .. })
+}
+
+pub open spec fn rsa_2048(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    forall |i: usize| #![trigger chain[i as int]]
+        0 <= i < chain.len() ==>
+        (&chain[i as int].subject_key matches SubjectKey::RSA { mod_length, .. } ==>
+        mod_length >= 2048)
+}
+
+pub open spec fn allowed_curves(chain: &Seq<ExecRef<Certificate>>, task: &Task) -> bool {
+    forall |i: usize| #![trigger chain[i as int]]
+        0 <= i < chain.len() ==>
+        (&chain[i as int].subject_key matches SubjectKey::EC { curve, .. } ==> {
+            ||| curve == "1.2.840.10045.3.1.7"@ // P-256
+            ||| curve == "1.3.132.0.34"@ // P-384
+            ||| curve == "1.3.132.0.35"@ // P-521
+        })
+}
+
+} // rspec!
+
+/// A validated chain should not contain expired certificates,
+/// when the task uses strict time checking (`TimeCheck::Strict`)
 pub trait NoExpiration: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            forall |i: usize| #![trigger chain[i as int]]
-                0 <= i < chain.len() ==>
-                chain[i as int].not_before <= task.now <= chain[i as int].not_after;
+        ensures no_expiration(&chain, &task);
 }
 
 /// Outer signature algorithm should match the inner one
 pub trait OuterInnerSigMatch: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            forall |i: usize| #![trigger chain[i as int]]
-                0 <= i < chain.len() ==>
-                chain[i as int].sig_alg_inner.bytes == chain[i as int].sig_alg_outer.bytes;
+        ensures outer_inner_sig_match(&chain, &task);
 }
 
 /// If the extension KeyUsage is present, at least one bit must be set
 pub trait KeyUsageNonEmpty: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            forall |i: usize| #![trigger chain[i as int]]
-                0 <= i < chain.len() ==>
-                (chain[i as int].ext_key_usage matches Some(key_usage) ==> {
-                    ||| key_usage.digital_signature
-                    ||| key_usage.non_repudiation
-                    ||| key_usage.key_encipherment
-                    ||| key_usage.data_encipherment
-                    ||| key_usage.key_agreement
-                    ||| key_usage.key_cert_sign
-                    ||| key_usage.crl_sign
-                    ||| key_usage.encipher_only
-                    ||| key_usage.decipher_only
-                });
+        ensures key_usage_non_empty(&chain, &task);
 }
 
 /// Issuer and subject UID should only appear if version is 2 or 3
 pub trait IssuerSubjectUIDVersion: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            forall |i: usize| #![trigger chain[i as int]]
-                0 <= i < chain.len() ==>
-                (chain[i as int].issuer_uid matches Some(_) ||
-                 chain[i as int].subject_uid matches Some(_)) ==>
-                chain[i as int].version == 2 || chain[i as int].version == 3;
+        ensures issuer_subject_uid_version(&chain, &task);
 }
 
 /// PathLenConstraints should be non-negative
 pub trait PathLenNonNegative: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            forall |i: usize| #![trigger chain[i as int]]
-                0 <= i < chain.len() ==>
-                (chain[i as int].ext_basic_constraints matches Some(bc) ==>
-                (bc.path_len matches Some(limit) ==> limit >= 0));
+        ensures path_len_non_negative(&chain, &task);
 }
 
 /// If BasicConstraints.PathLenConstraint is present,
@@ -77,48 +231,28 @@ pub trait PathLenNonNegative: Policy {
 pub trait PathLenConstraint: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            forall |i: usize| #![trigger chain[i as int]]
-                1 <= i < chain.len() ==> {
-                    &chain[i as int].ext_basic_constraints matches Some(bc) ==> {
-                        bc.path_len matches Some(limit) ==> {
-                            bc.is_ca &&
-                            (chain[i as int].ext_key_usage matches Some(key_usage) ==> key_usage.key_cert_sign)
-                            ==>
-                            (i - 1) <= limit as usize
-                        }
-                    }
-                };
+        ensures path_len_constraint(&chain, &task);
 }
 
 /// Every non-leaf certificate must be a CA certificate
 pub trait NonLeafMustBeCA: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            forall |i: usize| #![trigger chain[i as int]]
-                1 <= i < chain.len() ==>
-                (&chain[i as int].ext_basic_constraints matches Some(bc) && bc.is_ca);
+        ensures non_leaf_must_be_ca(&chain, &task);
 }
 
 /// Every non-leaf certificate must have keyCertSign set in KeyUsage (if present)
 pub trait NonLeafHasKeyCertSign: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            forall |i: usize| #![trigger chain[i as int]]
-                1 <= i < chain.len() ==>
-                (&chain[i as int].ext_key_usage matches Some(key_usage) ==> key_usage.key_cert_sign);
+        ensures non_leaf_has_key_cert_sign(&chain, &task);
 }
 
 /// If SubjectAltName is present, it should contain at least one name
 pub trait NonEmptySAN: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            forall |i: usize| #![trigger chain[i as int]]
-                0 <= i < chain.len() ==>
-                (chain[i as int].ext_subject_alt_name matches Some(san) ==> san.names.len() > 0);
+        ensures non_empty_san(&chain, &task);
 }
 
 /// Generalized from x509-limbo::rfc5280::aki::critical-aki
@@ -126,9 +260,7 @@ pub trait NonEmptySAN: Policy {
 pub trait AKINonCritical: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            chain.last().ext_authority_key_id matches Some(akid)
-            ==> !akid.critical.unwrap_or(false);
+        ensures aki_non_critical(&chain, &task);
 }
 
 /// Generalized from x509-limbo::rfc5280::aki::leaf/intermediate-missing-aki
@@ -138,11 +270,7 @@ pub trait AKINonCritical: Policy {
 pub trait NonRootHasAKI: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            forall |i: usize| #![trigger chain[i as int]]
-                0 <= i < chain.len() - 1 ==>
-                (chain[i as int].ext_authority_key_id matches Some(akid) &&
-                akid.key_id matches Some(..));
+        ensures non_root_has_aki(&chain, &task);
 }
 
 /// Generalized from x509-limbo::rfc5280::ski::intermediate-missing-ski
@@ -153,10 +281,7 @@ pub trait NonRootHasAKI: Policy {
 pub trait NonLeafHasSKI: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            forall |i: usize| #![trigger chain[i as int]]
-                1 <= i < chain.len() ==>
-                chain[i as int].ext_subject_key_id matches Some(..);
+        ensures non_leaf_has_ski(&chain, &task);
 }
 
 /// Generalized from x509-limbo::rfc5280::san::noncritical-with-empty-subject
@@ -165,10 +290,7 @@ pub trait NonLeafHasSKI: Policy {
 pub trait EmptySubjectImpliesCriticalSAN: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            chain[0].subject.0.len() == 0
-            ==> (chain[0].ext_subject_alt_name matches Some(san) &&
-                san.critical.unwrap_or(false));
+        ensures empty_subject_implies_critical_san(&chain, &task);
 }
 
 /// Generalized from x509-limbo::rfc5280::ski::critical-ski
@@ -176,9 +298,7 @@ pub trait EmptySubjectImpliesCriticalSAN: Policy {
 pub trait NonCriticalRootSKI: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            chain.last().ext_subject_key_id matches Some(skid)
-            ==> !skid.critical.unwrap_or(false);
+        ensures non_critical_root_ski(&chain, &task);
 }
 
 /// Generalized from x509-limbo::webpki::aki::root-with-aki-missing-keyidentifier
@@ -187,9 +307,7 @@ pub trait NonCriticalRootSKI: Policy {
 pub trait RootCAHasAKI: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            chain.last().ext_authority_key_id matches Some(akid)
-            && akid.key_id matches Some(..);
+        ensures root_ca_has_aki(&chain, &task);
 }
 
 /// Generalized from x509-limbo::webpki::aki::root-with-aki-authoritycertissuer
@@ -199,9 +317,7 @@ pub trait RootCAHasAKI: Policy {
 pub trait RootCAAKINoIssuerOrSerial: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            chain.last().ext_authority_key_id matches Some(akid)
-            ==> akid.issuer matches None && akid.serial matches None;
+        ensures root_ca_aki_no_issuer_or_serial(&chain, &task);
 }
 
 /// Generalized from x509-limbo::webpki::eku::ee-without-eku
@@ -209,9 +325,7 @@ pub trait RootCAAKINoIssuerOrSerial: Policy {
 pub trait LeafHasEKU: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            chain[0].ext_extended_key_usage matches Some(eku)
-            && !eku.critical.unwrap_or(false);
+        ensures leaf_has_eku(&chain, &task);
 }
 
 /// Generalized from x509-limbo::webpki::eku::root-has-eku
@@ -219,8 +333,7 @@ pub trait LeafHasEKU: Policy {
 pub trait RootHasNoEKU: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            chain.last().ext_extended_key_usage matches None;
+        ensures root_has_no_eku(&chain, &task);
 }
 
 /// Generalized from x509-limbo::webpki::forbidden-dsa-root
@@ -228,10 +341,7 @@ pub trait RootHasNoEKU: Policy {
 pub trait NoDSA: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            forall |i: usize| #![trigger chain[i as int]]
-                0 <= i < chain.len() ==>
-                !(chain[i as int].subject_key matches SubjectKey::DSA { .. });
+        ensures no_dsa(&chain, &task);
 }
 
 /// Generalized from x509-limbo::webpki::forbidden-weak-rsa-key-in-root
@@ -240,11 +350,15 @@ pub trait NoDSA: Policy {
 pub trait RSA2048: Policy {
     proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
         requires self.spec_valid_chain(chain, task)
-        ensures
-            forall |i: usize| #![trigger chain[i as int]]
-                0 <= i < chain.len()
-                ==> (chain[i as int].subject_key matches SubjectKey::RSA { mod_length }
-                ==> mod_length >= 2048);
+        ensures rsa_2048(&chain, &task);
+}
+
+/// CA/B BR 6.1.5: ECDSA keys must be on one of the
+/// NIST P-256, P-384, or P-521 named curves
+pub trait AllowedCurves: Policy {
+    proof fn conformance(&self, chain: Seq<Certificate>, task: Task)
+        requires self.spec_valid_chain(chain, task)
+        ensures allowed_curves(&chain, &task);
 }
 
 /// Tries to automatically prove the provided standard rules
@@ -0,0 +1,255 @@
+//! Diagnostics on why a certificate chain is rejected.
+//! NOTE: nothing in this module is verified; the failures are
+//! recorded during the verified path building of [`crate::Validator`],
+//! whose verdict (`ValidationReport::valid`) is always authoritative.
+//!
+//! With the `serde` feature, reports can be serialized but not
+//! deserialized, since predicate names are `&'static str`.
+
+use std::fmt;
+
+use crate::policy::{ExecCertificate, ExecTask, PolicyFailure};
+
+/// A certificate referred to in a [`ValidationReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum CertRef {
+    /// Index into the certificate chain being validated
+    Bundle(usize),
+
+    /// Index into the root store
+    Root(usize),
+}
+
+/// The reason why a candidate path is rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum PathFailure {
+    /// No certificate in the chain or the root store
+    /// is considered an issuer of the last certificate in the path.
+    NoIssuer,
+
+    /// `issuer` is a likely issuer per the policy,
+    /// but its signature does not verify.
+    BadSignature {
+        /// The certificate whose public key was used
+        issuer: CertRef,
+    },
+
+    /// The path completed with root `root_idx`
+    /// is rejected by the policy.
+    Policy {
+        /// Index of the root certificate in the root store
+        root_idx: usize,
+
+        /// Name of the failing predicate (e.g., `cert_verified_leaf`)
+        predicate: &'static str,
+    },
+}
+
+/// A rejected candidate path explored during validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PathReport {
+    /// Indices into the chain, starting from the leaf.
+    pub path: Vec<usize>,
+
+    /// Why the path is rejected.
+    pub failure: PathFailure,
+
+    /// The certificate the failure is attributed to,
+    /// or `None` if the failure concerns the entire chain.
+    pub cert: Option<CertRef>,
+
+    /// Depth of `cert` in the candidate chain (`0` for the leaf).
+    pub depth: Option<usize>,
+}
+
+/// A record of all candidate paths rejected by a [`crate::Validator`]
+/// before it accepts a chain, or before it gives up.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ValidationReport {
+    /// Whether a valid path was eventually found.
+    pub valid: bool,
+
+    /// Rejected paths in the order they are explored.
+    pub rejected: Vec<PathReport>,
+}
+
+impl PathReport {
+    pub(crate) fn new(path: &Vec<usize>, failure: PathFailure, depth: Option<usize>) -> Self {
+        let cert = depth.map(|d| match (path.get(d), &failure) {
+            (Some(idx), _) => CertRef::Bundle(*idx),
+            (None, PathFailure::Policy { root_idx, .. }) => CertRef::Root(*root_idx),
+            (None, _) => CertRef::Bundle(path[path.len() - 1]),
+        });
+
+        PathReport {
+            path: path.clone(),
+            failure,
+            cert,
+            depth,
+        }
+    }
+
+    pub(crate) fn from_policy(path: &Vec<usize>, root_idx: usize, failure: PolicyFailure) -> Self {
+        PathReport::new(
+            path,
+            PathFailure::Policy {
+                root_idx,
+                predicate: failure.predicate,
+            },
+            failure.depth,
+        )
+    }
+}
+
+/// Explains why a policy rejects a candidate chain
+pub(crate) type DiagnoseFn<'r> =
+    dyn Fn(&Vec<&ExecCertificate>, &ExecTask) -> Option<PolicyFailure> + 'r;
+
+/// Builds a [`ValidationReport`] from the events of path building
+pub(crate) struct ReportRecorder<'r> {
+    report: ValidationReport,
+    diagnose: &'r DiagnoseFn<'r>,
+
+    /// `(root_idx, subject_idx)` of each root that is a likely
+    /// issuer of the bundle certificate `subject_idx`,
+    /// but whose signature does not verify
+    bad_root_sigs: Vec<(usize, usize)>,
+
+    /// The path currently being explored
+    path: Vec<usize>,
+
+    /// Number of rejected paths recorded before exploring `path`
+    path_start: usize,
+}
+
+impl<'r> ReportRecorder<'r> {
+    pub(crate) fn new(diagnose: &'r DiagnoseFn<'r>) -> Self {
+        ReportRecorder {
+            report: ValidationReport::default(),
+            diagnose,
+            bad_root_sigs: Vec::new(),
+            path: Vec::new(),
+            path_start: 0,
+        }
+    }
+
+    pub(crate) fn bad_root_signature(&mut self, root_idx: usize, subject_idx: usize) {
+        self.bad_root_sigs.push((root_idx, subject_idx));
+    }
+
+    /// Starts exploring `path`, which fails on any
+    /// root issuer of its last certificate with a bad signature
+    pub(crate) fn explore(&mut self, path: &Vec<usize>) {
+        self.path = path.clone();
+        self.path_start = self.report.rejected.len();
+
+        let last = path[path.len() - 1];
+
+        let bad_roots = self
+            .bad_root_sigs
+            .iter()
+            .filter(|(_, subject_idx)| *subject_idx == last)
+            .map(|(root_idx, _)| *root_idx)
+            .collect::<Vec<_>>();
+
+        for root_idx in bad_roots {
+            self.push(PathFailure::BadSignature {
+                issuer: CertRef::Root(root_idx),
+            });
+        }
+    }
+
+    /// The bundle certificate `issuer_idx` is a likely issuer of
+    /// the last certificate in the current path, but its signature does not verify
+    pub(crate) fn bad_signature(&mut self, issuer_idx: usize) {
+        self.push(PathFailure::BadSignature {
+            issuer: CertRef::Bundle(issuer_idx),
+        });
+    }
+
+    /// `chain` (the current path completed with root `root_idx`)
+    /// is rejected by the policy on `task`
+    pub(crate) fn policy_rejected(
+        &mut self,
+        root_idx: usize,
+        chain: &Vec<&ExecCertificate>,
+        task: &ExecTask,
+    ) {
+        let failure = (self.diagnose)(chain, task).unwrap_or(PolicyFailure::chain("valid_chain"));
+        self.report
+            .rejected
+            .push(PathReport::from_policy(&self.path, root_idx, failure));
+    }
+
+    /// Finishes exploring the current path, which is rejected
+    /// for not having any issuer if nothing else is recorded
+    pub(crate) fn explored(&mut self, has_issuer: bool) {
+        if !has_issuer && self.report.rejected.len() == self.path_start {
+            self.push(PathFailure::NoIssuer);
+        }
+    }
+
+    pub(crate) fn finish(mut self, valid: bool) -> ValidationReport {
+        self.report.valid = valid;
+        self.report
+    }
+
+    fn push(&mut self, failure: PathFailure) {
+        let depth = Some(self.path.len() - 1);
+        self.report
+            .rejected
+            .push(PathReport::new(&self.path, failure, depth));
+    }
+}
+
+impl fmt::Display for CertRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CertRef::Bundle(i) => write!(f, "chain cert {}", i),
+            CertRef::Root(i) => write!(f, "root cert {}", i),
+        }
+    }
+}
+
+impl fmt::Display for PathReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "path {:?}", self.path)?;
+
+        match &self.failure {
+            PathFailure::NoIssuer => write!(f, ": no issuer found")?,
+            PathFailure::BadSignature { issuer } => {
+                write!(f, ": signature from {} does not verify", issuer)?
+            }
+            PathFailure::Policy {
+                root_idx,
+                predicate,
+            } => write!(f, " w/ root {}: {} failed", root_idx, predicate)?,
+        }
+
+        match (&self.cert, &self.depth) {
+            (Some(cert), Some(depth)) => write!(f, " on {} (depth {})", cert, depth),
+            _ => write!(f, " on the entire chain"),
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} ({} rejected path(s))",
+            if self.valid { "valid" } else { "invalid" },
+            self.rejected.len()
+        )?;
+
+        for path in &self.rejected {
+            writeln!(f, "  {}", path)?;
+        }
+
+        Ok(())
+    }
+}
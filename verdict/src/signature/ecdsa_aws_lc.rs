@@ -20,6 +20,13 @@ pub uninterp spec fn spec_p384_verify(
     msg: Seq<u8>,
 ) -> bool;
 
+pub uninterp spec fn spec_p521_verify(
+    alg: SpecAlgorithmIdentifierValue,
+    pub_key: Seq<u8>,
+    sig: Seq<u8>,
+    msg: Seq<u8>,
+) -> bool;
+
 pub enum ECDSAError {
     UnsupportedAlgorithm,
     VerificationFailed,
@@ -79,4 +86,33 @@ pub fn p384_verify(
     }
 }
 
+/// Verify ECDSA P-521 signature with SHA-256/SHA-384/SHA-512 through AWS-LC
+/// (not verified)
+#[verifier::external_body]
+pub fn p521_verify(
+    alg: &AlgorithmIdentifierValue<'_>,
+    pub_key: &[u8],
+    sig: &[u8],
+    msg: &[u8],
+) -> (res: Result<(), ECDSAError>)
+    ensures
+        res.is_ok() == spec_p521_verify(alg@, pub_key@, sig@, msg@),
+{
+    let scheme = if alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA256)) {
+        &aws_lc_rs::signature::ECDSA_P521_SHA256_ASN1
+    } else if alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA384)) {
+        &aws_lc_rs::signature::ECDSA_P521_SHA384_ASN1
+    } else if alg.id.polyfill_eq(&oid!(ECDSA_SIGNATURE_SHA512)) {
+        &aws_lc_rs::signature::ECDSA_P521_SHA512_ASN1
+    } else {
+        return Err(ECDSAError::UnsupportedAlgorithm);
+    };
+
+    if scheme.verify_sig(pub_key, msg, sig).is_ok() {
+        Ok(())
+    } else {
+        Err(ECDSAError::VerificationFailed)
+    }
+}
+
 }
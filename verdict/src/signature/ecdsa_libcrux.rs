@@ -25,10 +25,21 @@ pub uninterp spec fn spec_p384_verify(
     msg: Seq<u8>,
 ) -> bool;
 
+pub uninterp spec fn spec_p521_verify(
+    alg: SpecAlgorithmIdentifierValue,
+    pub_key: Seq<u8>,
+    sig: Seq<u8>,
+    msg: Seq<u8>,
+) -> bool;
+
 pub enum ECDSAError {
     InvalidSignature,
     UnsupportedAlgorithm,
     VerificationFailed,
+
+    /// No verified implementation is available
+    /// (and `verified-crypto` does not fall back to AWS-LC)
+    NoVerifiedBackend,
 }
 
 #[verifier::external_type_specification]
@@ -144,4 +155,20 @@ pub fn p384_verify(
     }
 }
 
+/// ECDSA P-521 is not supported with `verified-crypto`,
+/// since neither libcrux nor AWS-LC has a verified P-521
+/// implementation; all P-521 signatures are rejected
+#[verifier::external_body]
+pub fn p521_verify(
+    alg: &AlgorithmIdentifierValue<'_>,
+    pub_key: &[u8],
+    sig: &[u8],
+    msg: &[u8],
+) -> (res: Result<(), ECDSAError>)
+    ensures
+        res.is_ok() == spec_p521_verify(alg@, pub_key@, sig@, msg@),
+{
+    Err(ECDSAError::NoVerifiedBackend)
+}
+
 }
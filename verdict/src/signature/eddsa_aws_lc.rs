@@ -0,0 +1,36 @@
+use aws_lc_rs::signature::VerificationAlgorithm;
+use vstd::prelude::*;
+
+verus! {
+
+pub uninterp spec fn spec_ed25519_verify(
+    pub_key: Seq<u8>,
+    sig: Seq<u8>,
+    msg: Seq<u8>,
+) -> bool;
+
+pub enum EdDSAError {
+    VerificationFailed,
+}
+
+/// Verify Ed25519 signature (RFC 8032) through AWS-LC
+///
+/// `pub_key` is the raw 32-byte public key (RFC 8410),
+/// and `sig` is the raw 64-byte signature
+#[verifier::external_body]
+pub fn ed25519_verify(
+    pub_key: &[u8],
+    sig: &[u8],
+    msg: &[u8],
+) -> (res: Result<(), EdDSAError>)
+    ensures
+        res.is_ok() == spec_ed25519_verify(pub_key@, sig@, msg@),
+{
+    if aws_lc_rs::signature::ED25519.verify_sig(pub_key, msg, sig).is_ok() {
+        Ok(())
+    } else {
+        Err(EdDSAError::VerificationFailed)
+    }
+}
+
+}
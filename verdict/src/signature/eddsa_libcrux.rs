@@ -0,0 +1,55 @@
+use vstd::prelude::*;
+
+use libcrux::signature::{verify, Ed25519Signature, Signature};
+
+verus! {
+
+pub uninterp spec fn spec_ed25519_verify(
+    pub_key: Seq<u8>,
+    sig: Seq<u8>,
+    msg: Seq<u8>,
+) -> bool;
+
+pub enum EdDSAError {
+    InvalidSignature,
+    VerificationFailed,
+}
+
+#[verifier::external_body]
+#[inline(always)]
+fn ed25519_verify_internal(
+    pub_key: &[u8],
+    sig: &[u8],
+    msg: &[u8],
+) -> Result<(), EdDSAError>
+{
+    let sig = Ed25519Signature::from_slice(sig).or(Err(EdDSAError::InvalidSignature))?;
+
+    if verify(msg, &Signature::Ed25519(sig), pub_key).is_ok() {
+        Ok(())
+    } else {
+        Err(EdDSAError::VerificationFailed)
+    }
+}
+
+/// Verify Ed25519 signature (RFC 8032) through libcrux/HACL*
+///
+/// `pub_key` is the raw 32-byte public key (RFC 8410),
+/// and `sig` is the raw 64-byte signature
+#[verifier::external_body]
+pub fn ed25519_verify(
+    pub_key: &[u8],
+    sig: &[u8],
+    msg: &[u8],
+) -> (res: Result<(), EdDSAError>)
+    ensures
+        res.is_ok() == spec_ed25519_verify(pub_key@, sig@, msg@),
+{
+    if pub_key.len() != 32 || sig.len() != 64 {
+        return Err(EdDSAError::InvalidSignature);
+    }
+
+    ed25519_verify_internal(pub_key, sig, msg)
+}
+
+}
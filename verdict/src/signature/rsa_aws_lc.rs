@@ -1,9 +1,9 @@
-// RSA PKCS#1 v1.5 signature verification
+// RSA PKCS#1 v1.5 and RSASSA-PSS signature verification
 
 use vstd::prelude::*;
 
 use verdict_parser::x509::*;
-use verdict_parser::PolyfillEq;
+use verdict_parser::{Either, OptionDeep, PolyfillEq};
 
 use aws_lc_rs::signature::VerificationAlgorithm;
 
@@ -24,6 +24,12 @@ pub uninterp spec fn spec_pkcs1_v1_5_verify(
     sig: Seq<u8>,
     msg: Seq<u8>,
 ) -> bool;
+pub uninterp spec fn spec_pss_verify(
+    alg: SpecAlgorithmIdentifierValue,
+    pub_key: RSAPublicKeyInternal,
+    sig: Seq<u8>,
+    msg: Seq<u8>,
+) -> bool;
 
 /// Verify RSA PKCS#1 v1.5 signature
 ///
@@ -69,6 +75,70 @@ pub fn pkcs1_v1_5_verify(
     }
 }
 
+/// Get the hash algorithm and the salt length from RSASSA-PSS parameters
+///
+/// We require the parameters to be present, the hash function of MGF1
+/// to be the same as the message digest, and the trailer field to be 1
+#[verifier::external_body]
+fn pss_params<'a, 'b>(
+    alg: &'b AlgorithmIdentifierValue<'a>,
+) -> Result<(&'b ObjectIdentifierValue, i64), RSAError> {
+    let param = match &alg.param {
+        AlgorithmParamValue::RSASSAPSS(Either::Left(param)) => param,
+        _ => return Err(RSAError::UnsupportedAlgorithm),
+    };
+
+    let (OptionDeep::Some(hash_alg), OptionDeep::Some(mgf)) = (&param.hash_alg, &param.mask_gen_alg) else {
+        // SHA-1 by default
+        return Err(RSAError::UnsupportedAlgorithm);
+    };
+
+    if !mgf.id.polyfill_eq(&oid!(MGF1)) ||
+       !mgf.hash_alg.id.polyfill_eq(&hash_alg.id) ||
+       param.trailer_field != 1 {
+        return Err(RSAError::UnsupportedAlgorithm);
+    }
+
+    Ok((&hash_alg.id, param.salt_len))
+}
+
+/// Verify RSASSA-PSS signature
+///
+/// `alg` should be `id-RSASSA-PSS` with explicit parameters.
+/// AWS-LC only supports SHA-256, SHA-384 and SHA-512 with the
+/// salt length equal to the digest length, and MGF1 using the
+/// same hash function.
+///
+/// `pub_key`, `sig` and `msg` are the same as in `pkcs1_v1_5_verify`
+#[verifier::external_body]
+pub fn pss_verify(
+    alg: &AlgorithmIdentifierValue<'_>,
+    pub_key: &RSAPublicKeyInternal,
+    sig: &[u8],
+    msg: &[u8],
+) -> (res: Result<(), RSAError>)
+    ensures
+        res.is_ok() == spec_pss_verify(alg@, *pub_key, sig@, msg@),
+{
+    let (hash, salt_len) = pss_params(alg)?;
+
+    let scheme = if hash.polyfill_eq(&oid!(HASH_SHA256)) && salt_len == 32 {
+        &aws_lc_rs::signature::RSA_PSS_2048_8192_SHA256
+    } else if hash.polyfill_eq(&oid!(HASH_SHA384)) && salt_len == 48 {
+        &aws_lc_rs::signature::RSA_PSS_2048_8192_SHA384
+    } else if hash.polyfill_eq(&oid!(HASH_SHA512)) && salt_len == 64 {
+        &aws_lc_rs::signature::RSA_PSS_2048_8192_SHA512
+    } else {
+        return Err(RSAError::UnsupportedAlgorithm);
+    };
+
+    if scheme.verify_sig(&pub_key.0, msg, sig).is_ok() {
+        Ok(())
+    } else {
+        Err(RSAError::SignatureError)
+    }
+}
+
 #[verifier::external_body]
 pub fn pkcs1_v1_5_load_pub_key(pub_key: &[u8]) -> (res: Result<RSAPublicKeyInternal, RSAError>)
     ensures
@@ -1,4 +1,4 @@
-// RSA PKCS#1 v1.5 signature verification
+// RSA PKCS#1 v1.5 and RSASSA-PSS signature verification
 
 use vstd::prelude::*;
 
@@ -7,7 +7,7 @@ use verdict_polyfill::{slice_drop_first, slice_eq, slice_skip, usize_into_u32};
 use verdict_parser::asn1::ASN1;
 use verdict_parser::x509::*;
 use verdict_parser::Combinator;
-use verdict_parser::PolyfillEq;
+use verdict_parser::{Either, OptionDeep, PolyfillEq};
 
 verus! {
 
@@ -20,6 +20,7 @@ pub enum RSAError {
     AlgorithmMismatch,
     UnsupportedAlgorithm,
     HashMismatch,
+    PSSVerifyError,
 }
 
 pub struct RSAPublicKeyInternal {
@@ -35,6 +36,12 @@ pub uninterp spec fn spec_pkcs1_v1_5_verify(
     sig: Seq<u8>,
     msg: Seq<u8>,
 ) -> bool;
+pub uninterp spec fn spec_pss_verify(
+    alg: SpecAlgorithmIdentifierValue,
+    pub_key: RSAPublicKeyInternal,
+    sig: Seq<u8>,
+    msg: Seq<u8>,
+) -> bool;
 
 impl Drop for RSAPublicKeyInternal {
     #[verifier::external_body]
@@ -114,6 +121,35 @@ fn hacl_rsa_decrypt(
     }
 }
 
+#[verifier::external_body]
+#[inline(always)]
+fn hacl_rsapss_verify(
+    hash_alg: u8,
+    mod_bits: u32,
+    e_bits: u32,
+    pkey: *mut u64,
+    salt_len: u32,
+    sig_len: u32,
+    sig: &[u8],
+    msg_len: u32,
+    msg: &[u8],
+) -> bool
+{
+    unsafe {
+        libcrux_hacl::Hacl_RSAPSS_rsapss_verify(
+            hash_alg as _,
+            mod_bits,
+            e_bits,
+            pkey,
+            salt_len,
+            sig_len,
+            sig.as_ptr() as _,
+            msg_len,
+            msg.as_ptr() as _,
+        )
+    }
+}
+
 /// Verify RSA PKCS#1 v1.5 signature
 ///
 /// `alg` specifies the signature + digest combination to be used
@@ -216,6 +252,84 @@ pub fn pkcs1_v1_5_verify(
     Ok(())
 }
 
+/// Get the hash algorithm and the salt length from RSASSA-PSS parameters
+///
+/// We require the parameters to be present, the hash function of MGF1
+/// to be the same as the message digest, and the trailer field to be 1
+#[verifier::external_body]
+fn pss_params<'a, 'b>(
+    alg: &'b AlgorithmIdentifierValue<'a>,
+) -> Result<(&'b ObjectIdentifierValue, i64), RSAError> {
+    let param = match &alg.param {
+        AlgorithmParamValue::RSASSAPSS(Either::Left(param)) => param,
+        _ => return Err(RSAError::AlgorithmMismatch),
+    };
+
+    let (OptionDeep::Some(hash_alg), OptionDeep::Some(mgf)) = (&param.hash_alg, &param.mask_gen_alg) else {
+        // SHA-1 by default
+        return Err(RSAError::UnsupportedAlgorithm);
+    };
+
+    if !mgf.id.polyfill_eq(&oid!(MGF1)) ||
+       !mgf.hash_alg.id.polyfill_eq(&hash_alg.id) ||
+       param.trailer_field != 1 {
+        return Err(RSAError::UnsupportedAlgorithm);
+    }
+
+    Ok((&hash_alg.id, param.salt_len))
+}
+
+/// Verify RSASSA-PSS signature
+///
+/// `alg` should be `id-RSASSA-PSS` with explicit parameters,
+/// using SHA-256, SHA-384 or SHA-512 for both the message digest
+/// and MGF1. Any salt length is accepted.
+///
+/// `pub_key`, `sig` and `msg` are the same as in `pkcs1_v1_5_verify`
+#[verifier::external_body]
+pub fn pss_verify(
+    alg: &AlgorithmIdentifierValue<'_>,
+    pub_key: &RSAPublicKeyInternal,
+    sig: &[u8],
+    msg: &[u8],
+) -> (res: Result<(), RSAError>)
+    ensures
+        res.is_ok() == spec_pss_verify(alg@, *pub_key, sig@, msg@),
+{
+    let (hash, salt_len) = pss_params(alg)?;
+
+    let hash_alg = if hash.polyfill_eq(&oid!(HASH_SHA256)) {
+        libcrux_hacl::Spec_Hash_Definitions_SHA2_256
+    } else if hash.polyfill_eq(&oid!(HASH_SHA384)) {
+        libcrux_hacl::Spec_Hash_Definitions_SHA2_384
+    } else if hash.polyfill_eq(&oid!(HASH_SHA512)) {
+        libcrux_hacl::Spec_Hash_Definitions_SHA2_512
+    } else {
+        return Err(RSAError::UnsupportedAlgorithm);
+    };
+
+    if salt_len < 0 || salt_len > u32::MAX as i64 ||
+       sig.len() > u32::MAX as usize || msg.len() > u32::MAX as usize {
+        return Err(RSAError::SizeOverflow);
+    }
+
+    if hacl_rsapss_verify(
+        hash_alg as u8,
+        pub_key.n_bits,
+        pub_key.e_bits,
+        pub_key.key,
+        salt_len as u32,
+        sig.len() as u32,
+        sig,
+        msg.len() as u32,
+        msg,
+    ) {
+        Ok(())
+    } else {
+        Err(RSAError::PSSVerifyError)
+    }
+}
+
 #[verifier::external_body]
 pub fn pkcs1_v1_5_load_pub_key(pub_key: &[u8]) -> (res: Result<RSAPublicKeyInternal, RSAError>)
     ensures
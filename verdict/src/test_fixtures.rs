@@ -0,0 +1,30 @@
+//! Fixtures shared by the unit tests of the public API:
+//! `tests/roots.pem` and the chain of google.com in `tests/chains/google.pem`
+
+use crate::api::{ChromePolicy, ParsedCertificate, RootStore, Validator};
+
+/// A time at which every certificate in `google_chain` is valid
+pub const TIMESTAMP: u64 = 1725029869;
+
+/// The leaf (for "google.com" and "*.google.com"), WR2,
+/// and GTS Root R1 cross-signed by GlobalSign
+pub fn google_chain() -> Vec<ParsedCertificate> {
+    ParsedCertificate::from_pem(include_str!("../tests/chains/google.pem").as_bytes()).unwrap()
+}
+
+/// Same as `google_chain`, but in DER
+pub fn google_chain_der() -> Vec<Vec<u8>> {
+    google_chain()
+        .iter()
+        .map(|cert| cert.der().to_vec())
+        .collect()
+}
+
+pub fn test_roots() -> RootStore {
+    RootStore::from_pem(include_str!("../tests/roots.pem").as_bytes()).unwrap()
+}
+
+/// A validator with the default Chrome policy and `test_roots`
+pub fn chrome_validator() -> Validator<'static> {
+    Validator::from_roots(ChromePolicy::default(), &test_roots()).unwrap()
+}
@@ -1,5 +1,6 @@
 //! Some (unverified) utility functions, such as decoding PEM to Base64
 
+use std::collections::HashMap;
 use std::io::BufRead;
 use thiserror::Error;
 
@@ -19,9 +20,25 @@ pub enum PEMParseError {
 pub fn read_pem_as_base64<B: BufRead>(
     reader: B,
 ) -> impl Iterator<Item = Result<String, PEMParseError>> {
-    const PREFIX: &'static str = "-----BEGIN CERTIFICATE-----";
-    const SUFFIX: &'static str = "-----END CERTIFICATE-----";
+    read_pem_blocks_as_base64(
+        reader,
+        "-----BEGIN CERTIFICATE-----",
+        "-----END CERTIFICATE-----",
+    )
+}
+
+/// Same as [`read_pem_as_base64`], but for CRLs (`X509 CRL` blocks)
+pub fn read_pem_crls_as_base64<B: BufRead>(
+    reader: B,
+) -> impl Iterator<Item = Result<String, PEMParseError>> {
+    read_pem_blocks_as_base64(reader, "-----BEGIN X509 CRL-----", "-----END X509 CRL-----")
+}
 
+fn read_pem_blocks_as_base64<B: BufRead>(
+    reader: B,
+    prefix: &'static str,
+    suffix: &'static str,
+) -> impl Iterator<Item = Result<String, PEMParseError>> {
     let mut cur_cert_base64 = None;
 
     reader.lines().filter_map(move |line| {
@@ -29,14 +46,14 @@ pub fn read_pem_as_base64<B: BufRead>(
             let line = line?;
             let line_trimmed = line.trim();
 
-            if line_trimmed == PREFIX {
+            if line_trimmed == prefix {
                 if cur_cert_base64.is_some() {
                     Err(PEMParseError::NoMatchingEndCertificate)
                 } else {
                     cur_cert_base64 = Some(String::new());
                     Ok(None)
                 }
-            } else if line_trimmed == SUFFIX {
+            } else if line_trimmed == suffix {
                 match cur_cert_base64.take() {
                     // Found some base64 chunk
                     Some(cert_base64) => Ok(Some(cert_base64)),
@@ -46,7 +63,7 @@ pub fn read_pem_as_base64<B: BufRead>(
                 cur_cert_base64.push_str(line_trimmed);
                 Ok(None)
             } else {
-                // Ignore lines between SUFFIX and the next PREFIX
+                // Ignore lines between the suffix and the next prefix
                 Ok(None)
             }
         };
@@ -58,3 +75,263 @@ pub fn read_pem_as_base64<B: BufRead>(
         }
     })
 }
+
+#[derive(Debug, Error)]
+pub enum CertDataParseError {
+    #[error("found MULTILINE_OCTAL without matching END")]
+    NoMatchingEnd,
+
+    #[error("invalid attribute line: {0}")]
+    InvalidAttribute(String),
+
+    #[error("invalid octal escape: {0}")]
+    InvalidOctal(String),
+
+    #[error("certificate object without {0}")]
+    MissingAttribute(&'static str),
+
+    #[error("invalid distrust-after date")]
+    InvalidDistrustAfter,
+
+    #[error("IO error: {0}")]
+    IOError(#[from] std::io::Error),
+}
+
+/// A trusted root in NSS certdata.txt, with the trust
+/// settings from the matching CKO_NSS_TRUST object
+#[derive(Debug)]
+pub struct CertDataRoot {
+    pub der: Vec<u8>,
+    pub server_auth: bool,
+    pub email_protection: bool,
+    pub code_signing: bool,
+    pub server_distrust_after: Option<u64>,
+    pub email_distrust_after: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum CertDataValue {
+    Token(String),
+    Octal(Vec<u8>),
+}
+
+type CertDataObject = HashMap<String, CertDataValue>;
+
+/// Reads the certificates in NSS certdata.txt that are trust anchors
+/// (CKT_NSS_TRUSTED_DELEGATOR) for at least one purpose.
+/// Certificates without a trust object, or only with distrust
+/// entries (e.g. CKT_NSS_NOT_TRUSTED), are skipped.
+pub fn read_certdata<B: BufRead>(reader: B) -> Result<Vec<CertDataRoot>, CertDataParseError> {
+    let objects = read_certdata_objects(reader)?;
+
+    let class_of = |obj: &CertDataObject| match obj.get("CKA_CLASS") {
+        Some(CertDataValue::Token(class)) => class.clone(),
+        _ => String::new(),
+    };
+
+    let trusts = objects
+        .iter()
+        .filter(|obj| class_of(obj) == "CKO_NSS_TRUST")
+        .collect::<Vec<_>>();
+
+    let mut roots = Vec::new();
+
+    for cert in objects.iter().filter(|obj| class_of(obj) == "CKO_CERTIFICATE") {
+        let Some(CertDataValue::Octal(der)) = cert.get("CKA_VALUE") else {
+            return Err(CertDataParseError::MissingAttribute("CKA_VALUE"));
+        };
+        let issuer = cert
+            .get("CKA_ISSUER")
+            .ok_or(CertDataParseError::MissingAttribute("CKA_ISSUER"))?;
+        let serial = cert
+            .get("CKA_SERIAL_NUMBER")
+            .ok_or(CertDataParseError::MissingAttribute("CKA_SERIAL_NUMBER"))?;
+
+        // Trust objects refer to certificates by issuer and serial number
+        let Some(trust) = trusts.iter().find(|trust| {
+            trust.get("CKA_ISSUER") == Some(issuer)
+                && trust.get("CKA_SERIAL_NUMBER") == Some(serial)
+        }) else {
+            continue;
+        };
+
+        let is_delegator = |attr: &str| {
+            trust.get(attr)
+                == Some(&CertDataValue::Token("CKT_NSS_TRUSTED_DELEGATOR".to_string()))
+        };
+
+        let root = CertDataRoot {
+            der: der.clone(),
+            server_auth: is_delegator("CKA_TRUST_SERVER_AUTH"),
+            email_protection: is_delegator("CKA_TRUST_EMAIL_PROTECTION"),
+            code_signing: is_delegator("CKA_TRUST_CODE_SIGNING"),
+            server_distrust_after: read_distrust_after(cert.get("CKA_NSS_SERVER_DISTRUST_AFTER"))?,
+            email_distrust_after: read_distrust_after(cert.get("CKA_NSS_EMAIL_DISTRUST_AFTER"))?,
+        };
+
+        if root.server_auth || root.email_protection || root.code_signing {
+            roots.push(root);
+        }
+    }
+
+    Ok(roots)
+}
+
+/// Splits certdata.txt into objects, each starting with a CKA_CLASS attribute
+fn read_certdata_objects<B: BufRead>(reader: B) -> Result<Vec<CertDataObject>, CertDataParseError> {
+    let mut objects: Vec<CertDataObject> = Vec::new();
+    let mut lines = reader.lines();
+
+    while let Some(line) = lines.next() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') || line == "BEGINDATA" {
+            continue;
+        }
+
+        // Each attribute is of the form <name> <type> [<value>]
+        let mut parts = line.splitn(3, char::is_whitespace);
+        let (Some(name), Some(typ)) = (parts.next(), parts.next()) else {
+            return Err(CertDataParseError::InvalidAttribute(line.to_string()));
+        };
+
+        let value = if typ == "MULTILINE_OCTAL" {
+            let mut bytes = Vec::new();
+            loop {
+                let line = lines.next().ok_or(CertDataParseError::NoMatchingEnd)??;
+                let line = line.trim();
+                if line == "END" {
+                    break;
+                }
+                decode_octal_line(line, &mut bytes)?;
+            }
+            CertDataValue::Octal(bytes)
+        } else {
+            CertDataValue::Token(parts.next().unwrap_or("").trim().to_string())
+        };
+
+        if name == "CKA_CLASS" {
+            objects.push(HashMap::new());
+        }
+
+        // Ignore attributes before the first object
+        if let Some(obj) = objects.last_mut() {
+            obj.insert(name.to_string(), value);
+        }
+    }
+
+    Ok(objects)
+}
+
+/// Decodes a line of octal escapes such as `\060\202`
+fn decode_octal_line(line: &str, bytes: &mut Vec<u8>) -> Result<(), CertDataParseError> {
+    for escape in line.split('\\').skip(1) {
+        let byte = u8::from_str_radix(escape, 8)
+            .map_err(|_| CertDataParseError::InvalidOctal(line.to_string()))?;
+        bytes.push(byte);
+    }
+    Ok(())
+}
+
+/// Distrust-after dates are either CK_FALSE or an ASCII UTCTime
+fn read_distrust_after(
+    value: Option<&CertDataValue>,
+) -> Result<Option<u64>, CertDataParseError> {
+    let Some(CertDataValue::Octal(bytes)) = value else {
+        return Ok(None);
+    };
+
+    let time = std::str::from_utf8(bytes).map_err(|_| CertDataParseError::InvalidDistrustAfter)?;
+    let time = chrono::NaiveDateTime::parse_from_str(time, "%y%m%d%H%M%SZ")
+        .map_err(|_| CertDataParseError::InvalidDistrustAfter)?;
+
+    u64::try_from(time.and_utc().timestamp())
+        .map(Some)
+        .map_err(|_| CertDataParseError::InvalidDistrustAfter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CERTDATA: &str = r#"
+# Comments are ignored
+BEGINDATA
+CKA_CLASS CK_OBJECT_CLASS CKO_NSS_BUILTIN_ROOT_LIST
+CKA_LABEL UTF8 "Mozilla Builtin Roots"
+
+CKA_CLASS CK_OBJECT_CLASS CKO_CERTIFICATE
+CKA_LABEL UTF8 "Root 1"
+CKA_VALUE MULTILINE_OCTAL
+\060\003
+\001\002\003
+END
+CKA_ISSUER MULTILINE_OCTAL
+\061
+END
+CKA_SERIAL_NUMBER MULTILINE_OCTAL
+\002\001\001
+END
+CKA_NSS_SERVER_DISTRUST_AFTER MULTILINE_OCTAL
+\062\060\061\061\060\061\060\060\060\060\060\060\132
+END
+CKA_NSS_EMAIL_DISTRUST_AFTER CK_BBOOL CK_FALSE
+
+CKA_CLASS CK_OBJECT_CLASS CKO_NSS_TRUST
+CKA_ISSUER MULTILINE_OCTAL
+\061
+END
+CKA_SERIAL_NUMBER MULTILINE_OCTAL
+\002\001\001
+END
+CKA_TRUST_SERVER_AUTH CK_TRUST CKT_NSS_TRUSTED_DELEGATOR
+CKA_TRUST_EMAIL_PROTECTION CK_TRUST CKT_NSS_MUST_VERIFY_TRUST
+CKA_TRUST_CODE_SIGNING CK_TRUST CKT_NSS_MUST_VERIFY_TRUST
+
+CKA_CLASS CK_OBJECT_CLASS CKO_CERTIFICATE
+CKA_LABEL UTF8 "Distrusted"
+CKA_VALUE MULTILINE_OCTAL
+\060\000
+END
+CKA_ISSUER MULTILINE_OCTAL
+\061
+END
+CKA_SERIAL_NUMBER MULTILINE_OCTAL
+\002\001\002
+END
+
+CKA_CLASS CK_OBJECT_CLASS CKO_NSS_TRUST
+CKA_ISSUER MULTILINE_OCTAL
+\061
+END
+CKA_SERIAL_NUMBER MULTILINE_OCTAL
+\002\001\002
+END
+CKA_TRUST_SERVER_AUTH CK_TRUST CKT_NSS_NOT_TRUSTED
+CKA_TRUST_EMAIL_PROTECTION CK_TRUST CKT_NSS_NOT_TRUSTED
+CKA_TRUST_CODE_SIGNING CK_TRUST CKT_NSS_NOT_TRUSTED
+"#;
+
+    #[test]
+    fn test_read_certdata() {
+        let roots = read_certdata(CERTDATA.as_bytes()).unwrap();
+
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].der, vec![0o60, 3, 1, 2, 3]);
+        assert!(roots[0].server_auth);
+        assert!(!roots[0].email_protection);
+        assert!(!roots[0].code_signing);
+        assert_eq!(roots[0].server_distrust_after, Some(1604188800)); // 2020-11-01
+        assert_eq!(roots[0].email_distrust_after, None);
+    }
+
+    #[test]
+    fn test_read_certdata_unterminated() {
+        let certdata = "CKA_CLASS CK_OBJECT_CLASS CKO_CERTIFICATE\nCKA_VALUE MULTILINE_OCTAL\n\\060\n";
+        assert!(matches!(
+            read_certdata(certdata.as_bytes()),
+            Err(CertDataParseError::NoMatchingEnd)
+        ));
+    }
+}
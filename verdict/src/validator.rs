@@ -1,15 +1,18 @@
 //! High-level specs and impls of chain building and validation
 
+use std::sync::Arc;
 use vstd::prelude::*;
 
 #[allow(unused_imports)]
-use verdict_parser::{asn1::BitStringValue, x509::*, *};
+use verdict_parser::{asn1::{BitStringValue, GeneralizedTimeValue, SpecGeneralizedTimeValue}, x509::*, *};
 #[allow(unused_imports)]
 use verdict_polyfill::*;
 
 use crate::error::*;
+use crate::hash;
 use crate::issue::*;
-use crate::policy::{self, ExecTask, Policy, Task};
+use crate::policy::{self, DiagnosePolicy, ExecTask, Policy, Task};
+use crate::report::*;
 use crate::signature::*;
 
 verus! {
@@ -36,7 +39,11 @@ pub open spec fn spec_validate_x509_base64<P: Policy>(
     Query {
         policy: policy,
         roots: roots,
+        anchors: Seq::new(roots.len(), |_i: int| None),
         bundle: chain,
+        leaf: 0,
+        crls: seq![],
+        ocsp: seq![],
         task: task,
     }.valid()
 }
@@ -68,11 +75,14 @@ pub fn validate_x509_base64<P: Policy>(
     let validator = Validator::from_root_store(policy, &store)?;
     let res = validator.validate_base64(chain_base64, task)?;
 
+    assert(validator.crls@ =~= seq![]);
+
     // Some conversions from deep_view and view
     assert(roots_base64.deep_view() =~~= roots_base64@.map_values(|base64: Vec<u8>| base64@));
     assert(chain_base64.deep_view() =~~= chain_base64@.map_values(|base64: Vec<u8>| base64@));
 
     assert(validator.roots@ =~= roots_base64.deep_view().map_values(|base64: Seq<u8>| spec_parse_x509_base64(base64).unwrap()));
+    assert(validator.roots.anchors() =~= Seq::new(roots_base64.deep_view().len(), |_i: int| None::<policy::TrustAnchor>));
     assert(
         chain_base64@.map_values(|base64: Vec<u8>| spec_parse_x509_base64(base64@).unwrap())
         =~~=
@@ -87,8 +97,19 @@ pub struct Query<P: Policy> {
     pub policy: P,
     pub roots: Seq<SpecCertificateValue>,
 
-    /// `bundle[0]` is the leaf certificate
+    /// Constraints from the root program on each root (`anchors[i]` is about `roots[i]`)
+    pub anchors: Seq<Option<policy::TrustAnchor>>,
+
+    /// `bundle[leaf]` is the leaf certificate, and the rest of
+    /// `bundle` is an unordered pool of intermediate certificates
     pub bundle: Seq<SpecCertificateValue>,
+    pub leaf: usize,
+
+    /// CRLs consulted if `task.check_revocation` is set
+    pub crls: Seq<SpecCertificateListValue>,
+
+    /// Stapled OCSP responses, where `ocsp[i]` (if present) is about `bundle[i]`
+    pub ocsp: Seq<OptionDeep<SpecOCSPResponseValue>>,
 
     /// Hostname validation, chain validation, etc.
     pub task: Task,
@@ -104,12 +125,12 @@ impl<P: Policy> Query<P> {
             policy::Certificate::spec_from(issuer).unwrap(),
             policy::Certificate::spec_from(subject).unwrap(),
         )
-        &&& spec_verify_signature(issuer, subject)
+        &&& spec_verify_signature(spec_issuer_key(issuer), subject)
     }
 
     pub open spec fn is_simple_path(self, path: Seq<usize>) -> bool {
         &&& path.len() != 0
-        &&& path[0] == 0 // starts from the leaf (i.e. `bundle[0]`)
+        &&& path[0] == self.leaf // starts from the leaf (i.e. `bundle[leaf]`)
 
         // `path` contains unique indices into `self.bundle`
         &&& forall |i| 0 <= i < path.len() ==> 0 <= #[trigger] path[i] < self.bundle.len()
@@ -127,15 +148,97 @@ impl<P: Policy> Query<P> {
         &&& Self::issued(self.policy, self.roots[root_idx as int], self.bundle[path.last() as int])
     }
 
+    /// Revoked serials listed in any CRL in `crls` with a valid signature from `issuer`
+    pub open spec fn spec_issuer_revocations(crls: Seq<SpecCertificateListValue>, issuer: SpecCertificateValue) -> Seq<policy::RevokedSerial>
+        decreases crls.len()
+    {
+        if crls.len() == 0 {
+            seq![]
+        } else {
+            Self::spec_issuer_revocations(crls.drop_last(), issuer) + spec_crl_revocations(issuer, crls.last())
+        }
+    }
+
+    /// Revoked serials issued by any non-leaf certificate in `candidate`
+    pub open spec fn spec_chain_revocations(crls: Seq<SpecCertificateListValue>, candidate: Seq<SpecCertificateValue>) -> Seq<policy::RevokedSerial>
+        decreases candidate.len()
+    {
+        if candidate.len() <= 1 {
+            seq![]
+        } else {
+            Self::spec_chain_revocations(crls, candidate.drop_last()) + Self::spec_issuer_revocations(crls, candidate.last())
+        }
+    }
+
+    /// The stapled OCSP response for `bundle[idx]`, if any
+    pub open spec fn spec_stapled_ocsp(self, idx: usize) -> OptionDeep<SpecOCSPResponseValue> {
+        if idx < self.ocsp.len() {
+            self.ocsp[idx as int]
+        } else {
+            OptionDeep::None
+        }
+    }
+
+    /// OCSP statuses from the responses stapled to each certificate
+    /// in `path`, signed by (or on behalf of) its issuer in `candidate`
+    pub open spec fn spec_path_ocsp_results(self, path: Seq<usize>, candidate: Seq<SpecCertificateValue>) -> Seq<policy::OCSPResult>
+        decreases path.len()
+    {
+        if path.len() == 0 {
+            seq![]
+        } else {
+            let prev = self.spec_path_ocsp_results(path.drop_last(), candidate);
+
+            match self.spec_stapled_ocsp(path.last()) {
+                OptionDeep::Some(resp) => prev + spec_ocsp_results(candidate[path.len() as int], resp, self.task.now),
+                OptionDeep::None => prev,
+            }
+        }
+    }
+
+    pub proof fn lemma_no_stapled_ocsp(self, path: Seq<usize>, candidate: Seq<SpecCertificateValue>)
+        requires self.ocsp.len() == 0
+        ensures self.spec_path_ocsp_results(path, candidate) == Seq::<policy::OCSPResult>::empty()
+        decreases path.len()
+    {
+        if path.len() != 0 {
+            self.lemma_no_stapled_ocsp(path.drop_last(), candidate);
+        }
+    }
+
+    /// The task passed to the policy for a candidate chain:
+    /// if revocation checking is enabled, CRL entries from
+    /// issuers in the candidate chain are added to `task.revoked`;
+    /// statuses from stapled OCSP responses are added to `task.ocsp`
+    pub open spec fn task_for_candidate(self, path: Seq<usize>, candidate: Seq<SpecCertificateValue>) -> Task {
+        Task {
+            revoked: if self.task.check_revocation {
+                self.task.revoked + Self::spec_chain_revocations(self.crls, candidate)
+            } else {
+                self.task.revoked
+            },
+            ocsp: self.task.ocsp + self.spec_path_ocsp_results(path, candidate),
+            ..self.task
+        }
+    }
+
+    /// Abstract representation of a root certificate passed to the policy,
+    /// which carries its trust anchor (certificates in the bundle do not)
+    pub open spec fn spec_root_abs(self, root_idx: usize) -> policy::Certificate {
+        Roots::spec_anchored(self.roots[root_idx as int], self.anchors[root_idx as int])
+    }
+
     /// Check if the candidate chain satisfies the policy constraints
     pub open spec fn path_satisfies_policy(self, path: Seq<usize>, root_idx: usize) -> bool {
         let candidate = path.map_values(|i| self.bundle[i as int]) + seq![self.roots[root_idx as int]];
-        let abstract_candidate = candidate.map_values(|cert| policy::Certificate::spec_from(cert).unwrap());
-        self.policy.spec_valid_chain(abstract_candidate, self.task)
+        let abstract_candidate =
+            path.map_values(|i| policy::Certificate::spec_from(self.bundle[i as int]).unwrap()) +
+            seq![self.spec_root_abs(root_idx)];
+        self.policy.spec_valid_chain(abstract_candidate, self.task_for_candidate(path, candidate))
     }
 
     pub open spec fn valid(self) -> bool {
-        &&& self.bundle.len() != 0
+        &&& 0 <= self.leaf < self.bundle.len()
         &&& exists |path: Seq<usize>, root_idx: usize| {
             &&& self.is_simple_path_to_root(path, root_idx)
             &&& self.path_satisfies_policy(path, root_idx)
@@ -143,31 +246,352 @@ impl<P: Policy> Query<P> {
     }
 }
 
+/// `bundle[leaf]` is the only certificate in `bundle` that can be
+/// the leaf for `task` (see [`policy::is_leaf_candidate`])
+pub open spec fn spec_is_unique_leaf(bundle: Seq<SpecCertificateValue>, task: Task, leaf: usize) -> bool {
+    &&& 0 <= leaf < bundle.len()
+    &&& forall |i| #![trigger bundle[i]] 0 <= i < bundle.len() ==>
+        (policy::is_leaf_candidate(&task, &policy::Certificate::spec_from(bundle[i]).unwrap()) <==> i == leaf)
+}
+
+/// `paths` contains the pair of `path` (indices into the bundle)
+/// and `root_idx` (index of the root completing the chain)
+pub open spec fn spec_contains_path(paths: Seq<(Vec<usize>, usize)>, path: Seq<usize>, root_idx: usize) -> bool {
+    exists |k: int| 0 <= k < paths.len() && (#[trigger] paths[k]).0@ == path && paths[k].1 == root_idx
+}
+
+/// Revoked serials listed in `crl` if it is signed by `issuer`, otherwise empty
+pub open spec fn spec_crl_revocations(issuer: SpecCertificateValue, crl: SpecCertificateListValue) -> Seq<policy::RevokedSerial> {
+    if spec_verify_crl_signature(spec_issuer_key(issuer), crl) {
+        let issuer_fingerprint = policy::Certificate::spec_from(issuer).unwrap().fingerprint;
+
+        match crl.cert_list.revoked_certs {
+            OptionDeep::Some(revoked) => revoked.map_values(|entry: SpecRevokedCertificateValue| policy::RevokedSerial {
+                issuer_fingerprint: issuer_fingerprint,
+                serial: hash::spec_to_hex_upper(entry.serial),
+            }),
+            OptionDeep::None => seq![],
+        }
+    } else {
+        seq![]
+    }
+}
+
+/// The basic response in `resp`, if `resp` is successful
+pub open spec fn spec_basic_ocsp_response(resp: SpecOCSPResponseValue) -> Option<SpecBasicOCSPResponseValue> {
+    if let OptionDeep::Some(bytes) = resp.response_bytes {
+        if resp.status == 0 && bytes.response_type == spec_oid!(OCSP_BASIC) {
+            Some(bytes.response)
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+/// `delegate` is authorized by `issuer` to sign OCSP responses
+/// (RFC 6960, Section 4.2.2.2): it is signed by `issuer`, valid
+/// at `now`, and has the id-kp-OCSPSigning extended key usage
+///
+/// NOTE: only the validity and the extended key usage of `delegate` are
+/// converted, so that malformed fields elsewhere do not abort validation
+pub open spec fn spec_is_ocsp_delegate(issuer: SpecCertificateValue, delegate: SpecCertificateValue, now: u64) -> bool {
+    &&& spec_verify_signature(spec_issuer_key(issuer), delegate)
+    &&& policy::Certificate::spec_time_to_timestamp(delegate.cert.validity.not_before) matches Some(not_before)
+    &&& policy::Certificate::spec_time_to_timestamp(delegate.cert.validity.not_after) matches Some(not_after)
+    &&& not_before <= now <= not_after
+    &&& policy::Certificate::spec_get_extension(delegate, spec_oid!(EXTENDED_KEY_USAGE)) matches Some(ext)
+    &&& policy::ExtendedKeyUsage::spec_from(ext) matches Some(eku)
+    &&& exists |i: int| 0 <= i < eku.usages.len() &&
+        #[trigger] eku.usages[i] == policy::ExtendedKeyUsageType::OCSPSigning
+}
+
+/// `resp` is signed either by `issuer` or by a delegated
+/// responder (issued by `issuer`) included in `resp.certs`
+pub open spec fn spec_ocsp_signed_by(issuer: SpecCertificateValue, resp: SpecBasicOCSPResponseValue, now: u64) -> bool {
+    ||| spec_verify_ocsp_signature(spec_issuer_key(issuer), resp)
+    ||| resp.certs matches OptionDeep::Some(certs) && exists |i: int| 0 <= i < certs.len() && {
+            &&& spec_is_ocsp_delegate(issuer, #[trigger] certs[i], now)
+            &&& spec_verify_ocsp_signature(spec_issuer_key(certs[i]), resp)
+        }
+}
+
+/// `cert_id` refers to a certificate issued by `issuer`, i.e.,
+/// both issuerNameHash and issuerKeyHash match (RFC 6960, Section 4.1.1)
+pub open spec fn spec_cert_id_matches_issuer(issuer: SpecCertificateValue, cert_id: SpecCertIDValue) -> bool {
+    let key = BitStringValue::spec_bytes(issuer.cert.subject_key.pub_key);
+
+    &&& ASN1(Name)@.spec_serialize(issuer.cert.subject) matches Ok(name)
+    &&& if cert_id.hash_alg.id == spec_oid!(HASH_SHA1) {
+        &&& cert_id.issuer_name_hash == hash::spec_sha1_digest(name)
+        &&& cert_id.issuer_key_hash == hash::spec_sha1_digest(key)
+    } else if cert_id.hash_alg.id == spec_oid!(HASH_SHA256) {
+        &&& cert_id.issuer_name_hash == hash::spec_sha256_digest(name)
+        &&& cert_id.issuer_key_hash == hash::spec_sha256_digest(key)
+    } else {
+        false
+    }
+}
+
+pub open spec fn spec_ocsp_status(status: SpecCertStatusValue) -> policy::OCSPStatus {
+    match status {
+        SpecCertStatusValue::Good(..) => policy::OCSPStatus::Good,
+        SpecCertStatusValue::Revoked(..) => policy::OCSPStatus::Revoked,
+        _ => policy::OCSPStatus::Unknown,
+    }
+}
+
+pub open spec fn spec_ocsp_timestamp(time: SpecGeneralizedTimeValue) -> Option<u64> {
+    if let Some(ts) = policy::Certificate::spec_time_to_timestamp(SpecTimeValue::GeneralizedTime(time)) {
+        if ts >= 0 { Some(ts as u64) } else { None }
+    } else {
+        None
+    }
+}
+
+/// The status in `single`, or `None` if any of its times
+/// fails to convert, in which case the response is skipped
+pub open spec fn spec_ocsp_result(issuer: SpecCertificateValue, single: SpecSingleResponseValue) -> Option<policy::OCSPResult> {
+    let next_update = match single.next_update {
+        OptionDeep::Some(next_update) => match spec_ocsp_timestamp(next_update) {
+            Some(ts) => Some(Some(ts)),
+            None => None,
+        },
+        OptionDeep::None => Some(None),
+    };
+
+    if let Some(this_update) = spec_ocsp_timestamp(single.this_update) {
+        if let Some(next_update) = next_update {
+            Some(policy::OCSPResult {
+                issuer_fingerprint: policy::Certificate::spec_from(issuer).unwrap().fingerprint,
+                serial: hash::spec_to_hex_upper(single.cert_id.serial),
+                status: spec_ocsp_status(single.cert_status),
+                this_update: this_update,
+                next_update: next_update,
+            })
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+/// Statuses in `responses` about certificates issued by `issuer`
+pub open spec fn spec_ocsp_issuer_results(issuer: SpecCertificateValue, responses: Seq<SpecSingleResponseValue>) -> Seq<policy::OCSPResult>
+    decreases responses.len()
+{
+    if responses.len() == 0 {
+        seq![]
+    } else {
+        let prev = spec_ocsp_issuer_results(issuer, responses.drop_last());
+
+        if spec_cert_id_matches_issuer(issuer, responses.last().cert_id) {
+            if let Some(res) = spec_ocsp_result(issuer, responses.last()) {
+                prev.push(res)
+            } else {
+                prev
+            }
+        } else {
+            prev
+        }
+    }
+}
+
+/// Statuses in the stapled OCSP response `resp` about certificates issued
+/// by `issuer`, if `resp` is signed by `issuer` (or on behalf of it), otherwise empty
+pub open spec fn spec_ocsp_results(issuer: SpecCertificateValue, resp: SpecOCSPResponseValue, now: u64) -> Seq<policy::OCSPResult> {
+    if let Some(basic) = spec_basic_ocsp_response(resp) {
+        if spec_ocsp_signed_by(issuer, basic, now) {
+            spec_ocsp_issuer_results(issuer, basic.tbs_response_data.responses)
+        } else {
+            seq![]
+        }
+    } else {
+        seq![]
+    }
+}
+
 /// The internal version of X.509 validator
 pub struct Validator<'a, P: Policy> {
     pub policy: P,
-    pub roots: VecDeep<CertificateValue<'a>>,
 
-    /// Cached RSA public keys of each root certificate
-    pub roots_rsa_cache: Vec<Option<rsa::RSAPublicKeyInternal>>,
+    /// Trusted root certificates, possibly shared with other validators
+    pub roots: Arc<Roots>,
 
-    /// Abstract representation of each root certificate
-    pub roots_abs_cache: Vec<policy::ExecCertificate>,
+    /// CRLs used for revocation checking (empty by default)
+    pub crls: VecDeep<CertificateListValue<'a>>,
+
+    /// Limits on the work of path building (unlimited by default)
+    pub limits: ValidationLimits,
+}
+
+/// Limits on the work of path building in one validation, to bound
+/// the cost of bundles crafted to have many candidate paths.
+/// Exceeding any of them results in [`ValidationError::ResourceLimitExceeded`],
+/// unless a valid path is found first.
+#[derive(Debug, Clone, Copy)]
+pub struct ValidationLimits {
+    /// Maximum number of certificates in a path (excluding the root)
+    pub max_depth: usize,
+
+    /// Maximum number of signature verifications, including those
+    /// against root certificates and of CRLs and OCSP responses
+    pub max_signatures: usize,
+
+    /// Maximum number of paths explored
+    pub max_paths: usize,
+
+    /// Time budget in milliseconds
+    pub time_budget_ms: Option<u64>,
+}
+
+impl ValidationLimits {
+    /// No limits (the default)
+    pub fn unlimited() -> Self {
+        ValidationLimits {
+            max_depth: usize::MAX,
+            max_signatures: usize::MAX,
+            max_paths: usize::MAX,
+            time_budget_ms: None,
+        }
+    }
+}
+
+impl Default for ValidationLimits {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+/// NOTE: a trusted wrapper around [`std::time::Instant`]
+/// (`None` if the time budget overflows)
+#[verifier::external_body]
+struct Deadline(Option<std::time::Instant>);
+
+impl Deadline {
+    #[verifier::external_body]
+    fn after_ms(ms: u64) -> Self {
+        Deadline(std::time::Instant::now().checked_add(std::time::Duration::from_millis(ms)))
+    }
+
+    #[verifier::external_body]
+    fn passed(&self) -> bool {
+        self.0.is_some_and(|deadline| std::time::Instant::now() >= deadline)
+    }
+}
+
+/// NOTE: a trusted wrapper around the remaining signature verifications
+/// and the deadline of [`ValidationLimits`] in one validation, shared
+/// by all signature checks (hence the interior mutability)
+#[verifier::external_body]
+struct Budget {
+    signatures: std::cell::Cell<usize>,
+    deadline: Option<Deadline>,
+}
+
+impl Budget {
+    #[verifier::external_body]
+    fn new(limits: &ValidationLimits) -> Self {
+        Budget {
+            signatures: std::cell::Cell::new(limits.max_signatures),
+            deadline: limits.time_budget_ms.map(Deadline::after_ms),
+        }
+    }
+
+    /// Takes one signature verification from the budget,
+    /// or returns false if it is used up or the deadline has passed
+    #[verifier::external_body]
+    fn take_signature(&self) -> bool {
+        let remaining = self.signatures.get();
+
+        if remaining == 0 || self.deadline_passed() {
+            return false;
+        }
+
+        self.signatures.set(remaining - 1);
+        true
+    }
+
+    #[verifier::external_body]
+    fn deadline_passed(&self) -> bool {
+        self.deadline.as_ref().is_some_and(|deadline| deadline.passed())
+    }
+}
+
+/// NOTE: a trusted wrapper around an optional [`ReportRecorder`],
+/// which records why candidate paths are rejected during path
+/// building without affecting the result (see [`Validator::validate_with_report`])
+#[verifier::external_body]
+pub struct ReportSink<'r>(Option<std::cell::RefCell<ReportRecorder<'r>>>);
+
+impl<'r> ReportSink<'r> {
+    /// A sink that records nothing
+    #[verifier::external_body]
+    pub fn none() -> Self {
+        ReportSink(None)
+    }
+
+    #[verifier::external_body]
+    fn bad_root_signature(&self, root_idx: usize, subject_idx: usize) {
+        if let Some(recorder) = &self.0 {
+            recorder.borrow_mut().bad_root_signature(root_idx, subject_idx);
+        }
+    }
+
+    #[verifier::external_body]
+    fn explore(&self, path: &Vec<usize>) {
+        if let Some(recorder) = &self.0 {
+            recorder.borrow_mut().explore(path);
+        }
+    }
+
+    #[verifier::external_body]
+    fn bad_signature(&self, issuer_idx: usize) {
+        if let Some(recorder) = &self.0 {
+            recorder.borrow_mut().bad_signature(issuer_idx);
+        }
+    }
+
+    #[verifier::external_body]
+    fn policy_rejected(
+        &self,
+        root_idx: usize,
+        chain: &Vec<&policy::ExecCertificate>,
+        task: &ExecTask,
+    ) {
+        if let Some(recorder) = &self.0 {
+            recorder.borrow_mut().policy_rejected(root_idx, chain, task);
+        }
+    }
+
+    #[verifier::external_body]
+    fn explored(&self, has_issuer: bool) {
+        if let Some(recorder) = &self.0 {
+            recorder.borrow_mut().explored(has_issuer);
+        }
+    }
 }
 
 /// Caches within a particular validation job
-struct ValidatorCache<'a, 'b, 'c> {
+struct ValidatorCache<'a, 'b, 'c, 'r> {
     bundle: &'a VecDeep<CertificateValue<'b>>,
+    leaf: usize,
+    ocsp: &'a VecDeep<OptionDeep<OCSPResponseValue<'b>>>,
     task: &'c ExecTask,
 
     /// Cached abstract representation of each certificate
-    bundle_abs_cache: Vec<policy::ExecCertificate>,
+    bundle_abs_cache: Vec<&'a policy::ExecCertificate>,
 
     /// Cached root issuers of each intermediate certificate
     root_issuers: Vec<Vec<usize>>,
+
+    report: &'a ReportSink<'r>,
+    budget: &'a Budget,
 }
 
-impl<'a, 'b, 'c> ValidatorCache<'a, 'b, 'c> {
+impl<'a, 'b, 'c, 'r> ValidatorCache<'a, 'b, 'c, 'r> {
     closed spec fn wf<P: Policy>(&self, validator: &Validator<'_, P>) -> bool {
         &&& Validator::<P>::is_abs_cache(self.bundle@, self.bundle_abs_cache.deep_view())
 
@@ -181,97 +605,97 @@ impl<'a, 'b, 'c> ValidatorCache<'a, 'b, 'c> {
         Query {
             policy: validator.policy,
             roots: validator.roots@,
+            anchors: validator.roots.anchors(),
             bundle: self.bundle@,
+            leaf: self.leaf,
+            crls: validator.crls@,
+            ocsp: self.ocsp@,
             task: self.task.deep_view(),
         }
     }
 }
 
 impl<'a, P: Policy + 'a> Validator<'a, P> {
-    /// Initializes a [`Validator`] from parsed root certificates.
-    #[verifier::loop_isolation(false)]
-    pub fn from_parsed_roots(policy: P, roots: VecDeep<CertificateValue<'a>>) -> (res: Result<Self, ValidationError>)
+    /// Initializes a [`Validator`] from (possibly shared) root certificates.
+    pub fn from_roots(policy: P, roots: Arc<Roots>) -> (res: Self)
         ensures
-            res matches Ok(res) ==> {
-                &&& res.wf()
-                &&& res.policy == policy
-                &&& res.roots == roots
-            }
+            res.wf(),
+            res.policy == policy,
+            res.roots == roots,
+            res.crls@.len() == 0,
     {
-        let roots_len = roots.len();
-        let mut roots_rsa_cache = Vec::with_capacity(roots_len);
-
-        // Initialize the RSA key cache by parsing
-        // the RSA public key of each root certificate
-        for i in 0..roots_len
-            invariant
-                i == roots_rsa_cache@.len(),
-                forall |i| 0 <= i < roots_rsa_cache@.len() ==>
-                    (#[trigger] roots_rsa_cache@[i] matches Some(key) ==> {
-                        let subject_key = roots@[i].cert.subject_key;
-                        &&& subject_key.alg.param is RSAEncryption
-                        &&& rsa::spec_pkcs1_v1_5_load_pub_key(BitStringValue::spec_bytes(subject_key.pub_key)) == Some(key)
-                    })
-        {
-            let root = roots.get(i);
-
-            roots_rsa_cache.push(if let AlgorithmParamValue::RSAEncryption(..) = &root.get().cert.get().subject_key.alg.param {
-                let pub_key = root.get().cert.get().subject_key.pub_key.bytes();
-
-                match rsa::pkcs1_v1_5_load_pub_key(pub_key) {
-                    Ok(pub_key) => Some(pub_key),
-
-                    // NOTE: skip if the pub key of a root certificate fail to parse
-                    Err(..) => None,
-                }
-            } else {
-                None
-            });
-        }
-
-        let roots_abs_cache = Self::get_abs_cache(&roots)?;
-
-        Ok(Validator { policy, roots, roots_rsa_cache, roots_abs_cache })
+        Validator { policy, roots, crls: VecDeep::new(), limits: ValidationLimits::unlimited() }
     }
 
     /// Initializes a [`Validator`] from a [`RootStore`].
-    pub fn from_root_store(policy: P, store: &'a RootStore) -> (res: Result<Self, ValidationError>)
+    pub fn from_root_store(policy: P, store: &RootStore) -> (res: Result<Self, ValidationError>)
         ensures
             res matches Ok(res) ==> {
                 &&& res.wf()
                 &&& res.policy == policy
                 &&& res.roots@ =~= store.roots_der@.map_values(|der: Vec<u8>| spec_parse_x509_der(der@).unwrap())
+                &&& res.roots.anchors() =~= Seq::new(store.roots_der@.len(), |_i: int| None::<policy::TrustAnchor>)
+                &&& res.crls@.len() == 0
             }
     {
-        let roots_len = store.roots_der.len();
-        let mut roots = VecDeep::with_capacity(roots_len);
+        Ok(Self::from_roots(policy, Arc::new(Roots::from_der(&store.roots_der)?)))
+    }
 
-        for i in 0..roots_len
+    /// Replaces the root certificates, keeping the policy and CRLs.
+    pub fn set_roots(&mut self, roots: Arc<Roots>)
+        requires old(self).wf()
+        ensures
+            self.wf(),
+            self.policy == old(self).policy,
+            self.roots == roots,
+            self.crls == old(self).crls,
+    {
+        self.roots = roots;
+    }
+
+    /// Sets the limits on path building (see [`ValidationLimits`])
+    pub fn set_limits(&mut self, limits: ValidationLimits)
+        requires old(self).wf()
+        ensures
+            self.wf(),
+            self.policy == old(self).policy,
+            self.roots == old(self).roots,
+            self.crls == old(self).crls,
+    {
+        self.limits = limits;
+    }
+
+    /// Parses the CRLs in a [`RevocationStore`] and uses them
+    /// for tasks with revocation checking enabled.
+    pub fn set_revocation_store(&mut self, store: &'a RevocationStore) -> (res: Result<(), ValidationError>)
+        requires old(self).wf()
+        ensures
+            self.wf(),
+            self.policy == old(self).policy,
+            self.roots == old(self).roots,
+            res is Ok ==> self.crls@ =~= store.crls_der@.map_values(|der: Vec<u8>| spec_parse_crl_der(der@).unwrap()),
+    {
+        let crls_len = store.crls_der.len();
+        let mut crls = VecDeep::with_capacity(crls_len);
+
+        for i in 0..crls_len
             invariant
-                roots_len == store.roots_der@.len(),
-                i == roots@.len(),
-                forall |i| 0 <= i < roots@.len() ==>
-                    spec_parse_x509_der(store.roots_der@[i]@) == Some(#[trigger] roots@[i]),
+                crls_len == store.crls_der@.len(),
+                i == crls@.len(),
+                forall |i| 0 <= i < crls@.len() ==>
+                    spec_parse_crl_der(store.crls_der@[i]@) == Some(#[trigger] crls@[i]),
         {
-            roots.push(parse_x509_der(store.roots_der[i].as_slice())?);
+            crls.push(parse_crl_der(store.crls_der[i].as_slice())?);
         }
 
-        Self::from_parsed_roots(policy, roots)
+        self.crls = crls;
+        Ok(())
     }
 
     pub closed spec fn wf(self) -> bool {
-        // RSA public key cache is valid
-        &&& self.roots_rsa_cache@.len() == self.roots@.len()
-        &&& forall |i| 0 <= i < self.roots@.len() ==>
-            (#[trigger] self.roots_rsa_cache@[i] matches Some(key) ==> {
-                let subject_key = self.roots@[i].cert.subject_key;
-
-                &&& subject_key.alg.param is RSAEncryption
-                &&& rsa::spec_pkcs1_v1_5_load_pub_key(BitStringValue::spec_bytes(subject_key.pub_key)) == Some(key)
-            })
-
-        // Abstract representation cache is valid
-        &&& Self::is_abs_cache(self.roots@, self.roots_abs_cache.deep_view())
+        // Caches of root certificates are valid
+        // by the type invariant of `Roots`
+        true
     }
 
     closed spec fn is_abs_cache(certs: Seq<SpecCertificateValue>, cache: Seq<policy::Certificate>) -> bool
@@ -312,59 +736,84 @@ impl<'a, P: Policy + 'a> Validator<'a, P> {
         Ok(cache)
     }
 
+    /// Checks the issuing relation between two bundle certificates,
+    /// where each signature verification takes one from `cache.budget`
     fn check_interm_likely_issued(
         &self,
-        cache: &ValidatorCache<'_, '_, '_>,
+        cache: &ValidatorCache<'_, '_, '_, '_>,
         issuer_idx: usize,
         subject_idx: usize,
-    ) -> (res: bool)
+    ) -> (res: Result<bool, ValidationError>)
         requires
             cache.wf(self),
             0 <= issuer_idx < cache.bundle@.len(),
             0 <= subject_idx < cache.bundle@.len(),
 
         ensures
-            res == Query::issued(self.policy, cache.bundle@[issuer_idx as int], cache.bundle@[subject_idx as int]),
+            res matches Ok(res) ==>
+                res == Query::issued(self.policy, cache.bundle@[issuer_idx as int], cache.bundle@[subject_idx as int]),
     {
         reveal(Query::issued);
         let ghost _ = cache.bundle_abs_cache.deep_view()[issuer_idx as int];
         let ghost _ = cache.bundle_abs_cache.deep_view()[subject_idx as int];
 
-        self.policy.likely_issued(&cache.bundle_abs_cache[issuer_idx], &cache.bundle_abs_cache[subject_idx]) &&
-        verify_signature(cache.bundle.get(issuer_idx), cache.bundle.get(subject_idx))
+        if !self.policy.likely_issued(cache.bundle_abs_cache[issuer_idx], cache.bundle_abs_cache[subject_idx]) {
+            return Ok(false);
+        }
+
+        if !cache.budget.take_signature() {
+            return Err(ValidationError::ResourceLimitExceeded);
+        }
+
+        let verified = verify_signature(&IssuerKey::from_cert(cache.bundle.get(issuer_idx)), cache.bundle.get(subject_idx));
+
+        if !verified {
+            cache.report.bad_signature(issuer_idx);
+        }
+
+        Ok(verified)
     }
 
     /// A specialized version of `likely_issued`
-    /// that uses RSA public key cache of root certs
+    /// that uses RSA public key cache of root certs,
+    /// where each signature verification takes one from `budget`
     fn check_root_likely_issued(
         &self,
         bundle: &VecDeep<CertificateValue<'_>>,
-        bundle_abs_cache: &Vec<policy::ExecCertificate>,
+        bundle_abs_cache: &Vec<&policy::ExecCertificate>,
 
         root_idx: usize,
         subject_idx: usize,
-    ) -> (res: bool)
+        report: &ReportSink<'_>,
+        budget: &Budget,
+    ) -> (res: Result<bool, ValidationError>)
         requires
             self.wf(),
             Self::is_abs_cache(bundle@, bundle_abs_cache.deep_view()),
             0 <= root_idx < self.roots@.len(),
             0 <= subject_idx < bundle@.len(),
 
-        ensures res == Query::issued(self.policy, self.roots@[root_idx as int], bundle@[subject_idx as int])
+        ensures
+            res matches Ok(res) ==>
+                res == Query::issued(self.policy, self.roots@[root_idx as int], bundle@[subject_idx as int]),
     {
-        let root = self.roots.get(root_idx);
+        let roots = arc_as_ref(&self.roots);
         let subject = bundle.get(subject_idx);
 
         reveal(Query::issued);
-        let ghost _ = self.roots_abs_cache.deep_view()[root_idx as int];
         let ghost _ = bundle_abs_cache.deep_view()[subject_idx as int];
 
-        if !self.policy.likely_issued(&self.roots_abs_cache[root_idx], &bundle_abs_cache[subject_idx]) {
-            return false;
+        if !self.policy.likely_issued(roots.abs(root_idx), bundle_abs_cache[subject_idx]) {
+            return Ok(false);
+        }
+
+        if !budget.take_signature() {
+            return Err(ValidationError::ResourceLimitExceeded);
         }
 
-        // If we have the RSA public key cache for the root certificate, use it instead
-        if let Some(pub_key) = &self.roots_rsa_cache[root_idx] {
+        // If we have the RSA public key cache for the root certificate,
+        // use it for PKCS#1 v1.5 signatures instead
+        if let Some(pub_key) = roots.rsa_key(root_idx) {
             // Mostly the same as the RSA branch of `verify_signature`
             let tbs_cert = subject.get().cert.serialize();
             let sig_alg = &subject.get().sig_alg.get();
@@ -374,20 +823,33 @@ impl<'a, P: Policy + 'a> Validator<'a, P> {
                sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA256)) ||
                sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA384)) ||
                sig_alg.id.polyfill_eq(&oid!(RSA_SIGNATURE_SHA512)) {
-                return rsa::pkcs1_v1_5_verify(sig_alg, &pub_key, sig, tbs_cert).is_ok();
+                let verified = rsa::pkcs1_v1_5_verify(sig_alg, &pub_key, sig, tbs_cert).is_ok();
+
+                if !verified {
+                    report.bad_root_signature(root_idx, subject_idx);
+                }
+
+                return Ok(verified);
             }
 
-            return false;
+            // Other algorithms (e.g., RSASSA-PSS under an rsaEncryption
+            // root) fall through to the full check below
         }
 
-        verify_signature(root, subject)
+        let verified = verify_signature(&roots.key(root_idx), subject);
+
+        if !verified {
+            report.bad_root_signature(root_idx, subject_idx);
+        }
+
+        Ok(verified)
     }
 
     /// Check if a candidate path satisfies the policy
     #[verifier::loop_isolation(false)]
     fn check_chain_policy(
         &self,
-        cache: &ValidatorCache<'_, '_, '_>,
+        cache: &ValidatorCache<'_, '_, '_, '_>,
         path: &Vec<usize>,
         root_idx: usize,
     ) -> (res: Result<bool, ValidationError>)
@@ -418,77 +880,613 @@ impl<'a, P: Policy + 'a> Validator<'a, P> {
                     Some(candidate.deep_view()[j]) == policy::Certificate::spec_from(cache.bundle@[path@[j] as int]),
         {
             let ghost _ = cache.bundle_abs_cache.deep_view()[path@[i as int] as int];
-            candidate.push(&cache.bundle_abs_cache[path[i]]);
+            candidate.push(cache.bundle_abs_cache[path[i]]);
         }
 
-        // Append the root certificate
-        let ghost _ = self.roots_abs_cache.deep_view()[root_idx as int];
-        candidate.push(&self.roots_abs_cache[root_idx]);
+        // Append the root certificate along with its trust anchor
+        let roots = arc_as_ref(&self.roots);
+        candidate.push(roots.anchored(root_idx));
 
+        let ghost spec_candidate = path@.map_values(|i| cache.bundle@[i as int]) + seq![self.roots@[root_idx as int]];
         assert(candidate.deep_view() =~=
-            (path@.map_values(|i| cache.bundle@[i as int]) + seq![self.roots@[root_idx as int]])
-                .map_values(|cert| policy::Certificate::spec_from(cert).unwrap()));
-
-        Ok(self.policy.valid_chain(&candidate, &cache.task))
-    }
+            path@.map_values(|i| policy::Certificate::spec_from(cache.bundle@[i as int]).unwrap()) +
+            seq![cache.get_query(self).spec_root_abs(root_idx)]);
 
-    /// Given a simple path through the bundle certificates
-    /// and all root issuers of the last certificate in the path,
-    /// check if the entire path satisfies the policy
-    #[verifier::loop_isolation(false)]
-    #[allow(unexpected_cfgs)]
-    fn check_simple_path(
-        &self,
-        cache: &ValidatorCache<'_, '_, '_>,
-        path: &Vec<usize>,
-    ) -> (res: Result<bool, ValidationError>)
-        requires
-            self.wf(),
-            cache.wf(self),
-            cache.get_query(self).is_simple_path(path@),
+        if !cache.task.check_revocation && cache.ocsp.len() == 0 {
+            proof { cache.get_query(self).lemma_no_stapled_ocsp(path@, spec_candidate); }
+            assert(cache.task.deep_view() =~~= cache.get_query(self).task_for_candidate(path@, spec_candidate));
 
-        ensures
-            res matches Ok(res) ==>
-                res == exists |root_idx: usize|
-                    #[trigger] cache.get_query(self).is_simple_path_to_root(path@, root_idx) &&
-                    cache.get_query(self).path_satisfies_policy(path@, root_idx)
-    {
-        reveal(Validator::spec_root_issuers);
+            let valid = self.policy.valid_chain(&candidate, &cache.task);
+            if !valid {
+                cache.report.policy_rejected(root_idx, &candidate, &cache.task);
+            }
 
-        let last = path[path.len() - 1];
+            return Ok(valid);
+        }
 
-        let root_issuers = &cache.root_issuers[last];
-        let root_issuers_len = root_issuers.len();
         let ghost query = cache.get_query(self);
+        let mut task = cache.task.clone();
+
+        if cache.task.check_revocation {
+            // Collect revoked serials from CRLs issued by
+            // any non-leaf certificate in the candidate chain
+            assert(spec_candidate.take(1).drop_last() =~= seq![]);
+
+            for i in 1..path_len
+                invariant
+                    path_len == path@.len(),
+                    cache.get_query(self).is_simple_path_to_root(path@, root_idx),
+                    task.deep_view() == (policy::Task {
+                        revoked: cache.task.deep_view().revoked +
+                            Query::<P>::spec_chain_revocations(self.crls@, spec_candidate.take(i as int)),
+                        ..cache.task.deep_view()
+                    }),
+            {
+                let ghost _ = cache.bundle_abs_cache.deep_view()[path@[i as int] as int];
+                let ghost prev_revoked = task.revoked.deep_view();
+
+                self.issuer_revocations(
+                    &IssuerKey::from_cert(cache.bundle.get(path[i])), cache.bundle_abs_cache[path[i]],
+                    Ghost(cache.bundle@[path@[i as int] as int]), cache.budget, &mut task.revoked,
+                )?;
+
+                assert(spec_candidate.take(i + 1).drop_last() =~= spec_candidate.take(i as int));
+                assert(task.revoked.deep_view() =~= prev_revoked +
+                    Query::<P>::spec_issuer_revocations(self.crls@, spec_candidate.take(i + 1).last()));
+            }
 
-        for i in 0..root_issuers_len
+            // CRLs issued by the root
+            self.issuer_revocations(
+                &roots.key(root_idx), roots.abs(root_idx),
+                Ghost(self.roots@[root_idx as int]), cache.budget, &mut task.revoked,
+            )?;
+
+            assert(spec_candidate.take(spec_candidate.len() as int) =~= spec_candidate);
+            assert(spec_candidate.drop_last() =~= spec_candidate.take(path_len as int));
+        }
+
+        assert(task.deep_view() =~~= (policy::Task {
+            revoked: query.task_for_candidate(path@, spec_candidate).revoked,
+            ..cache.task.deep_view()
+        }));
+
+        // Collect OCSP statuses from the response stapled to each
+        // certificate in the path, if signed by its issuer in the chain
+        assert(path@.take(0) =~= seq![]);
+
+        for i in 0..path_len
             invariant
-                forall |j| 0 <= j < i ==>
-                    !query.path_satisfies_policy(path@, #[trigger] root_issuers@[j]),
+                path_len == path@.len(),
+                query == cache.get_query(self),
+                query.is_simple_path_to_root(path@, root_idx),
+                task.deep_view() == (policy::Task {
+                    revoked: query.task_for_candidate(path@, spec_candidate).revoked,
+                    ocsp: cache.task.deep_view().ocsp + query.spec_path_ocsp_results(path@.take(i as int), spec_candidate),
+                    ..cache.task.deep_view()
+                }),
         {
-            #[cfg(feature = "trace")]
-            eprintln_join!("checking path: ", format_dbg(path), " w/ root ", root_issuers[i]);
+            let ghost prev_ocsp = task.ocsp.deep_view();
 
-            if self.check_chain_policy(cache, &path, root_issuers[i])? {
-                // Found a valid chain
-                return Ok(true);
+            assert(path@.take(i + 1).drop_last() =~= path@.take(i as int));
+            assert(path@.take(i + 1).last() == path@[i as int]);
+
+            if path[i] < cache.ocsp.len() {
+                if let OptionDeep::Some(resp) = cache.ocsp.get(path[i]) {
+                    if i + 1 < path_len {
+                        let ghost _ = cache.bundle_abs_cache.deep_view()[path@[i + 1] as int];
+                        assert(spec_candidate[i + 1] == cache.bundle@[path@[i + 1] as int]);
+
+                        let issuer = cache.bundle.get(path[i + 1]);
+
+                        Self::ocsp_results(
+                            &IssuerKey::from_cert(issuer), issuer.get().cert.get().subject.serialize(),
+                            cache.bundle_abs_cache[path[i + 1]],
+                            Ghost(cache.bundle@[path@[i + 1] as int]), resp, cache.task.now, cache.budget, &mut task.ocsp,
+                        )?;
+                    } else {
+                        assert(spec_candidate[i + 1] == self.roots@[root_idx as int]);
+
+                        Self::ocsp_results(
+                            &roots.key(root_idx), roots.subject_name(root_idx), roots.abs(root_idx),
+                            Ghost(self.roots@[root_idx as int]), resp, cache.task.now, cache.budget, &mut task.ocsp,
+                        )?;
+                    }
+                }
             }
         }
 
-        assert forall |root_idx: usize|
-            #[trigger] query.is_simple_path_to_root(path@, root_idx) implies
-            !query.path_satisfies_policy(path@, root_idx)
-        by {
-            assert(root_issuers@.contains(root_idx));
+        assert(path@.take(path_len as int) =~= path@);
+        assert(task.deep_view() =~~= query.task_for_candidate(path@, spec_candidate));
+
+        let valid = self.policy.valid_chain(&candidate, &task);
+        if !valid {
+            cache.report.policy_rejected(root_idx, &candidate, &task);
         }
 
-        Ok(false)
+        Ok(valid)
     }
 
-    #[verifier::opaque]
-    closed spec fn spec_root_issuers(self, cert: SpecCertificateValue, indices: Seq<usize>) -> bool {
-        // All in-bound
-        &&& forall |i| 0 <= i < indices.len() ==> 0 <= #[trigger] indices[i] < self.roots@.len()
+    /// Append revoked serials in `self.crls` signed by `issuer` to `res`
+    #[verifier::loop_isolation(false)]
+    fn issuer_revocations(
+        &self,
+        issuer_key: &IssuerKey<'_>,
+        issuer_abs: &policy::ExecCertificate,
+        Ghost(issuer): Ghost<SpecCertificateValue>,
+        budget: &Budget,
+        res: &mut Vec<policy::ExecRevokedSerial>,
+    ) -> (ret: Result<(), ValidationError>)
+        requires
+            issuer_key@ == spec_issuer_key(issuer),
+            Some(issuer_abs.deep_view()) == policy::Certificate::spec_from(issuer),
+
+        ensures
+            ret is Ok ==>
+                res.deep_view() =~= old(res).deep_view() + Query::<P>::spec_issuer_revocations(self.crls@, issuer),
+    {
+        let crls_len = self.crls.len();
+
+        assert(self.crls@.take(0) =~= seq![]);
+
+        for i in 0..crls_len
+            invariant
+                crls_len == self.crls@.len(),
+                res.deep_view() =~= old(res).deep_view() + Query::<P>::spec_issuer_revocations(self.crls@.take(i as int), issuer),
+        {
+            let ghost prev_res = res.deep_view();
+
+            Self::crl_revocations(issuer_key, issuer_abs, Ghost(issuer), self.crls.get(i), budget, res)?;
+
+            assert(self.crls@.take(i + 1).drop_last() =~= self.crls@.take(i as int));
+            assert(self.crls@.take(i + 1).last() == self.crls@[i as int]);
+        }
+
+        assert(self.crls@.take(crls_len as int) =~= self.crls@);
+
+        Ok(())
+    }
+
+    /// Append revoked serials in `crl` to `res` if `crl` is signed by `issuer`
+    fn crl_revocations(
+        issuer_key: &IssuerKey<'_>,
+        issuer_abs: &policy::ExecCertificate,
+        Ghost(issuer): Ghost<SpecCertificateValue>,
+        crl: &CertificateListValue<'_>,
+        budget: &Budget,
+        res: &mut Vec<policy::ExecRevokedSerial>,
+    ) -> (ret: Result<(), ValidationError>)
+        requires
+            issuer_key@ == spec_issuer_key(issuer),
+            Some(issuer_abs.deep_view()) == policy::Certificate::spec_from(issuer),
+
+        ensures
+            ret is Ok ==> res.deep_view() =~= old(res).deep_view() + spec_crl_revocations(issuer, crl@),
+    {
+        if !budget.take_signature() {
+            return Err(ValidationError::ResourceLimitExceeded);
+        }
+
+        if !verify_crl_signature(issuer_key, crl) {
+            return Ok(());
+        }
+
+        if let OptionDeep::Some(revoked) = &crl.get().cert_list.get().revoked_certs {
+            let revoked_len = revoked.len();
+            let ghost prev_res = res.deep_view();
+
+            for i in 0..revoked_len
+                invariant
+                    revoked_len == revoked@.len(),
+                    res.deep_view() =~= prev_res + spec_crl_revocations(issuer, crl@).take(i as int),
+            {
+                res.push(policy::ExecRevokedSerial {
+                    issuer_fingerprint: issuer_abs.fingerprint.clone(),
+                    serial: hash::to_hex_upper(revoked.get(i).serial.bytes()),
+                });
+            }
+
+            assert(spec_crl_revocations(issuer, crl@).take(revoked_len as int) =~= spec_crl_revocations(issuer, crl@));
+        }
+
+        Ok(())
+    }
+
+    /// Append the statuses in the stapled OCSP response `resp` about certificates
+    /// issued by `issuer` to `res`, if `resp` is signed by `issuer` (or on behalf of it)
+    ///
+    /// NOTE: a malformed response is skipped instead of failing the validation
+    #[verifier::loop_isolation(false)]
+    fn ocsp_results(
+        issuer_key: &IssuerKey<'_>,
+        issuer_name: &[u8],
+        issuer_abs: &policy::ExecCertificate,
+        Ghost(issuer): Ghost<SpecCertificateValue>,
+        resp: &OCSPResponseValue<'_>,
+        now: u64,
+        budget: &Budget,
+        res: &mut Vec<policy::ExecOCSPResult>,
+    ) -> (ret: Result<(), ValidationError>)
+        requires
+            issuer_key@ == spec_issuer_key(issuer),
+            ASN1(Name)@.spec_serialize(issuer.cert.subject) matches Ok(name) && name == issuer_name@,
+            Some(issuer_abs.deep_view()) == policy::Certificate::spec_from(issuer),
+
+        ensures
+            ret is Ok ==> res.deep_view() =~= old(res).deep_view() + spec_ocsp_results(issuer, resp@, now),
+    {
+        let basic = match Self::basic_ocsp_response(resp) {
+            Some(basic) => basic,
+            None => return Ok(()),
+        };
+
+        if !Self::ocsp_signed_by(issuer_key, Ghost(issuer), basic, now, budget)? {
+            return Ok(());
+        }
+
+        let responses = &basic.tbs_response_data.get().responses;
+        let responses_len = responses.len();
+        let ghost prev_res = res.deep_view();
+
+        assert(responses@.take(0) =~= seq![]);
+
+        for i in 0..responses_len
+            invariant
+                responses_len == responses@.len(),
+                res.deep_view() =~= prev_res + spec_ocsp_issuer_results(issuer, responses@.take(i as int)),
+        {
+            let single = responses.get(i);
+
+            assert(responses@.take(i + 1).drop_last() =~= responses@.take(i as int));
+            assert(responses@.take(i + 1).last() == responses@[i as int]);
+
+            if Self::cert_id_matches_issuer(issuer_key, issuer_name, Ghost(issuer), &single.cert_id) {
+                if let Some(result) = Self::ocsp_result(issuer_abs, Ghost(issuer), single) {
+                    res.push(result);
+                }
+            }
+        }
+
+        assert(responses@.take(responses_len as int) =~= responses@);
+
+        Ok(())
+    }
+
+    fn basic_ocsp_response<'b, 'c>(resp: &'b OCSPResponseValue<'c>) -> (res: Option<&'b BasicOCSPResponseValue<'c>>)
+        ensures
+            res matches Some(basic) ==> spec_basic_ocsp_response(resp@) == Some(basic@),
+            res is None ==> spec_basic_ocsp_response(resp@) is None,
+    {
+        if let OptionDeep::Some(bytes) = &resp.response_bytes {
+            if resp.status == 0 && bytes.response_type.polyfill_eq(&oid!(OCSP_BASIC)) {
+                return Some(&bytes.response);
+            }
+        }
+
+        None
+    }
+
+    fn ocsp_signed_by(
+        issuer_key: &IssuerKey<'_>,
+        Ghost(issuer): Ghost<SpecCertificateValue>,
+        resp: &BasicOCSPResponseValue<'_>,
+        now: u64,
+        budget: &Budget,
+    ) -> (res: Result<bool, ValidationError>)
+        requires
+            issuer_key@ == spec_issuer_key(issuer),
+
+        ensures
+            res matches Ok(res) ==> res == spec_ocsp_signed_by(issuer, resp@, now),
+    {
+        if !budget.take_signature() {
+            return Err(ValidationError::ResourceLimitExceeded);
+        }
+
+        if verify_ocsp_signature(issuer_key, resp) {
+            return Ok(true);
+        }
+
+        // Otherwise look for a delegated responder
+        if let OptionDeep::Some(certs) = &resp.certs {
+            let certs_len = certs.len();
+
+            for i in 0..certs_len
+                invariant
+                    certs_len == certs@.len(),
+                    forall |j: int| #![trigger certs@[j]] 0 <= j < i ==> !{
+                        &&& spec_is_ocsp_delegate(issuer, certs@[j], now)
+                        &&& spec_verify_ocsp_signature(spec_issuer_key(certs@[j]), resp@)
+                    },
+            {
+                let delegate = certs.get(i);
+
+                if Self::is_ocsp_delegate(issuer_key, Ghost(issuer), delegate, now, budget)? {
+                    if !budget.take_signature() {
+                        return Err(ValidationError::ResourceLimitExceeded);
+                    }
+
+                    if verify_ocsp_signature(&IssuerKey::from_cert(delegate), resp) {
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn is_ocsp_delegate(
+        issuer_key: &IssuerKey<'_>,
+        Ghost(issuer): Ghost<SpecCertificateValue>,
+        delegate: &CertificateValue<'_>,
+        now: u64,
+        budget: &Budget,
+    ) -> (res: Result<bool, ValidationError>)
+        requires
+            issuer_key@ == spec_issuer_key(issuer),
+
+        ensures
+            res matches Ok(res) ==> res == spec_is_ocsp_delegate(issuer, delegate@, now),
+    {
+        if !budget.take_signature() {
+            return Err(ValidationError::ResourceLimitExceeded);
+        }
+
+        if !verify_signature(issuer_key, delegate) {
+            return Ok(false);
+        }
+
+        let validity = &delegate.get().cert.get().validity;
+
+        let not_before = match policy::Certificate::time_to_timestamp(&validity.not_before) {
+            Some(ts) => ts,
+            None => return Ok(false),
+        };
+
+        let not_after = match policy::Certificate::time_to_timestamp(&validity.not_after) {
+            Some(ts) => ts,
+            None => return Ok(false),
+        };
+
+        // Compare as in the spec (over integers), where `now` is unsigned
+        if (not_before >= 0 && not_before as u64 > now) || not_after < 0 || now > not_after as u64 {
+            return Ok(false);
+        }
+
+        let ext = match policy::Certificate::get_extension(delegate, &oid!(EXTENDED_KEY_USAGE)) {
+            Some(ext) => ext,
+            None => return Ok(false),
+        };
+
+        let eku = match policy::ExtendedKeyUsage::from(ext) {
+            Ok(eku) => eku,
+            Err(..) => return Ok(false),
+        };
+
+        let usages_len = eku.usages.len();
+
+        for i in 0..usages_len
+            invariant
+                usages_len == eku.usages.deep_view().len(),
+                forall |j: int| 0 <= j < i ==>
+                    #[trigger] eku.usages.deep_view()[j] != policy::ExtendedKeyUsageType::OCSPSigning,
+        {
+            if let policy::ExecExtendedKeyUsageType::OCSPSigning = &eku.usages[i] {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn cert_id_matches_issuer(
+        issuer_key: &IssuerKey<'_>,
+        issuer_name: &[u8],
+        Ghost(issuer): Ghost<SpecCertificateValue>,
+        cert_id: &CertIDValue<'_>,
+    ) -> (res: bool)
+        requires
+            issuer_key@ == spec_issuer_key(issuer),
+            ASN1(Name)@.spec_serialize(issuer.cert.subject) matches Ok(name) && name == issuer_name@,
+
+        ensures res == spec_cert_id_matches_issuer(issuer, cert_id@)
+    {
+        let key = issuer_key.pub_key;
+
+        if cert_id.hash_alg.id.polyfill_eq(&oid!(HASH_SHA1)) {
+            hash::sha1_digest(issuer_name).as_slice().polyfill_eq(&cert_id.issuer_name_hash) &&
+            hash::sha1_digest(key).as_slice().polyfill_eq(&cert_id.issuer_key_hash)
+        } else if cert_id.hash_alg.id.polyfill_eq(&oid!(HASH_SHA256)) {
+            hash::sha256_digest(issuer_name).as_slice().polyfill_eq(&cert_id.issuer_name_hash) &&
+            hash::sha256_digest(key).as_slice().polyfill_eq(&cert_id.issuer_key_hash)
+        } else {
+            false
+        }
+    }
+
+    fn ocsp_timestamp(time: &GeneralizedTimeValue<'_>) -> (res: Option<u64>)
+        ensures res == spec_ocsp_timestamp(time@)
+    {
+        let time = TimeValue::GeneralizedTime(PolyfillClone::clone(time));
+
+        match policy::Certificate::time_to_timestamp(&time) {
+            Some(ts) => if ts >= 0 { Some(ts as u64) } else { None },
+            None => None,
+        }
+    }
+
+    fn ocsp_result(
+        issuer_abs: &policy::ExecCertificate,
+        Ghost(issuer): Ghost<SpecCertificateValue>,
+        single: &SingleResponseValue<'_>,
+    ) -> (res: Option<policy::ExecOCSPResult>)
+        requires
+            Some(issuer_abs.deep_view()) == policy::Certificate::spec_from(issuer),
+
+        ensures
+            res matches Some(res) ==> spec_ocsp_result(issuer, single@) == Some(res.deep_view()),
+            res is None ==> spec_ocsp_result(issuer, single@) is None,
+    {
+        let status = match &single.cert_status {
+            CertStatusValue::Good(..) => policy::ExecOCSPStatus::Good,
+            CertStatusValue::Revoked(..) => policy::ExecOCSPStatus::Revoked,
+            _ => policy::ExecOCSPStatus::Unknown,
+        };
+
+        let this_update = match Self::ocsp_timestamp(&single.this_update) {
+            Some(ts) => ts,
+            None => return None,
+        };
+
+        let next_update = match &single.next_update {
+            OptionDeep::Some(next_update) => match Self::ocsp_timestamp(next_update) {
+                Some(ts) => Some(ts),
+                None => return None,
+            },
+            OptionDeep::None => None,
+        };
+
+        Some(policy::ExecOCSPResult {
+            issuer_fingerprint: issuer_abs.fingerprint.clone(),
+            serial: hash::to_hex_upper(single.cert_id.serial.bytes()),
+            status,
+            this_update,
+            next_update,
+        })
+    }
+
+    /// Given a simple path through the bundle certificates
+    /// and all root issuers of the last certificate in the path,
+    /// check if the entire path satisfies the policy, and if so,
+    /// return the index of the root certificate that completes the chain
+    #[verifier::loop_isolation(false)]
+    #[allow(unexpected_cfgs)]
+    fn check_simple_path(
+        &self,
+        cache: &ValidatorCache<'_, '_, '_, '_>,
+        path: &Vec<usize>,
+    ) -> (res: Result<Option<usize>, ValidationError>)
+        requires
+            self.wf(),
+            cache.wf(self),
+            cache.get_query(self).is_simple_path(path@),
+
+        ensures
+            res matches Ok(Some(root_idx)) ==> {
+                &&& cache.get_query(self).is_simple_path_to_root(path@, root_idx)
+                &&& cache.get_query(self).path_satisfies_policy(path@, root_idx)
+            },
+            res matches Ok(None) ==>
+                forall |root_idx: usize|
+                    #[trigger] cache.get_query(self).is_simple_path_to_root(path@, root_idx) ==>
+                    !cache.get_query(self).path_satisfies_policy(path@, root_idx),
+    {
+        reveal(Validator::spec_root_issuers);
+
+        let last = path[path.len() - 1];
+
+        let root_issuers = &cache.root_issuers[last];
+        let root_issuers_len = root_issuers.len();
+        let ghost query = cache.get_query(self);
+
+        for i in 0..root_issuers_len
+            invariant
+                forall |j| 0 <= j < i ==>
+                    !query.path_satisfies_policy(path@, #[trigger] root_issuers@[j]),
+        {
+            #[cfg(feature = "trace")]
+            eprintln_join!("checking path: ", format_dbg(path), " w/ root ", root_issuers[i]);
+
+            if self.check_chain_policy(cache, &path, root_issuers[i])? {
+                // Found a valid chain
+                return Ok(Some(root_issuers[i]));
+            }
+        }
+
+        assert forall |root_idx: usize|
+            #[trigger] query.is_simple_path_to_root(path@, root_idx) implies
+            !query.path_satisfies_policy(path@, root_idx)
+        by {
+            assert(root_issuers@.contains(root_idx));
+        }
+
+        Ok(None)
+    }
+
+    /// Same as [`Validator::check_simple_path`], but returns the indices
+    /// of all root certificates that complete a valid chain with `path`
+    #[verifier::loop_isolation(false)]
+    fn check_simple_path_all(
+        &self,
+        cache: &ValidatorCache<'_, '_, '_, '_>,
+        path: &Vec<usize>,
+    ) -> (res: Result<Vec<usize>, ValidationError>)
+        requires
+            self.wf(),
+            cache.wf(self),
+            cache.get_query(self).is_simple_path(path@),
+
+        ensures
+            res matches Ok(roots) ==>
+                forall |root_idx: usize| #[trigger] roots@.contains(root_idx) <==> {
+                    &&& cache.get_query(self).is_simple_path_to_root(path@, root_idx)
+                    &&& cache.get_query(self).path_satisfies_policy(path@, root_idx)
+                },
+    {
+        reveal(Validator::spec_root_issuers);
+
+        let last = path[path.len() - 1];
+
+        let root_issuers = &cache.root_issuers[last];
+        let root_issuers_len = root_issuers.len();
+        let ghost query = cache.get_query(self);
+
+        let mut res = Vec::new();
+
+        for i in 0..root_issuers_len
+            invariant
+                forall |k| 0 <= k < res.len() ==> {
+                    &&& query.is_simple_path_to_root(path@, #[trigger] res@[k])
+                    &&& query.path_satisfies_policy(path@, res@[k])
+                },
+                forall |j| 0 <= j < i && query.path_satisfies_policy(path@, #[trigger] root_issuers@[j]) ==>
+                    res@.contains(root_issuers@[j]),
+        {
+            if self.check_chain_policy(cache, &path, root_issuers[i])? {
+                let ghost old_res = res@;
+                res.push(root_issuers[i]);
+
+                assert forall |j| 0 <= j < i && old_res.contains(#[trigger] root_issuers@[j])
+                    implies res@.contains(root_issuers@[j])
+                by {
+                    let m = choose |m: int| 0 <= m < old_res.len() && old_res[m] == root_issuers@[j];
+                    assert(res@[m] == root_issuers@[j]);
+                }
+                assert(res@[res@.len() - 1] == root_issuers@[i as int]);
+            }
+        }
+
+        assert forall |root_idx: usize| #[trigger] res@.contains(root_idx) <==> {
+            &&& query.is_simple_path_to_root(path@, root_idx)
+            &&& query.path_satisfies_policy(path@, root_idx)
+        } by {
+            if res@.contains(root_idx) {
+                let k = choose |k: int| 0 <= k < res@.len() && res@[k] == root_idx;
+                assert(query.is_simple_path_to_root(path@, res@[k]));
+            }
+
+            if query.is_simple_path_to_root(path@, root_idx) {
+                assert(root_issuers@.contains(root_idx));
+            }
+        }
+
+        Ok(res)
+    }
+
+    #[verifier::opaque]
+    closed spec fn spec_root_issuers(self, cert: SpecCertificateValue, indices: Seq<usize>) -> bool {
+        // All in-bound
+        &&& forall |i| 0 <= i < indices.len() ==> 0 <= #[trigger] indices[i] < self.roots@.len()
 
         // Contains all likely root issuers
         &&& forall |i| 0 <= i < self.roots@.len() &&
@@ -500,23 +1498,103 @@ impl<'a, P: Policy + 'a> Validator<'a, P> {
             Query::issued(self.policy, self.roots@[#[trigger] indices[i] as int], cert)
     }
 
-    /// Get indices of root certificates that likely issued the given certificate
+    /// Get indices of root certificates that likely issued the given certificate,
+    /// only checking the candidates from the root index if the policy allows
     #[verifier::loop_isolation(false)]
     fn get_root_issuer(
         &self,
         bundle: &VecDeep<CertificateValue<'_>>,
-        bundle_abs_cache: &Vec<policy::ExecCertificate>,
+        bundle_abs_cache: &Vec<&policy::ExecCertificate>,
+        idx: usize,
+        report: &ReportSink<'_>,
+        budget: &Budget,
+    ) -> (res: Result<Vec<usize>, ValidationError>)
+        requires
+            self.wf(),
+            Self::is_abs_cache(bundle@, bundle_abs_cache.deep_view()),
+            0 <= idx < bundle@.len(),
+
+        ensures res matches Ok(res) ==> self.spec_root_issuers(bundle@[idx as int], res@)
+    {
+        let roots = arc_as_ref(&self.roots);
+        let subject = bundle_abs_cache[idx];
+        let lookup = self.policy.issuer_lookup();
+
+        let by_key_id = match lookup {
+            policy::IssuerLookup::Linear => return self.get_root_issuer_linear(bundle, bundle_abs_cache, idx, report, budget),
+            policy::IssuerLookup::Name => false,
+            policy::IssuerLookup::NameAndKeyId => true,
+        };
+
+        let candidates = roots.candidates(&subject.issuer);
+        let mut res = Vec::with_capacity(1); // usually there is only 1 root issuer
+
+        reveal(Query::issued);
+        let ghost _ = bundle_abs_cache.deep_view()[idx as int];
+
+        for j in 0..candidates.len()
+            invariant
+                forall |k| 0 <= k < res.len() ==> 0 <= #[trigger] res[k] < self.roots@.len(),
+                forall |k| 0 <= k < res.len() ==>
+                    Query::issued(self.policy, self.roots@[#[trigger] res[k] as int], bundle@[idx as int]),
+                forall |k| 0 <= k < j &&
+                    Query::issued(self.policy, self.roots@[candidates@[k] as int], bundle@[idx as int]) ==>
+                    res@.contains(#[trigger] candidates@[k]),
+        {
+            let i = candidates[j];
+
+            // Roots with a mismatching SKID cannot be the issuer
+            if by_key_id && !policy::exec_check_auth_key_id(roots.abs(i), subject) {
+                continue;
+            }
+
+            if self.check_root_likely_issued(bundle, bundle_abs_cache, i, idx, report, budget)? {
+                let ghost old_res = res@;
+                res.push(i);
+                assert forall |k| 0 <= k < j && old_res.contains(#[trigger] candidates@[k])
+                    implies res@.contains(candidates@[k])
+                by {
+                    let m = choose |m: int| 0 <= m < old_res.len() && old_res[m] == candidates@[k];
+                    assert(res@[m] == candidates@[k]);
+                }
+                assert(res@[res@.len() - 1] == i);
+            }
+        }
+
+        assert forall |i|
+            0 <= i < self.roots@.len() &&
+            Query::issued(self.policy, self.roots@[i as int], bundle@[idx as int])
+            implies #[trigger] res@.contains(i)
+        by {
+            assert(candidates@.contains(i));
+            let k = choose |k: int| 0 <= k < candidates@.len() && candidates@[k] == i;
+            assert(res@.contains(candidates@[k]));
+        }
+
+        reveal(Validator::spec_root_issuers);
+
+        Ok(res)
+    }
+
+    /// Same as [`Validator::get_root_issuer`], but checks every root certificate
+    #[verifier::loop_isolation(false)]
+    fn get_root_issuer_linear(
+        &self,
+        bundle: &VecDeep<CertificateValue<'_>>,
+        bundle_abs_cache: &Vec<&policy::ExecCertificate>,
         idx: usize,
-    ) -> (res: Vec<usize>)
+        report: &ReportSink<'_>,
+        budget: &Budget,
+    ) -> (res: Result<Vec<usize>, ValidationError>)
         requires
             self.wf(),
             Self::is_abs_cache(bundle@, bundle_abs_cache.deep_view()),
             0 <= idx < bundle@.len(),
 
-        ensures self.spec_root_issuers(bundle@[idx as int], res@)
+        ensures res matches Ok(res) ==> self.spec_root_issuers(bundle@[idx as int], res@)
     {
         let mut res = Vec::with_capacity(1); // usually there is only 1 root issuer
-        let roots_len = self.roots.len();
+        let roots_len = arc_as_ref(&self.roots).len();
 
         let ghost root_indices = Seq::new(self.roots@.len() as nat, |i| i as usize);
         let ghost pred = |j: usize| Query::issued(self.policy, self.roots@[j as int], bundle@[idx as int]);
@@ -528,7 +1606,7 @@ impl<'a, P: Policy + 'a> Validator<'a, P> {
         {
             reveal_with_fuel(Seq::<_>::filter, 1);
 
-            if self.check_root_likely_issued(bundle, bundle_abs_cache, i, idx) {
+            if self.check_root_likely_issued(bundle, bundle_abs_cache, i, idx, report, budget)? {
                 res.push(i);
             }
 
@@ -537,93 +1615,469 @@ impl<'a, P: Policy + 'a> Validator<'a, P> {
 
         assert(root_indices.take(roots_len as int) == root_indices);
 
-        assert forall |i|
-            0 <= i < self.roots@.len() &&
-            Query::issued(self.policy, self.roots@[i as int], bundle@[idx as int])
-            implies #[trigger] res@.contains(i)
-        by {
-            assert(root_indices[i as int] == i);
-            assert(pred(root_indices[i as int]));
-        }
+        assert forall |i|
+            0 <= i < self.roots@.len() &&
+            Query::issued(self.policy, self.roots@[i as int], bundle@[idx as int])
+            implies #[trigger] res@.contains(i)
+        by {
+            assert(root_indices[i as int] == i);
+            assert(pred(root_indices[i as int]));
+        }
+
+        reveal(Validator::spec_root_issuers);
+
+        Ok(res)
+    }
+
+    /// Initialize a validator cache for later validation
+    #[verifier::loop_isolation(false)]
+    fn new_cache<'b, 'c, 'd, 'r>(
+        &self,
+        bundle: &'b VecDeep<CertificateValue<'c>>,
+        leaf: usize,
+        bundle_abs_cache: Vec<&'b policy::ExecCertificate>,
+        ocsp: &'b VecDeep<OptionDeep<OCSPResponseValue<'c>>>,
+        task: &'d policy::ExecTask,
+        report: &'b ReportSink<'r>,
+        budget: &'b Budget,
+    ) -> (res: Result<ValidatorCache<'b, 'c, 'd, 'r>, ValidationError>)
+        requires
+            self.wf(),
+            Self::is_abs_cache(bundle@, bundle_abs_cache.deep_view()),
+        ensures
+            res matches Ok(res) ==> {
+                &&& res.wf(self)
+                &&& res.bundle == bundle
+                &&& res.leaf == leaf
+                &&& res.ocsp == ocsp
+                &&& res.task == task
+            },
+    {
+        let bundle_len = bundle.len();
+
+        // root_issuers[i] are the indices of root certificates that likely issued bundle[i]
+        let mut root_issuers: Vec<Vec<usize>> = Vec::with_capacity(bundle_len);
+
+        // Collect all root issuers for each certificate in the bundle
+        for i in 0..bundle_len
+            invariant
+                root_issuers@.len() == i,
+                forall |j| 0 <= j < i ==>
+                    self.spec_root_issuers(bundle@[j], #[trigger] root_issuers@[j]@),
+        {
+            root_issuers.push(self.get_root_issuer(bundle, &bundle_abs_cache, i, report, budget)?);
+        }
+
+        Ok(ValidatorCache {
+            bundle: bundle,
+            leaf: leaf,
+            ocsp: ocsp,
+            task: task,
+            bundle_abs_cache: bundle_abs_cache,
+            root_issuers: root_issuers,
+            report: report,
+            budget: budget,
+        })
+    }
+
+    /// Validates the given chain of parsed certificates
+    /// for the given `task`, where `bundle[0]` is the leaf certificate.
+    #[verifier::loop_isolation(false)]
+    pub fn validate(
+        &self,
+        bundle: &VecDeep<CertificateValue<'_>>,
+        task: &policy::ExecTask,
+    ) -> (res: Result<bool, ValidationError>)
+        requires self.wf()
+        ensures
+            // Soundness & completeness (modulo ValidationError)
+            res matches Ok(res) ==> res == (Query {
+                policy: self.policy,
+                roots: self.roots@,
+                anchors: self.roots.anchors(),
+                bundle: bundle@,
+                leaf: 0,
+                crls: self.crls@,
+                ocsp: seq![],
+                task: task.deep_view(),
+            }).valid(),
+    {
+        let ocsp: VecDeep<OptionDeep<OCSPResponseValue<'_>>> = VecDeep::new();
+        assert(ocsp@ =~= seq![]);
+
+        self.validate_stapled(bundle, &ocsp, task)
+    }
+
+    /// Same as [`Validator::validate`], but also takes into account
+    /// stapled OCSP responses, where `ocsp[i]` (if present) is the
+    /// response for `bundle[i]`
+    #[verifier::loop_isolation(false)]
+    pub fn validate_stapled(
+        &self,
+        bundle: &VecDeep<CertificateValue<'_>>,
+        ocsp: &VecDeep<OptionDeep<OCSPResponseValue<'_>>>,
+        task: &policy::ExecTask,
+    ) -> (res: Result<bool, ValidationError>)
+        requires self.wf()
+        ensures
+            // Soundness & completeness (modulo ValidationError)
+            res matches Ok(res) ==> res == (Query {
+                policy: self.policy,
+                roots: self.roots@,
+                anchors: self.roots.anchors(),
+                bundle: bundle@,
+                leaf: 0,
+                crls: self.crls@,
+                ocsp: ocsp@,
+                task: task.deep_view(),
+            }).valid(),
+    {
+        match self.validate_stapled_with_path(bundle, ocsp, task)? {
+            Some(..) => Ok(true),
+            None => Ok(false),
+        }
+    }
+
+    /// Same as [`Validator::validate`], but additionally returns
+    /// the accepted chain as a list of indices into `bundle`
+    /// (starting from the leaf `bundle[0]`), along with the index
+    /// of the root certificate that issued the last certificate in the path.
+    pub fn validate_with_path(
+        &self,
+        bundle: &VecDeep<CertificateValue<'_>>,
+        task: &policy::ExecTask,
+    ) -> (res: Result<Option<(Vec<usize>, usize)>, ValidationError>)
+        requires self.wf()
+        ensures
+            // Soundness: the returned path is a valid chain
+            res matches Ok(Some((path, root_idx))) ==> {
+                let query = Query {
+                    policy: self.policy,
+                    roots: self.roots@,
+                    anchors: self.roots.anchors(),
+                    bundle: bundle@,
+                    leaf: 0,
+                    crls: self.crls@,
+                    ocsp: seq![],
+                    task: task.deep_view(),
+                };
+                &&& query.is_simple_path_to_root(path@, root_idx)
+                &&& query.path_satisfies_policy(path@, root_idx)
+            },
+
+            // Completeness (modulo ValidationError)
+            res matches Ok(None) ==> !(Query {
+                policy: self.policy,
+                roots: self.roots@,
+                anchors: self.roots.anchors(),
+                bundle: bundle@,
+                leaf: 0,
+                crls: self.crls@,
+                ocsp: seq![],
+                task: task.deep_view(),
+            }).valid(),
+    {
+        let ocsp: VecDeep<OptionDeep<OCSPResponseValue<'_>>> = VecDeep::new();
+        assert(ocsp@ =~= seq![]);
+
+        self.validate_stapled_with_path(bundle, &ocsp, task)
+    }
+
+    /// Same as [`Validator::validate_with_path`], but also takes
+    /// into account stapled OCSP responses (see [`Validator::validate_stapled`])
+    pub fn validate_stapled_with_path(
+        &self,
+        bundle: &VecDeep<CertificateValue<'_>>,
+        ocsp: &VecDeep<OptionDeep<OCSPResponseValue<'_>>>,
+        task: &policy::ExecTask,
+    ) -> (res: Result<Option<(Vec<usize>, usize)>, ValidationError>)
+        requires self.wf()
+        ensures
+            // Soundness: the returned path is a valid chain
+            res matches Ok(Some((path, root_idx))) ==> {
+                let query = Query {
+                    policy: self.policy,
+                    roots: self.roots@,
+                    anchors: self.roots.anchors(),
+                    bundle: bundle@,
+                    leaf: 0,
+                    crls: self.crls@,
+                    ocsp: ocsp@,
+                    task: task.deep_view(),
+                };
+                &&& query.is_simple_path_to_root(path@, root_idx)
+                &&& query.path_satisfies_policy(path@, root_idx)
+            },
+
+            // Completeness (modulo ValidationError)
+            res matches Ok(None) ==> !(Query {
+                policy: self.policy,
+                roots: self.roots@,
+                anchors: self.roots.anchors(),
+                bundle: bundle@,
+                leaf: 0,
+                crls: self.crls@,
+                ocsp: ocsp@,
+                task: task.deep_view(),
+            }).valid(),
+    {
+        self.validate_bundle(bundle, 0, ocsp, task)
+    }
+
+    /// Same as [`Validator::validate_stapled_with_path`], but the leaf is
+    /// `bundle[leaf]`, and the other certificates in `bundle` can be in any
+    /// order (e.g., bundles sent out of order, or from PKCS#7)
+    pub fn validate_bundle(
+        &self,
+        bundle: &VecDeep<CertificateValue<'_>>,
+        leaf: usize,
+        ocsp: &VecDeep<OptionDeep<OCSPResponseValue<'_>>>,
+        task: &policy::ExecTask,
+    ) -> (res: Result<Option<(Vec<usize>, usize)>, ValidationError>)
+        requires self.wf()
+        ensures
+            // Soundness: the returned path is a valid chain
+            res matches Ok(Some((path, root_idx))) ==> {
+                let query = Query {
+                    policy: self.policy,
+                    roots: self.roots@,
+                    anchors: self.roots.anchors(),
+                    bundle: bundle@,
+                    leaf: leaf,
+                    crls: self.crls@,
+                    ocsp: ocsp@,
+                    task: task.deep_view(),
+                };
+                &&& query.is_simple_path_to_root(path@, root_idx)
+                &&& query.path_satisfies_policy(path@, root_idx)
+            },
+
+            // Completeness (modulo ValidationError)
+            res matches Ok(None) ==> !(Query {
+                policy: self.policy,
+                roots: self.roots@,
+                anchors: self.roots.anchors(),
+                bundle: bundle@,
+                leaf: leaf,
+                crls: self.crls@,
+                ocsp: ocsp@,
+                task: task.deep_view(),
+            }).valid(),
+    {
+        self.validate_bundle_with_sink(bundle, leaf, ocsp, task, &ReportSink::none())
+    }
 
-        reveal(Validator::spec_root_issuers);
+    /// Same as [`Validator::validate_bundle`], but records why
+    /// each explored candidate path is rejected in `report`
+    pub fn validate_bundle_with_sink(
+        &self,
+        bundle: &VecDeep<CertificateValue<'_>>,
+        leaf: usize,
+        ocsp: &VecDeep<OptionDeep<OCSPResponseValue<'_>>>,
+        task: &policy::ExecTask,
+        report: &ReportSink<'_>,
+    ) -> (res: Result<Option<(Vec<usize>, usize)>, ValidationError>)
+        requires self.wf()
+        ensures
+            // Soundness: the returned path is a valid chain
+            res matches Ok(Some((path, root_idx))) ==> {
+                let query = Query {
+                    policy: self.policy,
+                    roots: self.roots@,
+                    anchors: self.roots.anchors(),
+                    bundle: bundle@,
+                    leaf: leaf,
+                    crls: self.crls@,
+                    ocsp: ocsp@,
+                    task: task.deep_view(),
+                };
+                &&& query.is_simple_path_to_root(path@, root_idx)
+                &&& query.path_satisfies_policy(path@, root_idx)
+            },
 
-        res
+            // Completeness (modulo ValidationError)
+            res matches Ok(None) ==> !(Query {
+                policy: self.policy,
+                roots: self.roots@,
+                anchors: self.roots.anchors(),
+                bundle: bundle@,
+                leaf: leaf,
+                crls: self.crls@,
+                ocsp: ocsp@,
+                task: task.deep_view(),
+            }).valid(),
+    {
+        let bundle_abs_cache = Self::get_abs_cache(bundle)?;
+        self.validate_cached(bundle, leaf, vec_refs(&bundle_abs_cache), ocsp, task, report)
     }
 
-    /// Initialize a validator cache for later validation
-    #[verifier::loop_isolation(false)]
-    fn new_cache<'b, 'c, 'd>(
+    /// Same as [`Validator::validate_with_path`], but returns every valid
+    /// chain (e.g., through both the old and the new root of a cross-signed
+    /// PKI), each as a path of indices into `bundle` and a root index
+    pub fn validate_all(
         &self,
-        bundle: &'b VecDeep<CertificateValue<'c>>,
-        task: &'d policy::ExecTask,
-    ) -> (res: Result<ValidatorCache<'b, 'c, 'd>, ValidationError>)
-        requires self.wf(),
+        bundle: &VecDeep<CertificateValue<'_>>,
+        task: &policy::ExecTask,
+    ) -> (res: Result<Vec<(Vec<usize>, usize)>, ValidationError>)
+        requires self.wf()
         ensures
-            res matches Ok(res) ==> {
-                &&& res.wf(self)
-                &&& res.bundle == bundle
-                &&& res.task == task
+            // Exactly the valid chains (modulo ValidationError)
+            res matches Ok(paths) ==> {
+                let query = Query {
+                    policy: self.policy,
+                    roots: self.roots@,
+                    anchors: self.roots.anchors(),
+                    bundle: bundle@,
+                    leaf: 0,
+                    crls: self.crls@,
+                    ocsp: seq![],
+                    task: task.deep_view(),
+                };
+                forall |path: Seq<usize>, root_idx: usize|
+                    #[trigger] spec_contains_path(paths@, path, root_idx) <==> {
+                        &&& query.is_simple_path_to_root(path, root_idx)
+                        &&& query.path_satisfies_policy(path, root_idx)
+                    }
             },
     {
-        let bundle_len = bundle.len();
+        let ocsp: VecDeep<OptionDeep<OCSPResponseValue<'_>>> = VecDeep::new();
+        assert(ocsp@ =~= seq![]);
 
-        // Cache abstract representation of each certificate
         let bundle_abs_cache = Self::get_abs_cache(bundle)?;
-
-        // root_issuers[i] are the indices of root certificates that likely issued bundle[i]
-        let mut root_issuers: Vec<Vec<usize>> = Vec::with_capacity(bundle_len);
-
-        // Collect all root issuers for each certificate in the bundle
-        for i in 0..bundle_len
-            invariant
-                root_issuers@.len() == i,
-                forall |j| 0 <= j < i ==>
-                    self.spec_root_issuers(bundle@[j], #[trigger] root_issuers@[j]@),
-        {
-            root_issuers.push(self.get_root_issuer(bundle, &bundle_abs_cache, i));
-        }
-
-        Ok(ValidatorCache {
-            bundle: bundle,
-            task: task,
-            bundle_abs_cache: bundle_abs_cache,
-            root_issuers: root_issuers,
-        })
+        self.search_cached(bundle, 0, vec_refs(&bundle_abs_cache), &ocsp, task, &ReportSink::none(), false)
     }
 
-    /// Parses the given chain of parsed certificates,
-    /// and then validates the chain for the given `task`,
-    /// where `bundle[0]` is the leaf certificate.
-    #[verifier::loop_isolation(false)]
-    #[verifier::exec_allows_no_decreases_clause]
-    pub fn validate(
+    /// Validates the given chain of parsed certificates whose abstract
+    /// representations are already computed in `bundle_abs_cache`
+    /// (e.g., from [`ParsedCertificate`]s)
+    fn validate_cached(
         &self,
         bundle: &VecDeep<CertificateValue<'_>>,
+        leaf: usize,
+        bundle_abs_cache: Vec<&policy::ExecCertificate>,
+        ocsp: &VecDeep<OptionDeep<OCSPResponseValue<'_>>>,
         task: &policy::ExecTask,
-    ) -> (res: Result<bool, ValidationError>)
-        requires self.wf()
+        report: &ReportSink<'_>,
+    ) -> (res: Result<Option<(Vec<usize>, usize)>, ValidationError>)
+        requires
+            self.wf(),
+            Self::is_abs_cache(bundle@, bundle_abs_cache.deep_view()),
         ensures
-            // Soundness & completeness (modulo ValidationError)
-            res matches Ok(res) ==> res == (Query {
+            // Soundness: the returned path is a valid chain
+            res matches Ok(Some((path, root_idx))) ==> {
+                let query = Query {
+                    policy: self.policy,
+                    roots: self.roots@,
+                    anchors: self.roots.anchors(),
+                    bundle: bundle@,
+                    leaf: leaf,
+                    crls: self.crls@,
+                    ocsp: ocsp@,
+                    task: task.deep_view(),
+                };
+                &&& query.is_simple_path_to_root(path@, root_idx)
+                &&& query.path_satisfies_policy(path@, root_idx)
+            },
+
+            // Completeness (modulo ValidationError)
+            res matches Ok(None) ==> !(Query {
                 policy: self.policy,
                 roots: self.roots@,
+                anchors: self.roots.anchors(),
                 bundle: bundle@,
+                leaf: leaf,
+                crls: self.crls@,
+                ocsp: ocsp@,
                 task: task.deep_view(),
             }).valid(),
+    {
+        let mut paths = self.search_cached(bundle, leaf, bundle_abs_cache, ocsp, task, report, true)?;
+        let ghost found = paths@;
+
+        let res = paths.pop();
+        assert(found.len() > 0 ==> res == Some(found[0]));
+
+        Ok(res)
+    }
+
+    /// DFS from `bundle[leaf]` for simple paths to a root that satisfy the
+    /// policy. If `stop_at_first` is set, the search stops at the first valid
+    /// path (returning at most one); otherwise, all valid paths are returned
+    #[verifier::loop_isolation(false)]
+    #[verifier::exec_allows_no_decreases_clause]
+    fn search_cached(
+        &self,
+        bundle: &VecDeep<CertificateValue<'_>>,
+        leaf: usize,
+        bundle_abs_cache: Vec<&policy::ExecCertificate>,
+        ocsp: &VecDeep<OptionDeep<OCSPResponseValue<'_>>>,
+        task: &policy::ExecTask,
+        report: &ReportSink<'_>,
+        stop_at_first: bool,
+    ) -> (res: Result<Vec<(Vec<usize>, usize)>, ValidationError>)
+        requires
+            self.wf(),
+            Self::is_abs_cache(bundle@, bundle_abs_cache.deep_view()),
+        ensures
+            res matches Ok(paths) ==> {
+                let query = Query {
+                    policy: self.policy,
+                    roots: self.roots@,
+                    anchors: self.roots.anchors(),
+                    bundle: bundle@,
+                    leaf: leaf,
+                    crls: self.crls@,
+                    ocsp: ocsp@,
+                    task: task.deep_view(),
+                };
+
+                // Soundness: only valid chains are returned
+                &&& forall |k| 0 <= k < paths.len() ==> {
+                    &&& query.is_simple_path_to_root((#[trigger] paths@[k]).0@, paths@[k].1)
+                    &&& query.path_satisfies_policy(paths@[k].0@, paths@[k].1)
+                }
+
+                // Completeness (modulo ValidationError)
+                &&& if stop_at_first {
+                    &&& paths.len() <= 1
+                    &&& paths.len() == 0 ==> !query.valid()
+                } else {
+                    forall |path: Seq<usize>, root_idx: usize|
+                        #[trigger] spec_contains_path(paths@, path, root_idx) <==> {
+                            &&& query.is_simple_path_to_root(path, root_idx)
+                            &&& query.path_satisfies_policy(path, root_idx)
+                        }
+                }
+            },
     {
         if bundle.len() == 0 {
             return Err(ValidationError::EmptyChain);
         }
 
-        let cache = self.new_cache(bundle, task)?;
+        if leaf >= bundle.len() {
+            return Err(ValidationError::LeafNotFound);
+        }
+
+        let limits = &self.limits;
+        let budget = Budget::new(limits);
+
+        let cache = self.new_cache(bundle, leaf, bundle_abs_cache, ocsp, task, report, &budget)?;
 
         let bundle_len = bundle.len();
         let ghost query = cache.get_query(self);
 
-        // DFS from bundle[0] to try to reach a root
+        let mut num_paths: usize = 0;
+
+        // Set if some path is not extended due to `limits.max_depth`,
+        // in which case not finding a valid path is inconclusive
+        let mut truncated = false;
+
+        // Valid paths found so far
+        let mut res: Vec<(Vec<usize>, usize)> = Vec::new();
+
+        // DFS from bundle[leaf] to try to reach a root
         // Stack of path prefices to explore
-        let mut stack: Vec<Vec<usize>> = vec![ vec![ 0 ] ];
+        let mut stack: Vec<Vec<usize>> = vec![ vec![ leaf ] ];
 
         // For triggering quantifiers associated with the leaf
         let ghost _ = stack@[0]@;
@@ -632,24 +2086,97 @@ impl<'a, P: Policy + 'a> Validator<'a, P> {
             invariant
                 forall |i| 0 <= i < stack.len() ==> query.is_simple_path(#[trigger] stack@[i]@),
 
-                // For completeness: any simple path not prefixed by elements in
-                // the current stack should be already confirmed as invalid
+                // Soundness: only valid paths are collected
+                forall |k| 0 <= k < res.len() ==> {
+                    &&& query.is_simple_path_to_root((#[trigger] res@[k]).0@, res@[k].1)
+                    &&& query.path_satisfies_policy(res@[k].0@, res@[k].1)
+                },
+
+                // The search returns as soon as a valid path is found
+                stop_at_first ==> res.len() == 0,
+
+                // For completeness: any valid simple path not prefixed by
+                // elements in the current stack should be already collected
+                !truncated ==>
                 forall |path: Seq<usize>, root_idx: usize|
                     #[trigger] query.is_simple_path_to_root(path, root_idx) &&
+                    query.path_satisfies_policy(path, root_idx) &&
                     (forall |i| 0 <= i < stack.len() ==>
                         !is_prefix_of(#[trigger] stack@[i]@, path))
                     ==>
-                    !query.path_satisfies_policy(path, root_idx),
+                    spec_contains_path(res@, path, root_idx),
         {
             let ghost prev_stack = stack@;
+            let ghost prev_truncated = truncated;
+            let ghost prev_res = res@;
 
             if let Some(cur_path) = stack.pop() {
+                if num_paths >= limits.max_paths {
+                    return Err(ValidationError::ResourceLimitExceeded);
+                }
+
+                if budget.deadline_passed() {
+                    return Err(ValidationError::ResourceLimitExceeded);
+                }
+
+                num_paths = num_paths + 1;
+
                 let last = cur_path[cur_path.len() - 1];
 
-                if self.check_simple_path(&cache, &cur_path)? {
-                    return Ok(true);
+                report.explore(&cur_path);
+
+                if stop_at_first {
+                    if let Some(root_idx) = self.check_simple_path(&cache, &cur_path)? {
+                        let ghost path = cur_path@;
+                        res.push((cur_path, root_idx));
+                        assert(res@[0].0@ == path && res@[0].1 == root_idx);
+                        return Ok(res);
+                    }
+                } else {
+                    // Collect `cur_path` with every root completing a valid chain
+                    let roots = self.check_simple_path_all(&cache, &cur_path)?;
+
+                    for j in 0..roots.len()
+                        invariant
+                            res@.len() >= prev_res.len(),
+                            forall |k| 0 <= k < prev_res.len() ==> res@[k] == #[trigger] prev_res[k],
+                            forall |k| 0 <= k < res.len() ==> {
+                                &&& query.is_simple_path_to_root((#[trigger] res@[k]).0@, res@[k].1)
+                                &&& query.path_satisfies_policy(res@[k].0@, res@[k].1)
+                            },
+                            forall |k| 0 <= k < j ==> spec_contains_path(res@, cur_path@, #[trigger] roots@[k]),
+                    {
+                        let ghost old_res = res@;
+
+                        res.push((Clone::clone(&cur_path), roots[j]));
+
+                        assert(roots@.contains(roots@[j as int]));
+                        assert(res@[res@.len() - 1].0@ == cur_path@);
+
+                        assert forall |k| 0 <= k < j implies
+                            spec_contains_path(res@, cur_path@, #[trigger] roots@[k])
+                        by {
+                            let m = choose |m: int| 0 <= m < old_res.len() && old_res[m].0@ == cur_path@ && old_res[m].1 == roots@[k];
+                            assert(res@[m] == old_res[m]);
+                        }
+                    }
+
+                    assert forall |root_idx: usize|
+                        #[trigger] query.is_simple_path_to_root(cur_path@, root_idx) &&
+                        query.path_satisfies_policy(cur_path@, root_idx)
+                        implies
+                        spec_contains_path(res@, cur_path@, root_idx)
+                    by {
+                        // By post-condition of check_simple_path_all
+                        assert(roots@.contains(root_idx));
+                        let k = choose |k: int| 0 <= k < roots@.len() && roots@[k] == root_idx;
+                        assert(spec_contains_path(res@, cur_path@, roots@[k]));
+                    }
                 }
 
+                // Only for reporting paths without any issuer
+                let mut has_issuer = cache.root_issuers[last].len() != 0;
+
                 // Push any extension of `path` that is still a simple path
                 for i in 0..bundle_len
                     invariant
@@ -657,9 +2184,12 @@ impl<'a, P: Policy + 'a> Validator<'a, P> {
                         forall |i| 0 <= i < prev_stack.len() - 1 ==>
                             stack@[i] == #[trigger] prev_stack[i],
 
+                        prev_truncated ==> truncated,
+
                         // For any other `path` prefixed by `cur_path` (and longer than it)
                         // either `path` is prefixed by some path in the stack
-                        // or `path`'s next node >= i
+                        // or `path`'s next node >= i (unless some extension is dropped)
+                        !truncated ==>
                         forall |path: Seq<usize>|
                             #[trigger] is_prefix_of(cur_path@, path) &&
                             query.is_simple_path(path) &&
@@ -673,17 +2203,24 @@ impl<'a, P: Policy + 'a> Validator<'a, P> {
                 {
                     let ghost prev_stack = stack@;
 
-                    if !vec_contains(&cur_path, &i) && self.check_interm_likely_issued(&cache, i, last) {
-                        let mut next_path = Clone::clone(&cur_path);
-                        next_path.push(i);
-                        stack.push(next_path);
+                    if !vec_contains(&cur_path, &i) && self.check_interm_likely_issued(&cache, i, last)? {
+                        has_issuer = true;
+
+                        if cur_path.len() < limits.max_depth {
+                            let mut next_path = Clone::clone(&cur_path);
+                            next_path.push(i);
+                            stack.push(next_path);
+                        } else {
+                            truncated = true;
+                        }
                     }
 
                     assert forall |path: Seq<usize>|
                         #[trigger] is_prefix_of(cur_path@, path) &&
                         query.is_simple_path(path) &&
                         path.len() > cur_path@.len() &&
-                        path[cur_path@.len() as int] < i + 1
+                        path[cur_path@.len() as int] < i + 1 &&
+                        !truncated
                         implies
                         exists |j| 0 <= j < stack@.len() && is_prefix_of(#[trigger] stack@[j]@, path)
                     by {
@@ -696,47 +2233,317 @@ impl<'a, P: Policy + 'a> Validator<'a, P> {
                                 // Not a path
                                 assert(path[cur_path@.len() - 1] == i);
                             } else {
-                                // Path was just added
-                                assert(is_prefix_of(stack@[stack@.len() - 1]@, path));
+                                // Path was just added
+                                assert(is_prefix_of(stack@[stack@.len() - 1]@, path));
+                            }
+                        } else {
+                            // By loop invariant
+                            let k = choose |k| 0 <= k < prev_stack.len() && is_prefix_of(#[trigger] prev_stack[k]@, path);
+                            assert(stack@[k] == prev_stack[k]);
+                        }
+                    }
+                }
+
+                report.explored(has_issuer);
+
+                // Check the completeness invariant
+                // For any valid path starting `bundle[leaf]`
+                // that does NOT have any of the stack
+                // elements as prefix, should be collected
+                assert forall |path: Seq<usize>, root_idx: usize|
+                    #[trigger] query.is_simple_path_to_root(path, root_idx) &&
+                    query.path_satisfies_policy(path, root_idx) &&
+                    !truncated &&
+                    (forall |i| 0 <= i < stack.len() ==>
+                        !is_prefix_of(#[trigger] stack@[i]@, path))
+                    implies
+                    spec_contains_path(res@, path, root_idx)
+                by {
+                    if !is_prefix_of(cur_path@, path) {
+                        assert(forall |i| 0 <= i < prev_stack.len()
+                            ==> !is_prefix_of(#[trigger] prev_stack[i]@, path));
+
+                        // Found before, and `res` only grows
+                        let m = choose |m: int| 0 <= m < prev_res.len() && prev_res[m].0@ == path && prev_res[m].1 == root_idx;
+                        assert(res@[m] == prev_res[m]);
+                    } else if path.len() <= cur_path@.len() {
+                        assert(path =~= cur_path@);
+                        // By post-condition of check_simple_path(_all)
+                    } // else by LI of the inner loop
+                }
+            } else {
+                if truncated {
+                    return Err(ValidationError::ResourceLimitExceeded);
+                }
+
+                assert forall |path: Seq<usize>, root_idx: usize|
+                    #[trigger] spec_contains_path(res@, path, root_idx) <==> {
+                        &&& query.is_simple_path_to_root(path, root_idx)
+                        &&& query.path_satisfies_policy(path, root_idx)
+                    }
+                by {
+                    if spec_contains_path(res@, path, root_idx) {
+                        let k = choose |k: int| 0 <= k < res@.len() && res@[k].0@ == path && res@[k].1 == root_idx;
+                        assert(query.is_simple_path_to_root(res@[k].0@, res@[k].1));
+                    }
+                }
+
+                return Ok(res);
+            }
+        }
+    }
+
+    /// Same as [`Validator::validate`], but on certificates
+    /// that are parsed and converted ahead of time, so that
+    /// their abstract representations can be reused across
+    /// validation jobs
+    pub fn validate_parsed(
+        &self,
+        bundle: &Vec<&ParsedCertificate>,
+        task: &policy::ExecTask,
+    ) -> (res: Result<bool, ValidationError>)
+        requires self.wf()
+        ensures
+            // Soundness & completeness (modulo ValidationError)
+            res matches Ok(res) ==> res == (Query {
+                policy: self.policy,
+                roots: self.roots@,
+                anchors: self.roots.anchors(),
+                bundle: bundle@.map_values(|cert: &ParsedCertificate| cert@),
+                leaf: 0,
+                crls: self.crls@,
+                ocsp: seq![],
+                task: task.deep_view(),
+            }).valid(),
+    {
+        self.validate_parsed_bundle(bundle, 0, task)
+    }
+
+    /// Same as [`Validator::validate_parsed`], but the leaf is `bundle[leaf]`,
+    /// and the other certificates can be in any order
+    /// (see also [`Validator::validate_bundle`] and [`Validator::find_leaf`])
+    pub fn validate_parsed_bundle(
+        &self,
+        bundle: &Vec<&ParsedCertificate>,
+        leaf: usize,
+        task: &policy::ExecTask,
+    ) -> (res: Result<bool, ValidationError>)
+        requires self.wf()
+        ensures
+            // Soundness & completeness (modulo ValidationError)
+            res matches Ok(res) ==> res == (Query {
+                policy: self.policy,
+                roots: self.roots@,
+                anchors: self.roots.anchors(),
+                bundle: bundle@.map_values(|cert: &ParsedCertificate| cert@),
+                leaf: leaf,
+                crls: self.crls@,
+                ocsp: seq![],
+                task: task.deep_view(),
+            }).valid(),
+    {
+        match self.validate_parsed_bundle_with_path(bundle, leaf, task)? {
+            Some(..) => Ok(true),
+            None => Ok(false),
+        }
+    }
+
+    /// Same as [`Validator::validate_parsed_bundle`], but additionally returns
+    /// the accepted chain as a list of indices into `bundle` (starting from `leaf`),
+    /// along with the index of the root certificate (see [`Validator::validate_bundle`])
+    pub fn validate_parsed_bundle_with_path(
+        &self,
+        bundle: &Vec<&ParsedCertificate>,
+        leaf: usize,
+        task: &policy::ExecTask,
+    ) -> (res: Result<Option<(Vec<usize>, usize)>, ValidationError>)
+        requires self.wf()
+        ensures
+            // Soundness: the returned path is a valid chain
+            res matches Ok(Some((path, root_idx))) ==> {
+                let query = Query {
+                    policy: self.policy,
+                    roots: self.roots@,
+                    anchors: self.roots.anchors(),
+                    bundle: bundle@.map_values(|cert: &ParsedCertificate| cert@),
+                    leaf: leaf,
+                    crls: self.crls@,
+                    ocsp: seq![],
+                    task: task.deep_view(),
+                };
+                &&& query.is_simple_path_to_root(path@, root_idx)
+                &&& query.path_satisfies_policy(path@, root_idx)
+            },
+
+            // Completeness (modulo ValidationError)
+            res matches Ok(None) ==> !(Query {
+                policy: self.policy,
+                roots: self.roots@,
+                anchors: self.roots.anchors(),
+                bundle: bundle@.map_values(|cert: &ParsedCertificate| cert@),
+                leaf: leaf,
+                crls: self.crls@,
+                ocsp: seq![],
+                task: task.deep_view(),
+            }).valid(),
+    {
+        let bundle_len = bundle.len();
+        let mut certs = VecDeep::with_capacity(bundle_len);
+        let mut bundle_abs_cache = Vec::with_capacity(bundle_len);
+
+        for i in 0..bundle_len
+            invariant
+                bundle_len == bundle@.len(),
+                certs@.len() == i,
+                bundle_abs_cache@.len() == i,
+                forall |j| 0 <= j < i ==> #[trigger] certs@[j] == bundle@[j]@,
+                forall |j| 0 <= j < i ==>
+                    Some(#[trigger] bundle_abs_cache.deep_view()[j]) == policy::Certificate::spec_from(certs@[j]),
+        {
+            let ghost old_cache = bundle_abs_cache.deep_view();
+
+            certs.push(PolyfillClone::clone(bundle[i].cert()));
+            bundle_abs_cache.push(bundle[i].abs());
+
+            assert forall |j| 0 <= j < i + 1 implies
+                Some(#[trigger] bundle_abs_cache.deep_view()[j]) == policy::Certificate::spec_from(certs@[j])
+            by {
+                if j < i {
+                    assert(bundle_abs_cache.deep_view()[j] == old_cache[j]);
+                }
+            }
+        }
+
+        assert(certs@ =~= bundle@.map_values(|cert: &ParsedCertificate| cert@));
+
+        let ocsp: VecDeep<OptionDeep<OCSPResponseValue<'_>>> = VecDeep::new();
+        assert(ocsp@ =~= seq![]);
+
+        self.validate_cached(&certs, leaf, bundle_abs_cache, &ocsp, task, &ReportSink::none())
+    }
+
+    /// Same as [`Validator::validate_parsed_bundle_with_path`], but also uses the
+    /// certificates in `cached` (e.g., intermediates seen in previous
+    /// validations) as candidate intermediates, so the query is over
+    /// `bundle + cached` (and the returned path indexes into `bundle + cached`)
+    pub fn validate_parsed_with_cache(
+        &self,
+        bundle: &Vec<&ParsedCertificate>,
+        cached: &Vec<&ParsedCertificate>,
+        leaf: usize,
+        task: &policy::ExecTask,
+    ) -> (res: Result<Option<(Vec<usize>, usize)>, ValidationError>)
+        requires self.wf()
+        ensures
+            // Soundness: the returned path is a valid chain
+            res matches Ok(Some((path, root_idx))) ==> {
+                let query = Query {
+                    policy: self.policy,
+                    roots: self.roots@,
+                    anchors: self.roots.anchors(),
+                    bundle: (bundle@ + cached@).map_values(|cert: &ParsedCertificate| cert@),
+                    leaf: leaf,
+                    crls: self.crls@,
+                    ocsp: seq![],
+                    task: task.deep_view(),
+                };
+                &&& query.is_simple_path_to_root(path@, root_idx)
+                &&& query.path_satisfies_policy(path@, root_idx)
+            },
+
+            // Completeness (modulo ValidationError)
+            res matches Ok(None) ==> !(Query {
+                policy: self.policy,
+                roots: self.roots@,
+                anchors: self.roots.anchors(),
+                bundle: (bundle@ + cached@).map_values(|cert: &ParsedCertificate| cert@),
+                leaf: leaf,
+                crls: self.crls@,
+                ocsp: seq![],
+                task: task.deep_view(),
+            }).valid(),
+    {
+        let mut all: Vec<&ParsedCertificate> = Vec::new();
+
+        for i in 0..bundle.len()
+            invariant all@ =~= bundle@.take(i as int),
+        {
+            all.push(bundle[i]);
+            assert(bundle@.take(i + 1) =~= bundle@.take(i as int).push(bundle@[i as int]));
+        }
+
+        assert(bundle@.take(bundle@.len() as int) =~= bundle@);
+
+        for i in 0..cached.len()
+            invariant all@ =~= bundle@ + cached@.take(i as int),
+        {
+            all.push(cached[i]);
+            assert(cached@.take(i + 1) =~= cached@.take(i as int).push(cached@[i as int]));
+        }
+
+        assert(cached@.take(cached@.len() as int) =~= cached@);
+        assert(all@ =~= bundle@ + cached@);
+
+        self.validate_parsed_bundle_with_path(&all, leaf, task)
+    }
+
+    /// Finds the leaf in an unordered bundle, i.e., the only
+    /// certificate that is not a CA and is issued for the peer
+    /// in `task` (see [`policy::is_leaf_candidate`]).
+    /// Returns `None` if there is no such certificate or more than one.
+    pub fn find_leaf(bundle: &Vec<&ParsedCertificate>, task: &policy::ExecTask) -> (res: Option<usize>)
+        ensures
+            res matches Some(leaf) ==>
+                spec_is_unique_leaf(bundle@.map_values(|cert: &ParsedCertificate| cert@), task.deep_view(), leaf),
+            res is None ==>
+                !exists |leaf: usize| spec_is_unique_leaf(bundle@.map_values(|cert: &ParsedCertificate| cert@), task.deep_view(), leaf),
+    {
+        let ghost certs = bundle@.map_values(|cert: &ParsedCertificate| cert@);
+        let ghost is_candidate = |i: int|
+            policy::is_leaf_candidate(&task.deep_view(), &policy::Certificate::spec_from(certs[i]).unwrap());
+
+        let bundle_len = bundle.len();
+        let mut found: Option<usize> = None;
+
+        for i in 0..bundle_len
+            invariant
+                bundle_len == bundle@.len(),
+                found matches Some(leaf) ==> leaf < i && is_candidate(leaf as int),
+                forall |j| 0 <= j < i && is_candidate(j) ==> found == Some(j as usize),
+        {
+            assert(certs[i as int] == bundle@[i as int]@);
+
+            if policy::exec_is_leaf_candidate(task, bundle[i].abs()) {
+                if let Some(prev) = found {
+                    // At least two candidates: `bundle[prev]` and `bundle[i]`
+                    assert forall |leaf: usize| !spec_is_unique_leaf(certs, task.deep_view(), leaf) by {
+                        if 0 <= leaf < certs.len() {
+                            if leaf == i {
+                                assert(is_candidate(prev as int));
+                            } else {
+                                assert(is_candidate(i as int));
                             }
-                        } else {
-                            // By loop invariant
-                            let k = choose |k| 0 <= k < prev_stack.len() && is_prefix_of(#[trigger] prev_stack[k]@, path);
-                            assert(stack@[k] == prev_stack[k]);
                         }
                     }
+                    return None;
                 }
 
-                // Check the completeness invariant
-                // For any path starting `bundle[0]`
-                // that does NOT have any of the stack
-                // elements as prefix, should not have
-                // a simple valid path to a root
-                assert forall |path: Seq<usize>, root_idx: usize|
-                    #[trigger] query.is_simple_path_to_root(path, root_idx) &&
-                    (forall |i| 0 <= i < stack.len() ==>
-                        !is_prefix_of(#[trigger] stack@[i]@, path))
-                    implies
-                    // No valid simple path to root
-                    !query.path_satisfies_policy(path, root_idx)
-                by {
-                    if !is_prefix_of(cur_path@, path) {
-                        assert(forall |i| 0 <= i < prev_stack.len()
-                            ==> !is_prefix_of(#[trigger] prev_stack[i]@, path));
-                    } else {
-                        if path.len() <= cur_path@.len() {
-                            assert(path =~= cur_path@);
-                            // By post-condition of check_simple_path
-                        } // else by LI of the inner loop
+                found = Some(i);
+            }
+        }
+
+        match found {
+            Some(leaf) => {
+                assert(is_candidate(leaf as int));
+                Some(leaf)
+            }
+            None => {
+                assert forall |leaf: usize| !spec_is_unique_leaf(certs, task.deep_view(), leaf) by {
+                    if 0 <= leaf < certs.len() {
+                        assert(!is_candidate(leaf as int));
                     }
                 }
-
-            } else {
-                // assert(forall |path: Seq<usize>, root_idx: usize|
-                //     #[trigger] query.is_simple_path_to_root(path, root_idx) ==>
-                //     !query.path_satisfies_policy(path, root_idx));
-                // assert(!query.valid());
-                return Ok(false);
+                None
             }
         }
     }
@@ -755,7 +2562,11 @@ impl<'a, P: Policy + 'a> Validator<'a, P> {
                 res == (Query {
                     policy: self.policy,
                     roots: self.roots@,
+                    anchors: self.roots.anchors(),
                     bundle: bundle@.map_values(|der: Vec<u8>| spec_parse_x509_der(der@).unwrap()),
+                    leaf: 0,
+                    crls: self.crls@,
+                    ocsp: seq![],
                     task: task.deep_view(),
                 }).valid(),
     {
@@ -789,7 +2600,11 @@ impl<'a, P: Policy + 'a> Validator<'a, P> {
                 res == (Query {
                     policy: self.policy,
                     roots: self.roots@,
+                    anchors: self.roots.anchors(),
                     bundle: bundle@.map_values(|base64: Vec<u8>| spec_parse_x509_base64(base64@).unwrap()),
+                    leaf: 0,
+                    crls: self.crls@,
+                    ocsp: seq![],
                     task: task.deep_view(),
                 }).valid(),
     {
@@ -798,20 +2613,559 @@ impl<'a, P: Policy + 'a> Validator<'a, P> {
 
         for i in 0..bundle_len
             invariant
-                bundle_len == bundle@.len(),
-                bundle_der@.len() == i,
-                forall |j| 0 <= j < i ==> spec_decode_base64(bundle@[j]@) == Some(#[trigger] bundle_der@[j]@),
+                bundle_len == bundle@.len(),
+                bundle_der@.len() == i,
+                forall |j| 0 <= j < i ==> spec_decode_base64(bundle@[j]@) == Some(#[trigger] bundle_der@[j]@),
+        {
+            bundle_der.push(decode_base64(bundle[i].as_slice())?);
+        }
+
+        assert(
+            bundle_der@.map_values(|der: Vec<u8>| spec_parse_x509_der(der@).unwrap())
+            =~=
+            bundle@.map_values(|base64: Vec<u8>| spec_parse_x509_base64(base64@).unwrap())
+        );
+
+        self.validate_der(&bundle_der, task)
+    }
+}
+
+/// A certificate parsed from ASN.1 DER, along with its
+/// abstract representation (including the SHA-256 fingerprint),
+/// so that both can be reused across validation jobs
+///
+/// NOTE: the parsed [`CertificateValue`] borrows from the heap allocation
+/// of `der`, which is never mutated or dropped before it (see
+/// [`parse_x509_der_owned`]), so the certificate is parsed only once
+pub struct ParsedCertificate {
+    cert: CertificateValue<'static>,
+    der: Vec<u8>,
+    abs: policy::ExecCertificate,
+}
+
+impl View for ParsedCertificate {
+    type V = SpecCertificateValue;
+
+    closed spec fn view(&self) -> Self::V {
+        spec_parse_x509_der(self.der@).unwrap()
+    }
+}
+
+impl ParsedCertificate {
+    #[verifier::type_invariant]
+    closed spec fn inv(self) -> bool {
+        &&& spec_parse_x509_der(self.der@) == Some(self.cert@)
+        &&& Some(self.abs.deep_view()) == policy::Certificate::spec_from(self@)
+    }
+
+    /// Parses and converts a certificate in ASN.1 DER format
+    pub fn from_der(der: Vec<u8>) -> (res: Result<ParsedCertificate, ValidationError>)
+        ensures
+            res matches Ok(res) ==> spec_parse_x509_der(der@) == Some(res@),
+    {
+        let cert = parse_x509_der_owned(&der)?;
+        let abs = policy::Certificate::from(&cert)?;
+        Ok(ParsedCertificate { cert, der, abs })
+    }
+
+    /// The original DER encoding
+    pub fn der(&self) -> (res: &[u8])
+        ensures spec_parse_x509_der(res@) == Some(self@),
+    {
+        proof {
+            use_type_invariant(self);
+        }
+        self.der.as_slice()
+    }
+
+    /// Cached abstract representation of the certificate
+    pub fn abs(&self) -> (res: &policy::ExecCertificate)
+        ensures Some(res.deep_view()) == policy::Certificate::spec_from(self@),
+    {
+        proof {
+            use_type_invariant(self);
+        }
+        &self.abs
+    }
+
+    /// The certificate parsed at construction
+    pub fn cert(&self) -> (res: &CertificateValue<'_>)
+        ensures res@ == self@,
+    {
+        proof {
+            use_type_invariant(self);
+        }
+        shorten_cert_lifetime(&self.cert)
+    }
+}
+
+/// Parses `der` into a [`CertificateValue`] borrowing its heap allocation
+///
+/// NOTE: this is a trusted wrapper that erases the lifetime of the borrow,
+/// which is only used to store the result next to `der` in a [`ParsedCertificate`]
+#[verifier::external_body]
+#[allow(unsafe_code)]
+fn parse_x509_der_owned(der: &Vec<u8>) -> (res: Result<CertificateValue<'static>, ParseError>)
+    ensures
+        res matches Ok(res) ==> spec_parse_x509_der(der@) == Some(res@),
+{
+    // SAFETY: moving the `Vec` into the `ParsedCertificate` does not move
+    // its heap allocation, which lives (unchanged) as long as the result
+    let der: &'static [u8] = unsafe { std::slice::from_raw_parts(der.as_ptr(), der.len()) };
+    parse_x509_der(der)
+}
+
+/// Bounds the lifetime of a certificate stored in a [`ParsedCertificate`]
+/// by that of the [`ParsedCertificate`] itself
+///
+/// NOTE: this is a trusted wrapper, since [`CertificateValue`] is
+/// not covariant in its lifetime (so the coercion has to be explicit)
+#[verifier::external_body]
+#[allow(unsafe_code)]
+fn shorten_cert_lifetime<'a>(cert: &'a CertificateValue<'static>) -> (res: &'a CertificateValue<'a>)
+    ensures res@ == cert@,
+{
+    // SAFETY: the certificate is only read through a shared reference,
+    // and the DER it borrows outlives `'a` (see `parse_x509_der_owned`)
+    unsafe { std::mem::transmute::<&'a CertificateValue<'static>, &'a CertificateValue<'a>>(cert) }
+}
+
+/// Parsed root certificates along with some caches for validation,
+/// which can be shared by multiple validators
+pub struct Roots {
+    certs: Vec<Arc<ParsedCertificate>>,
+
+    /// Constraints from the root program on each root certificate
+    anchors: Vec<Option<policy::ExecTrustAnchor>>,
+
+    /// Abstract representation of each root certificate
+    /// with `trust_anchor` set to the corresponding anchor
+    anchored_cache: Vec<policy::ExecCertificate>,
+
+    /// Cached RSA public keys of each root certificate
+    rsa_cache: Vec<Option<rsa::RSAPublicKeyInternal>>,
+
+    /// Public key of each root certificate (see [`IssuerKey`]), so that
+    /// signatures, CRLs and OCSP responses issued by roots can be
+    /// checked without extracting the public keys again
+    key_cache: Vec<(KeyAlg, Vec<u8>)>,
+
+    /// DER encoding of the subject name of each root certificate,
+    /// for matching issuerNameHash in OCSP responses
+    name_cache: Vec<Vec<u8>>,
+
+    /// Indices of root certificates by the (normalized) subject name
+    index: RootIndex,
+}
+
+/// A map from keys to indices of root certificates
+/// NOTE: this is a trusted wrapper around `HashMap`
+#[verifier::external_body]
+struct RootIndex(std::collections::HashMap<u64, Vec<usize>>);
+
+impl RootIndex {
+    /// Indices associated with `key`
+    uninterp spec fn spec_get(&self, key: u64) -> Seq<usize>;
+
+    #[verifier::external_body]
+    fn new() -> (res: Self)
+        ensures forall |key| #[trigger] res.spec_get(key) == Seq::<usize>::empty(),
+    {
+        RootIndex(std::collections::HashMap::new())
+    }
+
+    #[verifier::external_body]
+    fn insert(&mut self, key: u64, idx: usize)
+        ensures
+            self.spec_get(key) == old(self).spec_get(key).push(idx),
+            forall |other| other != key ==> #[trigger] self.spec_get(other) == old(self).spec_get(other),
+    {
+        self.0.entry(key).or_default().push(idx);
+    }
+
+    #[verifier::external_body]
+    fn get(&self, key: u64) -> (res: &[usize])
+        ensures res@ == self.spec_get(key),
+    {
+        self.0.get(&key).map(|indices| indices.as_slice()).unwrap_or(&[])
+    }
+}
+
+impl View for Roots {
+    type V = Seq<SpecCertificateValue>;
+
+    closed spec fn view(&self) -> Self::V {
+        self.certs@.map_values(|cert: Arc<ParsedCertificate>| cert@)
+    }
+}
+
+impl Roots {
+    #[verifier::type_invariant]
+    closed spec fn inv(self) -> bool {
+        &&& self.rsa_cache@.len() == self.certs@.len()
+        &&& forall |i| 0 <= i < self.certs@.len() ==>
+            (#[trigger] self.rsa_cache@[i] matches Some(key) ==> Self::spec_is_rsa_key(self.certs@[i]@, key))
+
+        &&& self.key_cache@.len() == self.certs@.len()
+        &&& forall |i| 0 <= i < self.certs@.len() ==>
+            Self::spec_is_issuer_key(self.certs@[i]@, #[trigger] self.key_cache@[i])
+
+        &&& self.name_cache@.len() == self.certs@.len()
+        &&& forall |i| 0 <= i < self.certs@.len() ==>
+            Self::spec_is_subject_name(self.certs@[i]@, #[trigger] self.name_cache@[i])
+
+        &&& self.anchors@.len() == self.certs@.len()
+        &&& self.anchored_cache@.len() == self.certs@.len()
+        &&& forall |i| 0 <= i < self.certs@.len() ==>
+            #[trigger] self.anchored_cache.deep_view()[i] ==
+                Self::spec_anchored(self.certs@[i]@, self.anchors.deep_view()[i])
+
+        &&& forall |i: usize| 0 <= i < self.certs@.len() ==>
+            self.index.spec_get(Self::spec_subject_key(#[trigger] self.certs@[i as int]@)).contains(i)
+        &&& forall |key: u64, j: int| 0 <= j < self.index.spec_get(key).len() ==>
+            #[trigger] self.index.spec_get(key)[j] < self.certs@.len()
+    }
+
+    /// Key of the subject name of a root certificate in the index
+    pub open spec fn spec_subject_key(cert: SpecCertificateValue) -> u64 {
+        policy::name_key(&policy::Certificate::spec_from(cert).unwrap().subject)
+    }
+
+    /// Trust anchor of each root certificate
+    pub closed spec fn anchors(&self) -> Seq<Option<policy::TrustAnchor>> {
+        self.anchors.deep_view()
+    }
+
+    /// Abstract representation of a root certificate with the given trust anchor
+    pub open spec fn spec_anchored(cert: SpecCertificateValue, anchor: Option<policy::TrustAnchor>) -> policy::Certificate {
+        policy::Certificate {
+            trust_anchor: anchor,
+            ..policy::Certificate::spec_from(cert).unwrap()
+        }
+    }
+
+    /// `key` is the public key of `cert` (see [`spec_issuer_key`])
+    pub open spec fn spec_is_issuer_key(cert: SpecCertificateValue, key: (KeyAlg, Vec<u8>)) -> bool {
+        spec_issuer_key(cert) == (key.0, key.1@)
+    }
+
+    /// `name` is the DER encoding of the subject name of `cert`
+    pub open spec fn spec_is_subject_name(cert: SpecCertificateValue, name: Vec<u8>) -> bool {
+        ASN1(Name)@.spec_serialize(cert.cert.subject) matches Ok(ser) && ser == name@
+    }
+
+    /// `key` is the RSA public key of `cert`
+    pub open spec fn spec_is_rsa_key(cert: SpecCertificateValue, key: rsa::RSAPublicKeyInternal) -> bool {
+        let subject_key = cert.cert.subject_key;
+
+        &&& subject_key.alg.param is RSAEncryption
+        &&& rsa::spec_pkcs1_v1_5_load_pub_key(BitStringValue::spec_bytes(subject_key.pub_key)) == Some(key)
+    }
+
+    /// Prepares the given root certificates (with optional
+    /// trust anchors, see [`policy::ExecTrustAnchor`]) for validation
+    #[verifier::loop_isolation(false)]
+    pub fn new(
+        certs: Vec<Arc<ParsedCertificate>>,
+        anchors: Vec<Option<policy::ExecTrustAnchor>>,
+    ) -> (res: Result<Roots, ValidationError>)
+        requires certs@.len() == anchors@.len(),
+        ensures
+            res matches Ok(res) ==> {
+                &&& res@ =~= certs@.map_values(|cert: Arc<ParsedCertificate>| cert@)
+                &&& res.anchors() == anchors.deep_view()
+            },
+    {
+        let len = certs.len();
+        let mut rsa_cache = Vec::with_capacity(len);
+        let mut key_cache = Vec::with_capacity(len);
+        let mut name_cache = Vec::with_capacity(len);
+        let mut anchored_cache = Vec::with_capacity(len);
+        let mut index = RootIndex::new();
+
+        // Initialize the key caches by extracting the public key
+        // (and parsing the RSA public key) of each root certificate,
+        // attach the trust anchors to the abstract representations,
+        // and index the roots by their subject names
+        for i in 0..len
+            invariant
+                len == certs@.len(),
+                len == anchors@.len(),
+                i == rsa_cache@.len(),
+                i == key_cache@.len(),
+                i == name_cache@.len(),
+                i == anchored_cache@.len(),
+                forall |j| 0 <= j < rsa_cache@.len() ==>
+                    (#[trigger] rsa_cache@[j] matches Some(key) ==> Self::spec_is_rsa_key(certs@[j]@, key)),
+                forall |j| 0 <= j < i ==>
+                    Self::spec_is_issuer_key(certs@[j]@, #[trigger] key_cache@[j]),
+                forall |j| 0 <= j < i ==>
+                    Self::spec_is_subject_name(certs@[j]@, #[trigger] name_cache@[j]),
+                forall |j| 0 <= j < i ==>
+                    #[trigger] anchored_cache.deep_view()[j] ==
+                        Self::spec_anchored(certs@[j]@, anchors.deep_view()[j]),
+                forall |j: usize| 0 <= j < i ==>
+                    index.spec_get(Self::spec_subject_key(#[trigger] certs@[j as int]@)).contains(j),
+                forall |key: u64, k: int| 0 <= k < index.spec_get(key).len() ==>
+                    #[trigger] index.spec_get(key)[k] < i,
+        {
+            let root = arc_as_ref(&certs[i]).cert();
+
+            rsa_cache.push(if let AlgorithmParamValue::RSAEncryption(..) = &root.get().cert.get().subject_key.alg.param {
+                let pub_key = root.get().cert.get().subject_key.pub_key.bytes();
+
+                match rsa::pkcs1_v1_5_load_pub_key(pub_key) {
+                    Ok(pub_key) => Some(pub_key),
+
+                    // NOTE: skip if the pub key of a root certificate fail to parse
+                    Err(..) => None,
+                }
+            } else {
+                None
+            });
+
+            let key = IssuerKey::from_cert(root);
+            key_cache.push((key.alg, slice_to_vec(key.pub_key)));
+            assert(key_cache@[i as int].1@ =~= key.pub_key@);
+
+            name_cache.push(slice_to_vec(root.get().cert.get().subject.serialize()));
+            assert(Self::spec_is_subject_name(certs@[i as int]@, name_cache@[i as int]));
+
+            let ghost old_cache = anchored_cache.deep_view();
+            let mut anchored = policy::Certificate::from(root)?;
+
+            let ghost old_index = index;
+            let key = policy::exec_name_key(&anchored.subject);
+            index.insert(key, i);
+
+            assert(key == Self::spec_subject_key(certs@[i as int]@));
+            assert(index.spec_get(key)[index.spec_get(key).len() - 1] == i);
+
+            assert forall |j: usize| 0 <= j < i implies
+                index.spec_get(Self::spec_subject_key(#[trigger] certs@[j as int]@)).contains(j)
+            by {
+                let other = Self::spec_subject_key(certs@[j as int]@);
+                if other == key {
+                    let m = choose |m: int| 0 <= m < old_index.spec_get(key).len() && old_index.spec_get(key)[m] == j;
+                    assert(index.spec_get(key)[m] == j);
+                }
+            }
+
+            assert forall |other: u64, k: int| 0 <= k < index.spec_get(other).len() implies
+                #[trigger] index.spec_get(other)[k] < i + 1
+            by {
+                if other != key || k < old_index.spec_get(key).len() {
+                    assert(index.spec_get(other)[k] == old_index.spec_get(other)[k]);
+                }
+            }
+
+            anchored.trust_anchor = match &anchors[i] {
+                Some(anchor) => Some(anchor.clone()),
+                None => None,
+            };
+
+            anchored_cache.push(anchored);
+
+            assert forall |j| 0 <= j < i + 1 implies
+                #[trigger] anchored_cache.deep_view()[j] ==
+                    Self::spec_anchored(certs@[j]@, anchors.deep_view()[j])
+            by {
+                if j < i {
+                    assert(anchored_cache.deep_view()[j] == old_cache[j]);
+                }
+            }
+        }
+
+        Ok(Roots { certs, anchors, anchored_cache, rsa_cache, key_cache, name_cache, index })
+    }
+
+    /// Parses root certificates in ASN.1 DER format (without trust anchors)
+    pub fn from_der(roots_der: &Vec<Vec<u8>>) -> (res: Result<Roots, ValidationError>)
+        ensures
+            res matches Ok(res) ==> {
+                &&& res@ =~= roots_der@.map_values(|der: Vec<u8>| spec_parse_x509_der(der@).unwrap())
+                &&& res.anchors() =~= Seq::new(roots_der@.len(), |_i: int| None::<policy::TrustAnchor>)
+            },
+    {
+        let len = roots_der.len();
+        let mut certs = Vec::with_capacity(len);
+        let mut anchors = Vec::with_capacity(len);
+
+        for i in 0..len
+            invariant
+                len == roots_der@.len(),
+                i == certs@.len(),
+                i == anchors@.len(),
+                forall |j| 0 <= j < i ==>
+                    spec_parse_x509_der(roots_der@[j]@) == Some(#[trigger] certs@[j]@),
+                forall |j| 0 <= j < i ==> #[trigger] anchors@[j] is None,
+        {
+            let der = slice_to_vec(roots_der[i].as_slice());
+            assert(der@ =~= roots_der@[i as int]@);
+
+            certs.push(Arc::new(ParsedCertificate::from_der(der)?));
+            anchors.push(None);
+        }
+
+        assert(anchors.deep_view() =~= Seq::new(len as nat, |_i: int| None::<policy::TrustAnchor>));
+
+        Self::new(certs, anchors)
+    }
+
+    pub fn len(&self) -> (res: usize)
+        ensures res == self@.len(),
+    {
+        self.certs.len()
+    }
+
+    /// The `i`-th root certificate
+    pub fn get(&self, i: usize) -> (res: &CertificateValue<'_>)
+        requires 0 <= i < self@.len(),
+        ensures res@ == self@[i as int],
+    {
+        arc_as_ref(&self.certs[i]).cert()
+    }
+
+    /// Abstract representation of the `i`-th root certificate
+    pub fn abs(&self, i: usize) -> (res: &policy::ExecCertificate)
+        requires 0 <= i < self@.len(),
+        ensures Some(res.deep_view()) == policy::Certificate::spec_from(self@[i as int]),
+    {
+        arc_as_ref(&self.certs[i]).abs()
+    }
+
+    /// Same as [`Roots::abs`], but with `trust_anchor` set,
+    /// which is passed to the policy as the root of a candidate chain
+    pub fn anchored(&self, i: usize) -> (res: &policy::ExecCertificate)
+        requires 0 <= i < self@.len(),
+        ensures res.deep_view() == Self::spec_anchored(self@[i as int], self.anchors()[i as int]),
+    {
+        proof {
+            use_type_invariant(self);
+        }
+        &self.anchored_cache[i]
+    }
+
+    /// Indices of root certificates whose subject may be the same as
+    /// `name` after normalization, with possible false positives
+    pub fn candidates(&self, name: &policy::ExecDistinguishedName) -> (res: &[usize])
+        ensures
+            forall |i| 0 <= i < res@.len() ==> #[trigger] res@[i] < self@.len(),
+            forall |i: usize| 0 <= i < self@.len() &&
+                policy::same_dn(&policy::Certificate::spec_from(self@[i as int]).unwrap().subject, &name.deep_view(), true)
+                ==> #[trigger] res@.contains(i),
+    {
+        let res = self.index.get(policy::exec_name_key(name));
+
+        proof {
+            use_type_invariant(self);
+
+            let key = policy::name_key(&name.deep_view());
+
+            assert forall |i| 0 <= i < res@.len() implies #[trigger] res@[i] < self@.len() by {
+                assert(res@[i] == self.index.spec_get(key)[i]);
+            }
+
+            assert forall |i: usize| 0 <= i < self@.len() &&
+                policy::same_dn(&policy::Certificate::spec_from(self@[i as int]).unwrap().subject, &name.deep_view(), true)
+                implies #[trigger] res@.contains(i)
+            by {
+                assert(self@[i as int] == self.certs@[i as int]@);
+                policy::lemma_same_dn_name_key(
+                    &policy::Certificate::spec_from(self@[i as int]).unwrap().subject,
+                    &name.deep_view(),
+                );
+            }
+        }
+
+        res
+    }
+
+    /// Trust anchor of the `i`-th root certificate, if any
+    pub fn anchor(&self, i: usize) -> (res: &Option<policy::ExecTrustAnchor>)
+        requires 0 <= i < self@.len(),
+        ensures res.deep_view() == self.anchors()[i as int],
+    {
+        proof {
+            use_type_invariant(self);
+        }
+        &self.anchors[i]
+    }
+
+    /// Public key of the `i`-th root certificate
+    pub fn key(&self, i: usize) -> (res: IssuerKey<'_>)
+        requires 0 <= i < self@.len(),
+        ensures res@ == spec_issuer_key(self@[i as int]),
+    {
+        proof {
+            use_type_invariant(self);
+        }
+        let (alg, pub_key) = &self.key_cache[i];
+        IssuerKey { alg: *alg, pub_key: pub_key.as_slice() }
+    }
+
+    /// DER encoding of the subject name of the `i`-th root certificate
+    pub fn subject_name(&self, i: usize) -> (res: &[u8])
+        requires 0 <= i < self@.len(),
+        ensures ASN1(Name)@.spec_serialize(self@[i as int].cert.subject) matches Ok(name) && name == res@,
+    {
+        proof {
+            use_type_invariant(self);
+        }
+        self.name_cache[i].as_slice()
+    }
+
+    /// Cached RSA public key of the `i`-th root certificate, if any
+    pub fn rsa_key(&self, i: usize) -> (res: &Option<rsa::RSAPublicKeyInternal>)
+        requires 0 <= i < self@.len(),
+        ensures *res matches Some(key) ==> Self::spec_is_rsa_key(self@[i as int], key),
+    {
+        proof {
+            use_type_invariant(self);
+        }
+        &self.rsa_cache[i]
+    }
+
+    /// All root certificates
+    pub fn certs(&self) -> (res: &Vec<Arc<ParsedCertificate>>)
+        ensures res@.map_values(|cert: Arc<ParsedCertificate>| cert@) == self@,
+    {
+        &self.certs
+    }
+}
+
+/// A collection of CRLs (RFC 5280, Section 5)
+pub struct RevocationStore {
+    pub crls_der: Vec<Vec<u8>>,
+}
+
+impl RevocationStore {
+    /// Creates a [`RevocationStore`] from a list of CRLs in Base64-encoded ASN.1 DER format.
+    pub fn from_base64(crls_base64: &Vec<Vec<u8>>) -> (res: Result<RevocationStore, ParseError>)
+        ensures
+            res matches Ok(res) ==> {
+                &&& res.crls_der@.len() == crls_base64.len()
+                &&& forall |i| 0 <= i < crls_base64@.len() ==>
+                        spec_decode_base64(#[trigger] crls_base64@[i]@) == Some(res.crls_der@[i]@)
+            },
+            res is Err ==>
+                exists |i| 0 <= i < crls_base64.len() &&
+                    spec_decode_base64(#[trigger] crls_base64@[i]@) is None,
+    {
+        let mut crls_der: Vec<Vec<u8>> = Vec::with_capacity(crls_base64.len());
+        let len = crls_base64.len();
+
+        for i in 0..len
+            invariant
+                len == crls_base64@.len(),
+                crls_der@.len() == i,
+                forall |j| 0 <= j < i ==>
+                    spec_decode_base64(#[trigger] crls_base64@[j]@) == Some(crls_der@[j]@),
         {
-            bundle_der.push(decode_base64(bundle[i].as_slice())?);
+            crls_der.push(decode_base64(crls_base64[i].as_slice())?);
         }
 
-        assert(
-            bundle_der@.map_values(|der: Vec<u8>| spec_parse_x509_der(der@).unwrap())
-            =~=
-            bundle@.map_values(|base64: Vec<u8>| spec_parse_x509_base64(base64@).unwrap())
-        );
-
-        self.validate_der(&bundle_der, task)
+        Ok(RevocationStore { crls_der })
     }
 }
 
@@ -889,7 +3243,8 @@ impl<'a, P: Policy> Validator<'a, P> {
         let mut used_roots = Vec::new();
 
         // Check if root cert issued any of the chain certs
-        for (i, root) in self.roots_abs_cache.iter().enumerate() {
+        for i in 0..self.roots.len() {
+            let root = self.roots.abs(i);
             let mut used = false;
 
             for (j, chain_cert) in chain_abs.iter().enumerate() {
@@ -905,7 +3260,7 @@ impl<'a, P: Policy> Validator<'a, P> {
         }
 
         let print_cert = |cert: &x509::CertificateValue<'_>| {
-            eprintln!("  subject: {}", cert.get().cert.get().subject);
+            eprintln!("  subject: {}", cert.get().cert.get().subject.get());
             eprintln!("  issued by: {}", cert.get().cert.get().issuer);
             eprintln!("  signed with: {:?}", cert.get().sig_alg);
             eprintln!("  subject key: {:?}", cert.get().cert.get().subject_key.alg);
@@ -937,7 +3292,7 @@ impl<'a, P: Policy> Validator<'a, P> {
 
         for i in used_roots.iter() {
             eprintln!("abstract root cert {}:", i);
-            eprintln!("  {:?}", &self.roots_abs_cache[*i]);
+            eprintln!("  {:?}", self.roots.anchored(*i));
         }
 
         eprintln!("=================== end task info ===================");
@@ -946,9 +3301,51 @@ impl<'a, P: Policy> Validator<'a, P> {
     }
 }
 
+impl<'r> ReportSink<'r> {
+    /// A sink that records rejected paths, explained by `diagnose`
+    fn new(diagnose: &'r DiagnoseFn<'r>) -> Self {
+        ReportSink(Some(std::cell::RefCell::new(ReportRecorder::new(diagnose))))
+    }
+
+    /// Takes the recorded report along with the final verdict
+    fn finish(self, valid: bool) -> ValidationReport {
+        match self.0 {
+            Some(recorder) => recorder.into_inner().finish(valid),
+            None => ValidationReport {
+                valid,
+                ..ValidationReport::default()
+            },
+        }
+    }
+}
+
+impl<'a, P: Policy + DiagnosePolicy> Validator<'a, P> {
+    /// Same as [`Validator::validate`], but also records why each
+    /// candidate path explored during path building is rejected.
+    /// NOTE: only the verdict in [`ValidationReport::valid`] is verified.
+    pub fn validate_with_report(
+        &self,
+        bundle: &VecDeep<CertificateValue<'_>>,
+        task: &ExecTask,
+    ) -> Result<ValidationReport, ValidationError> {
+        let diagnose = |chain: &Vec<&policy::ExecCertificate>, task: &ExecTask| {
+            self.policy.diagnose_chain(chain, task)
+        };
+        let report = ReportSink::new(&diagnose);
+
+        let valid = self
+            .validate_bundle_with_sink(bundle, 0, &VecDeep::new(), task, &report)?
+            .is_some();
+
+        Ok(report.finish(valid))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use policy::{ChromePolicy, ExecPurpose, FirefoxPolicy, OpenSSLPolicy};
+    use policy::{
+        ChromePolicy, ExecPeerName, ExecPurpose, ExecTimeCheck, FirefoxPolicy, OpenSSLPolicy,
+    };
 
     use super::*;
 
@@ -1015,9 +3412,13 @@ mod tests {
                     &chain_base64,
                     $policy,
                     &ExecTask {
-                        hostname: Some(hostname.to_string()),
+                        peer: Some(ExecPeerName::Dns(hostname.to_string())),
                         purpose: ExecPurpose::ServerAuth,
                         now: *now,
+                        time_check: ExecTimeCheck::Strict,
+                        check_revocation: false,
+                        revoked: vec![],
+                        ocsp: vec![],
                     },
                 );
 
@@ -1041,4 +3442,1070 @@ mod tests {
     fn test_well_known_sites_openssl() {
         test_policy!(OpenSSLPolicy::default());
     }
+
+    /// Checks that `diagnose_chain` explains exactly the candidate chains
+    /// rejected by `valid_chain`, since its checks are copied by hand.
+    /// Candidates are the prefixes of each chain in `TESTS` completed by
+    /// any likely root issuer, under tasks that accept or reject them.
+    macro_rules! test_diagnose {
+        ($policy:expr) => {
+            let policy = $policy;
+            let roots_base64 = pem_to_base64(include_str!("../tests/roots.pem"));
+            let store = RootStore::from_base64(&roots_base64).unwrap();
+            let roots = Roots::from_der(&store.roots_der).unwrap();
+
+            let (mut num_valid, mut num_invalid) = (0, 0);
+
+            for (pem, hostname, now, _) in TESTS {
+                let bundle = pem_to_base64(pem)
+                    .iter()
+                    .map(|base64| {
+                        let der = decode_base64(base64).unwrap();
+                        policy::Certificate::from(&parse_x509_der(&der).unwrap()).unwrap()
+                    })
+                    .collect::<Vec<_>>();
+
+                let make_task = |peer: Option<&str>, purpose, now| ExecTask {
+                    peer: peer.map(|name| ExecPeerName::Dns(name.to_string())),
+                    purpose,
+                    now,
+                    time_check: ExecTimeCheck::Strict,
+                    check_revocation: false,
+                    revoked: vec![],
+                    ocsp: vec![],
+                };
+                let tasks = [
+                    make_task(Some(*hostname), ExecPurpose::ServerAuth, *now),
+                    make_task(None, ExecPurpose::ServerAuth, *now),
+                    make_task(Some("example.com"), ExecPurpose::ServerAuth, *now),
+                    make_task(Some(*hostname), ExecPurpose::ServerAuth, 1000000000),
+                    make_task(Some(*hostname), ExecPurpose::ServerAuth, 1900000000),
+                    make_task(None, ExecPurpose::ClientAuth, *now),
+                    make_task(None, ExecPurpose::CodeSigning, *now),
+                ];
+
+                for len in 1..=bundle.len() {
+                    for root_idx in 0..roots.len() {
+                        if !policy.likely_issued(roots.abs(root_idx), &bundle[len - 1]) {
+                            continue;
+                        }
+
+                        let mut chain = bundle[..len].iter().collect::<Vec<_>>();
+                        chain.push(roots.anchored(root_idx));
+
+                        for task in &tasks {
+                            let valid = policy.valid_chain(&chain, task);
+                            assert_eq!(
+                                policy.diagnose_chain(&chain, task).is_none(),
+                                valid,
+                                "{} (len {}, root {}) under {:?}",
+                                hostname,
+                                len,
+                                root_idx,
+                                task,
+                            );
+
+                            if valid {
+                                num_valid += 1;
+                            } else {
+                                num_invalid += 1;
+                            }
+                        }
+                    }
+                }
+            }
+
+            assert!(num_valid > 0 && num_invalid > 0);
+        };
+    }
+
+    #[test]
+    fn test_diagnose_chrome() {
+        test_diagnose!(ChromePolicy::default());
+    }
+
+    #[test]
+    fn test_diagnose_firefox() {
+        test_diagnose!(FirefoxPolicy::default());
+    }
+
+    #[test]
+    fn test_diagnose_openssl() {
+        test_diagnose!(OpenSSLPolicy::default());
+    }
+
+    #[test]
+    fn test_well_known_sites_with_path() {
+        let roots_base64 = pem_to_base64(include_str!("../tests/roots.pem"));
+        let store = RootStore::from_base64(&roots_base64).unwrap();
+        let validator = Validator::from_root_store(ChromePolicy::default(), &store).unwrap();
+
+        for (pem, hostname, now, expected) in TESTS {
+            let chain_der = pem_to_base64(pem)
+                .iter()
+                .map(|base64| decode_base64(base64).unwrap())
+                .collect::<Vec<_>>();
+            let chain = VecDeep::from_vec(
+                chain_der
+                    .iter()
+                    .map(|der| parse_x509_der(der).unwrap())
+                    .collect(),
+            );
+
+            let res = validator
+                .validate_with_path(
+                    &chain,
+                    &ExecTask {
+                        peer: Some(ExecPeerName::Dns(hostname.to_string())),
+                        purpose: ExecPurpose::ServerAuth,
+                        now: *now,
+                        time_check: ExecTimeCheck::Strict,
+                        check_revocation: false,
+                        revoked: vec![],
+                        ocsp: vec![],
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(res.is_some(), *expected);
+
+            if let Some((path, root_idx)) = res {
+                assert_eq!(path[0], 0);
+                assert!(path.iter().all(|i| *i < chain.len()));
+                assert!(root_idx < validator.roots.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_all() {
+        let roots_base64 = pem_to_base64(include_str!("../tests/roots.pem"));
+        let store = RootStore::from_base64(&roots_base64).unwrap();
+        let validator = Validator::from_root_store(ChromePolicy::default(), &store).unwrap();
+
+        for (pem, hostname, now, expected) in TESTS {
+            let chain_der = pem_to_base64(pem)
+                .iter()
+                .map(|base64| decode_base64(base64).unwrap())
+                .collect::<Vec<_>>();
+            let chain = VecDeep::from_vec(
+                chain_der
+                    .iter()
+                    .map(|der| parse_x509_der(der).unwrap())
+                    .collect(),
+            );
+            let task = ExecTask {
+                peer: Some(ExecPeerName::Dns(hostname.to_string())),
+                purpose: ExecPurpose::ServerAuth,
+                now: *now,
+                time_check: ExecTimeCheck::Strict,
+                check_revocation: false,
+                revoked: vec![],
+                ocsp: vec![],
+            };
+
+            let first = validator.validate_with_path(&chain, &task).unwrap();
+            let all = validator.validate_all(&chain, &task).unwrap();
+
+            assert_eq!(!all.is_empty(), *expected);
+
+            // The first valid path is among all valid paths, without duplicates
+            if let Some(first) = first {
+                assert!(all.contains(&first));
+            }
+            for (i, (path, root_idx)) in all.iter().enumerate() {
+                assert_eq!(path[0], 0);
+                assert!(*root_idx < validator.roots.len());
+                assert!(!all[..i].contains(&(path.clone(), *root_idx)));
+            }
+        }
+
+        // google.pem includes GTS Root R1 cross-signed by GlobalSign Root CA,
+        // so besides the path to GTS Root R1, the only other candidate is through
+        // the cross-signed certificate (both roots are in roots.pem)
+        let chain_der = pem_to_base64(include_str!("../tests/chains/google.pem"))
+            .iter()
+            .map(|base64| decode_base64(base64).unwrap())
+            .collect::<Vec<_>>();
+        let chain = VecDeep::from_vec(chain_der.iter().map(|der| parse_x509_der(der).unwrap()).collect());
+        let task = ExecTask {
+            peer: Some(ExecPeerName::Dns("google.com".to_string())),
+            purpose: ExecPurpose::ServerAuth,
+            now: 1725029869,
+            time_check: ExecTimeCheck::Strict,
+            check_revocation: false,
+            revoked: vec![],
+            ocsp: vec![],
+        };
+
+        let all = validator.validate_all(&chain, &task).unwrap();
+        assert!(all.iter().any(|(path, _)| path == &vec![0, 1]));
+        assert!(all.iter().all(|(path, _)| path == &vec![0, 1] || path == &vec![0, 1, 2]));
+
+        let wrong_host = ExecTask { peer: Some(ExecPeerName::Dns("example.com".to_string())), ..task };
+        assert!(validator.validate_all(&chain, &wrong_host).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_well_known_sites_parsed() {
+        let roots_base64 = pem_to_base64(include_str!("../tests/roots.pem"));
+        let store = RootStore::from_base64(&roots_base64).unwrap();
+        let validator = Validator::from_root_store(ChromePolicy::default(), &store).unwrap();
+
+        for (pem, hostname, now, expected) in TESTS {
+            let chain = pem_to_base64(pem)
+                .iter()
+                .map(|base64| ParsedCertificate::from_der(decode_base64(base64).unwrap()).unwrap())
+                .collect::<Vec<_>>();
+            let chain = chain.iter().collect::<Vec<_>>();
+
+            // The same parsed certificates can be reused across tasks
+            for (peer, expected) in [(*hostname, *expected), ("example.com", false)] {
+                let task = ExecTask {
+                    peer: Some(ExecPeerName::Dns(peer.to_string())),
+                    purpose: ExecPurpose::ServerAuth,
+                    now: *now,
+                    time_check: ExecTimeCheck::Strict,
+                    check_revocation: false,
+                    revoked: vec![],
+                    ocsp: vec![],
+                };
+
+                assert_eq!(validator.validate_parsed(&chain, &task).unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unordered_bundle() {
+        let roots_base64 = pem_to_base64(include_str!("../tests/roots.pem"));
+        let store = RootStore::from_base64(&roots_base64).unwrap();
+        let validator = Validator::from_root_store(ChromePolicy::default(), &store).unwrap();
+
+        let server_auth = |hostname: &str, now: u64| ExecTask {
+            peer: Some(ExecPeerName::Dns(hostname.to_string())),
+            purpose: ExecPurpose::ServerAuth,
+            now,
+            time_check: ExecTimeCheck::Strict,
+            check_revocation: false,
+            revoked: vec![],
+            ocsp: vec![],
+        };
+
+        for (pem, hostname, now, expected) in TESTS {
+            // Put the leaf last
+            let chain = pem_to_base64(pem)
+                .iter()
+                .rev()
+                .map(|base64| ParsedCertificate::from_der(decode_base64(base64).unwrap()).unwrap())
+                .collect::<Vec<_>>();
+            let chain = chain.iter().collect::<Vec<_>>();
+            let task = server_auth(hostname, *now);
+
+            assert_eq!(validator.validate_parsed_bundle(&chain, chain.len() - 1, &task).unwrap(), *expected);
+            assert!(matches!(
+                validator.validate_parsed_bundle(&chain, chain.len(), &task),
+                Err(ValidationError::LeafNotFound),
+            ));
+        }
+
+        let chain = pem_to_base64(include_str!("../tests/chains/google.pem"))
+            .iter()
+            .rev()
+            .map(|base64| ParsedCertificate::from_der(decode_base64(base64).unwrap()).unwrap())
+            .collect::<Vec<_>>();
+        let chain = chain.iter().collect::<Vec<_>>();
+
+        // The intermediates are CAs, so the leaf is the only candidate
+        let task = server_auth("google.com", 1725029869);
+        assert_eq!(Validator::<ChromePolicy>::find_leaf(&chain, &task), Some(chain.len() - 1));
+
+        // No certificate is issued for the hostname
+        let task = server_auth("example.com", 1725029869);
+        assert_eq!(Validator::<ChromePolicy>::find_leaf(&chain, &task), None);
+
+        // Two copies of the leaf are ambiguous
+        let mut dup = chain.clone();
+        dup.push(chain[chain.len() - 1]);
+        let task = server_auth("google.com", 1725029869);
+        assert_eq!(Validator::<ChromePolicy>::find_leaf(&dup, &task), None);
+    }
+
+    #[test]
+    fn test_set_roots() {
+        let roots_base64 = pem_to_base64(include_str!("../tests/roots.pem"));
+        let store = RootStore::from_base64(&roots_base64).unwrap();
+        let roots = Arc::new(Roots::from_der(&store.roots_der).unwrap());
+
+        // Two validators can share the same parsed roots
+        let mut chrome = Validator::from_roots(ChromePolicy::default(), roots.clone());
+        let firefox = Validator::from_roots(FirefoxPolicy::default(), roots.clone());
+
+        let chain_der = pem_to_base64(include_str!("../tests/chains/google.pem"))
+            .iter()
+            .map(|base64| decode_base64(base64).unwrap())
+            .collect::<Vec<_>>();
+        let chain = VecDeep::from_vec(
+            chain_der
+                .iter()
+                .map(|der| parse_x509_der(der).unwrap())
+                .collect(),
+        );
+        let task = ExecTask {
+            peer: Some(ExecPeerName::Dns("google.com".to_string())),
+            purpose: ExecPurpose::ServerAuth,
+            now: 1725029869,
+            time_check: ExecTimeCheck::Strict,
+            check_revocation: false,
+            revoked: vec![],
+            ocsp: vec![],
+        };
+
+        assert!(chrome.validate(&chain, &task).unwrap());
+        assert!(firefox.validate(&chain, &task).unwrap());
+
+        chrome.set_roots(Arc::new(Roots::new(vec![], vec![]).unwrap()));
+        assert!(!chrome.validate(&chain, &task).unwrap());
+        assert!(firefox.validate(&chain, &task).unwrap());
+
+        chrome.set_roots(roots);
+        assert!(chrome.validate(&chain, &task).unwrap());
+    }
+
+    #[test]
+    fn test_root_index() {
+        let roots_base64 = pem_to_base64(include_str!("../tests/roots.pem"));
+        let store = RootStore::from_base64(&roots_base64).unwrap();
+        let roots = Roots::from_der(&store.roots_der).unwrap();
+
+        for i in 0..roots.len() {
+            assert!(roots.candidates(&roots.abs(i).subject).contains(&i));
+        }
+
+        // Every root that likely issued a certificate is a candidate
+        let chrome = ChromePolicy::default();
+        let firefox = FirefoxPolicy::default();
+
+        for (pem, ..) in TESTS {
+            for base64 in pem_to_base64(pem) {
+                let der = decode_base64(&base64).unwrap();
+                let cert = policy::Certificate::from(&parse_x509_der(&der).unwrap()).unwrap();
+                let candidates = roots.candidates(&cert.issuer);
+
+                assert!(candidates.len() < roots.len());
+
+                for i in 0..roots.len() {
+                    if chrome.likely_issued(roots.abs(i), &cert) || firefox.likely_issued(roots.abs(i), &cert) {
+                        assert!(candidates.contains(&i));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_resource_limits() {
+        let roots_base64 = pem_to_base64(include_str!("../tests/roots.pem"));
+        let store = RootStore::from_base64(&roots_base64).unwrap();
+        let mut validator = Validator::from_root_store(ChromePolicy::default(), &store).unwrap();
+
+        let chain_der = pem_to_base64(include_str!("../tests/chains/google.pem"))
+            .iter()
+            .map(|base64| decode_base64(base64).unwrap())
+            .collect::<Vec<_>>();
+        let chain = VecDeep::from_vec(
+            chain_der
+                .iter()
+                .map(|der| parse_x509_der(der).unwrap())
+                .collect(),
+        );
+        let task = ExecTask {
+            peer: Some(ExecPeerName::Dns("google.com".to_string())),
+            purpose: ExecPurpose::ServerAuth,
+            now: 1725029869,
+            time_check: ExecTimeCheck::Strict,
+            check_revocation: false,
+            revoked: vec![],
+            ocsp: vec![],
+        };
+
+        let limited = |limits: ValidationLimits| {
+            let mut validator = Validator::from_root_store(ChromePolicy::default(), &store).unwrap();
+            validator.set_limits(limits);
+            validator.validate(&chain, &task)
+        };
+        let exceeded = |res: Result<bool, ValidationError>| {
+            matches!(res, Err(ValidationError::ResourceLimitExceeded))
+        };
+
+        // The valid path is leaf -> WR2 -> GTS Root R1 (in the root store),
+        // found after checking the root issuers of WR2 and of the cross-signed
+        // GTS Root R1 (GlobalSign Root CA), and then WR2 against the leaf
+        assert!(validator.validate(&chain, &task).unwrap());
+        assert!(limited(ValidationLimits { max_depth: 2, ..ValidationLimits::default() }).unwrap());
+        assert!(limited(ValidationLimits { max_signatures: 3, ..ValidationLimits::default() }).unwrap());
+        assert!(limited(ValidationLimits { max_paths: 2, ..ValidationLimits::default() }).unwrap());
+
+        assert!(exceeded(limited(ValidationLimits { max_depth: 1, ..ValidationLimits::default() })));
+        assert!(exceeded(limited(ValidationLimits { max_signatures: 2, ..ValidationLimits::default() })));
+        assert!(exceeded(limited(ValidationLimits { max_signatures: 0, ..ValidationLimits::default() })));
+        assert!(exceeded(limited(ValidationLimits { max_paths: 1, ..ValidationLimits::default() })));
+        assert!(exceeded(limited(ValidationLimits { time_budget_ms: Some(0), ..ValidationLimits::default() })));
+
+        // Limits do not turn an invalid chain into an error if the search completes
+        validator.set_limits(ValidationLimits { max_depth: 3, ..ValidationLimits::default() });
+        let wrong_host = ExecTask { peer: Some(ExecPeerName::Dns("example.com".to_string())), ..task.clone() };
+        assert!(!validator.validate(&chain, &wrong_host).unwrap());
+    }
+
+    #[test]
+    fn test_trust_anchor() {
+        let roots_base64 = pem_to_base64(include_str!("../tests/roots.pem"));
+        let store = RootStore::from_base64(&roots_base64).unwrap();
+        let certs = Roots::from_der(&store.roots_der).unwrap().certs().clone();
+
+        let chain_der = pem_to_base64(include_str!("../tests/chains/google.pem"))
+            .iter()
+            .map(|base64| decode_base64(base64).unwrap())
+            .collect::<Vec<_>>();
+        let chain = VecDeep::from_vec(
+            chain_der
+                .iter()
+                .map(|der| parse_x509_der(der).unwrap())
+                .collect(),
+        );
+        let task = ExecTask {
+            peer: Some(ExecPeerName::Dns("google.com".to_string())),
+            purpose: ExecPurpose::ServerAuth,
+            now: 1725029869,
+            time_check: ExecTimeCheck::Strict,
+            check_revocation: false,
+            revoked: vec![],
+            ocsp: vec![],
+        };
+
+        // Same trust anchor for every root
+        let anchor_all = |server_auth: bool,
+                          server_distrust_after: Option<u64>,
+                          server_distrust_exceptions: &[&str],
+                          permitted_dns_names: Option<&[&str]>| {
+            let anchors = certs
+                .iter()
+                .map(|_| {
+                    Some(policy::ExecTrustAnchor {
+                        server_auth,
+                        client_auth: server_auth,
+                        code_signing: false,
+                        email_protection: true,
+                        permitted_dns_names: permitted_dns_names
+                            .map(|names| names.iter().map(|name| name.to_string()).collect()),
+                        server_distrust_after,
+                        email_distrust_after: None,
+                        server_distrust_exceptions: server_distrust_exceptions
+                            .iter()
+                            .map(|fp| fp.to_string())
+                            .collect(),
+                    })
+                })
+                .collect();
+            Arc::new(Roots::new(certs.clone(), anchors).unwrap())
+        };
+
+        // The chain goes through WR2, which is issued by the root
+        let wr2 = policy::Certificate::from(&parse_x509_der(&chain_der[1]).unwrap()).unwrap().fingerprint;
+        let leaf = policy::Certificate::from(&parse_x509_der(&chain_der[0]).unwrap()).unwrap().fingerprint;
+
+        for (roots, expected) in [
+            (anchor_all(true, None, &[], None), true),
+            (anchor_all(false, None, &[], None), false),
+            // Leaf issued after the distrust date
+            (anchor_all(true, Some(1600000000), &[], None), false),
+            (anchor_all(true, Some(1800000000), &[], None), true),
+            // Distrusted except for chains through an exempted CA
+            (anchor_all(true, Some(0), &[&wr2], None), true),
+            (anchor_all(true, Some(0), &[&leaf], None), false),
+            (anchor_all(true, None, &[], Some(&["*.fr"])), false),
+        ] {
+            let chrome = Validator::from_roots(ChromePolicy::default(), roots.clone());
+            let firefox = Validator::from_roots(FirefoxPolicy::default(), roots);
+            assert_eq!(chrome.validate(&chain, &task).unwrap(), expected);
+            assert_eq!(firefox.validate(&chain, &task).unwrap(), expected);
+        }
+
+        // Chrome checks the domain restriction against the hostname,
+        // while Firefox checks it against every DNS name in the leaf SAN
+        let roots = anchor_all(true, None, &[], Some(&["*.fr", "google.com"]));
+        let chrome = Validator::from_roots(ChromePolicy::default(), roots.clone());
+        let firefox = Validator::from_roots(FirefoxPolicy::default(), roots);
+        assert!(chrome.validate(&chain, &task).unwrap());
+        assert!(!firefox.validate(&chain, &task).unwrap());
+    }
+
+    #[test]
+    fn test_time_check() {
+        let roots_base64 = pem_to_base64(include_str!("../tests/roots.pem"));
+        let chain_base64 = pem_to_base64(include_str!("../tests/chains/google.pem"));
+
+        fn validate<P: Policy>(
+            roots_base64: &Vec<Vec<u8>>,
+            chain_base64: &Vec<Vec<u8>>,
+            policy: P,
+            now: u64,
+            time_check: ExecTimeCheck,
+        ) -> bool {
+            let task = ExecTask {
+                peer: Some(ExecPeerName::Dns("google.com".to_string())),
+                purpose: ExecPurpose::ServerAuth,
+                now,
+                time_check,
+                check_revocation: false,
+                revoked: vec![],
+                ocsp: vec![],
+            };
+            validate_x509_base64(roots_base64, chain_base64, policy, &task).unwrap()
+        }
+
+        // The leaf is valid in [1722839846, 1730097445]
+        let expired = 1730097445 + 100;
+
+        for (now, time_check, expected) in [
+            (1725029869, ExecTimeCheck::Strict, true),
+            (expired, ExecTimeCheck::Strict, false),
+            (expired, ExecTimeCheck::Skew(60), false),
+            (expired, ExecTimeCheck::Skew(3600), true),
+            (0, ExecTimeCheck::Strict, false),
+            (0, ExecTimeCheck::AsOfIssuance, true),
+            (expired, ExecTimeCheck::Skip, true),
+        ] {
+            for res in [
+                validate(&roots_base64, &chain_base64, ChromePolicy::default(), now, time_check),
+                validate(&roots_base64, &chain_base64, FirefoxPolicy::default(), now, time_check),
+                validate(&roots_base64, &chain_base64, OpenSSLPolicy::default(), now, time_check),
+            ] {
+                assert_eq!(res, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_policy() {
+        use policy::{BasePolicy, CustomPolicy, NameMatching, Rules};
+
+        let roots_base64 = pem_to_base64(include_str!("../tests/roots.pem"));
+        let chain_base64 = pem_to_base64(include_str!("../tests/chains/google.pem"));
+
+        let validate_task = |rules: Rules, task: &ExecTask| {
+            let policy = CustomPolicy {
+                base: BasePolicy::Empty,
+                rules,
+            };
+            validate_x509_base64(&roots_base64, &chain_base64, policy, task).unwrap()
+        };
+        let task = |hostname: &str, now: u64| ExecTask {
+            peer: Some(ExecPeerName::Dns(hostname.to_string())),
+            purpose: ExecPurpose::ServerAuth,
+            now,
+            time_check: ExecTimeCheck::Strict,
+            check_revocation: false,
+            revoked: vec![],
+            ocsp: vec![],
+        };
+        let validate = |rules: Rules, hostname: &str, now: u64| validate_task(rules, &task(hostname, now));
+
+        let with_rules = |f: fn(&mut Rules)| {
+            let mut rules = Rules::none();
+            f(&mut rules);
+            rules
+        };
+
+        // The empty base accepts any chain issued by a root
+        assert!(validate(Rules::none(), "example.com", 1800000000));
+
+        // Unless the minimal rules are enabled
+        assert!(validate(Rules::minimal(), "mail.google.com", 1725029869));
+        assert!(!validate(Rules::minimal(), "example.com", 1725029869));
+        assert!(!validate(Rules::minimal(), "google.com", 1800000000));
+
+        // The leaf is only for server authentication
+        let client_auth = ExecTask { purpose: ExecPurpose::ClientAuth, ..task("google.com", 1725029869) };
+        assert!(validate_task(Rules::none(), &client_auth));
+        assert!(!validate_task(Rules::minimal(), &client_auth));
+
+        // The leaf is revoked by WR2
+        let abs = |base64: &Vec<u8>| {
+            let der = decode_base64(base64).unwrap();
+            policy::Certificate::from(&parse_x509_der(&der).unwrap()).unwrap()
+        };
+        let (leaf, wr2) = (abs(&chain_base64[0]), abs(&chain_base64[1]));
+        let revoked = ExecTask {
+            check_revocation: true,
+            revoked: vec![policy::ExecRevokedSerial {
+                issuer_fingerprint: wr2.fingerprint,
+                serial: leaf.serial,
+            }],
+            ..task("google.com", 1725029869)
+        };
+        assert!(validate_task(Rules::none(), &revoked));
+        assert!(!validate_task(Rules::minimal(), &revoked));
+
+        // The leaf expired in Oct, 2024
+        let no_expiration = |rules: &mut Rules| rules.no_expiration = true;
+        assert!(validate(with_rules(no_expiration), "google.com", 1725029869));
+        assert!(!validate(with_rules(no_expiration), "google.com", 1800000000));
+
+        let strict = |rules: &mut Rules| {
+            rules.non_leaf_must_be_ca = true;
+            rules.leaf_has_eku = true;
+            rules.no_dsa = true;
+            rules.allowed_curves = true;
+        };
+        assert!(validate(with_rules(strict), "google.com", 1725029869));
+
+        // The leaf has "*.google.com" but not "mail.google.com" in SAN
+        let exact = |rules: &mut Rules| rules.name_matching = NameMatching::Exact;
+        let wildcard = |rules: &mut Rules| rules.name_matching = NameMatching::Wildcard;
+        assert!(validate(with_rules(exact), "google.com", 1725029869));
+        assert!(!validate(with_rules(exact), "mail.google.com", 1725029869));
+        assert!(validate(with_rules(wildcard), "mail.google.com", 1725029869));
+        assert!(!validate(with_rules(wildcard), "example.com", 1725029869));
+
+        // All certificates in the chain are signed with RSA + SHA256
+        let rsa_sha256 = |rules: &mut Rules| {
+            rules.allowed_sig_algs = Some(vec!["1.2.840.113549.1.1.11".to_string()])
+        };
+        let ecdsa_sha256 = |rules: &mut Rules| {
+            rules.allowed_sig_algs = Some(vec!["1.2.840.10045.4.3.2".to_string()])
+        };
+        assert!(validate(with_rules(rsa_sha256), "google.com", 1725029869));
+        assert!(!validate(with_rules(ecdsa_sha256), "google.com", 1725029869));
+    }
+
+    #[test]
+    fn test_report_wrong_hostname() {
+        let roots_base64 = pem_to_base64(include_str!("../tests/roots.pem"));
+        let store = RootStore::from_base64(&roots_base64).unwrap();
+        let validator = Validator::from_root_store(ChromePolicy::default(), &store).unwrap();
+
+        let chain_der = pem_to_base64(include_str!("../tests/chains/google.pem"))
+            .iter()
+            .map(|base64| decode_base64(base64).unwrap())
+            .collect::<Vec<_>>();
+        let chain = VecDeep::from_vec(
+            chain_der
+                .iter()
+                .map(|der| parse_x509_der(der).unwrap())
+                .collect(),
+        );
+
+        let report = validator
+            .validate_with_report(
+                &chain,
+                &ExecTask {
+                    peer: Some(ExecPeerName::Dns("example.com".to_string())),
+                    purpose: ExecPurpose::ServerAuth,
+                    now: 1725029869,
+                    time_check: ExecTimeCheck::Strict,
+                    check_revocation: false,
+                    revoked: vec![],
+                    ocsp: vec![],
+                },
+            )
+            .unwrap();
+
+        assert!(!report.valid);
+        assert!(report.rejected.iter().any(|path| {
+            matches!(
+                path.failure,
+                PathFailure::Policy {
+                    predicate: "match_peer_name",
+                    ..
+                }
+            ) && path.cert == Some(CertRef::Bundle(0))
+                && path.depth == Some(0)
+        }));
+    }
+
+    #[test]
+    fn test_purpose_mismatch() {
+        let roots_base64 = pem_to_base64(include_str!("../tests/roots.pem"));
+        let chain_base64 = pem_to_base64(include_str!("../tests/chains/google.pem"));
+
+        // The leaf only has the serverAuth EKU
+        for purpose in [ExecPurpose::CodeSigning, ExecPurpose::ClientAuth] {
+            let task = ExecTask {
+                peer: None,
+                purpose,
+                now: 1725029869,
+                time_check: ExecTimeCheck::Strict,
+                check_revocation: false,
+                revoked: vec![],
+                ocsp: vec![],
+            };
+
+            assert!(!validate_x509_base64(
+                &roots_base64,
+                &chain_base64,
+                ChromePolicy::default(),
+                &task
+            )
+            .unwrap());
+            assert!(!validate_x509_base64(
+                &roots_base64,
+                &chain_base64,
+                FirefoxPolicy::default(),
+                &task
+            )
+            .unwrap());
+            assert!(!validate_x509_base64(
+                &roots_base64,
+                &chain_base64,
+                OpenSSLPolicy::default(),
+                &task
+            )
+            .unwrap());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let roots_base64 = pem_to_base64(include_str!("../tests/roots.pem"));
+        let chain_base64 = pem_to_base64(include_str!("../tests/chains/google.pem"));
+
+        let leaf_der = decode_base64(&chain_base64[0]).unwrap();
+        let leaf = policy::Certificate::from(&parse_x509_der(&leaf_der).unwrap()).unwrap();
+
+        let json = serde_json::to_string(&leaf).unwrap();
+        let leaf_de: policy::ExecCertificate = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&leaf_de).unwrap(), json);
+        assert_eq!(leaf_de.fingerprint, leaf.fingerprint);
+
+        let task = ExecTask {
+            peer: Some(ExecPeerName::Dns("google.com".to_string())),
+            purpose: ExecPurpose::ServerAuth,
+            now: 1725029869,
+            time_check: ExecTimeCheck::Skew(60),
+            check_revocation: false,
+            revoked: vec![],
+            ocsp: vec![],
+        };
+
+        let json = serde_json::to_string(&task).unwrap();
+        let task_de: ExecTask = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&task_de).unwrap(), json);
+
+        // The deserialized task gives the same result
+        assert!(validate_x509_base64(
+            &roots_base64,
+            &chain_base64,
+            ChromePolicy::default(),
+            &task_de,
+        )
+        .unwrap());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_ip_peer_name() {
+        let task = crate::Task::builder(ExecPurpose::ServerAuth)
+            .ip_addr("192.0.2.1".parse().unwrap())
+            .timestamp(1725029869)
+            .build();
+
+        let json = serde_json::to_string(&task).unwrap();
+        let task_de: crate::Task = serde_json::from_str(&json).unwrap();
+        assert_eq!(task_de.peer_name(), task.peer_name());
+
+        // An IP address with neither 4 nor 16 octets is rejected
+        let json = json.replace("[192,0,2,1]", "[192,0,2,1,0]");
+        assert!(serde_json::from_str::<crate::Task>(&json).is_err());
+    }
+
+    #[test]
+    fn test_email_lower() {
+        // Only the domain after the last '@' is case-insensitive
+        assert_eq!(policy::exec_email_lower(&"Admin@Google.COM".to_string()), "Admin@google.com");
+        assert_eq!(policy::exec_email_lower(&"\"A@B\"@Example.com".to_string()), "\"A@B\"@example.com");
+        assert_eq!(policy::exec_email_lower(&"Admin".to_string()), "Admin");
+    }
+
+    #[test]
+    fn test_peer_name_mismatch() {
+        let roots_base64 = pem_to_base64(include_str!("../tests/roots.pem"));
+        let chain_base64 = pem_to_base64(include_str!("../tests/chains/google.pem"));
+
+        // The leaf only has dNSName entries in SAN
+        for peer in [
+            ExecPeerName::Ip(vec![142, 250, 80, 46]),
+            ExecPeerName::Email("admin@google.com".to_string()),
+        ] {
+            let task = ExecTask {
+                peer: Some(peer),
+                purpose: ExecPurpose::ServerAuth,
+                now: 1725029869,
+                time_check: ExecTimeCheck::Strict,
+                check_revocation: false,
+                revoked: vec![],
+                ocsp: vec![],
+            };
+
+            assert!(!validate_x509_base64(
+                &roots_base64,
+                &chain_base64,
+                ChromePolicy::default(),
+                &task
+            )
+            .unwrap());
+            assert!(!validate_x509_base64(
+                &roots_base64,
+                &chain_base64,
+                FirefoxPolicy::default(),
+                &task
+            )
+            .unwrap());
+            assert!(!validate_x509_base64(
+                &roots_base64,
+                &chain_base64,
+                OpenSSLPolicy::default(),
+                &task
+            )
+            .unwrap());
+        }
+    }
+
+    /// Decode the first PEM block (of any label) into DER
+    fn pem_to_der(pem: &str) -> Vec<u8> {
+        let base64 = pem
+            .lines()
+            .skip_while(|line| !line.starts_with("-----BEGIN"))
+            .skip(1)
+            .take_while(|line| !line.starts_with("-----END"))
+            .collect::<String>();
+        decode_base64(base64.as_bytes()).unwrap()
+    }
+
+    /// Validate `leaf` issued directly by the test CRL root,
+    /// with the given CRLs and revocation checking option
+    fn validate_with_crls(leaf: &str, crls: &[&str], check_revocation: bool) -> bool {
+        let store = RootStore {
+            roots_der: vec![pem_to_der(include_str!("../tests/crl/root.pem"))],
+        };
+        let crl_store = RevocationStore {
+            crls_der: crls.iter().map(|crl| pem_to_der(crl)).collect(),
+        };
+
+        let mut validator = Validator::from_root_store(OpenSSLPolicy::default(), &store).unwrap();
+        validator.set_revocation_store(&crl_store).unwrap();
+
+        let leaf_der = pem_to_der(leaf);
+        let chain = VecDeep::from_vec(vec![parse_x509_der(&leaf_der).unwrap()]);
+
+        let task = ExecTask {
+            peer: None,
+            purpose: ExecPurpose::ServerAuth,
+            now: 1795000000,
+            time_check: ExecTimeCheck::Strict,
+            check_revocation,
+            revoked: vec![],
+            ocsp: vec![],
+        };
+
+        let res = validator.validate(&chain, &task).unwrap();
+
+        // The report should carry the same verdict
+        assert_eq!(validator.validate_with_report(&chain, &task).unwrap().valid, res);
+
+        res
+    }
+
+    #[test]
+    fn test_crl_revocation() {
+        let revoked = include_str!("../tests/crl/revoked.pem");
+        let good = include_str!("../tests/crl/good.pem");
+        let crl = include_str!("../tests/crl/crl.pem");
+
+        assert!(!validate_with_crls(revoked, &[crl], true));
+        assert!(validate_with_crls(good, &[crl], true));
+
+        // CRLs are ignored unless revocation checking is enabled
+        assert!(validate_with_crls(revoked, &[crl], false));
+        assert!(validate_with_crls(revoked, &[], true));
+    }
+
+    #[test]
+    fn test_crl_wrong_signer() {
+        // Same issuer name as the root, but signed by a different key
+        let forged_crl = include_str!("../tests/crl/forged_crl.pem");
+
+        assert!(validate_with_crls(
+            include_str!("../tests/crl/revoked.pem"),
+            &[forged_crl],
+            true
+        ));
+    }
+
+    /// Validate `leaf` issued directly by the test CRL root,
+    /// with an optional stapled OCSP response (in DER) for the leaf
+    fn validate_with_ocsp(leaf: &str, ocsp: Option<&[u8]>, now: u64) -> bool {
+        let store = RootStore {
+            roots_der: vec![pem_to_der(include_str!("../tests/crl/root.pem"))],
+        };
+        let validator = Validator::from_root_store(OpenSSLPolicy::default(), &store).unwrap();
+
+        let leaf_der = pem_to_der(leaf);
+        let chain = VecDeep::from_vec(vec![parse_x509_der(&leaf_der).unwrap()]);
+        let ocsp = VecDeep::from_vec(vec![match ocsp {
+            Some(der) => OptionDeep::Some(parse_ocsp_response_der(der).unwrap()),
+            None => OptionDeep::None,
+        }]);
+
+        let task = ExecTask {
+            peer: None,
+            purpose: ExecPurpose::ServerAuth,
+            now,
+            time_check: ExecTimeCheck::Strict,
+            check_revocation: false,
+            revoked: vec![],
+            ocsp: vec![],
+        };
+
+        validator.validate_stapled(&chain, &ocsp, &task).unwrap()
+    }
+
+    #[test]
+    fn test_ocsp_stapled() {
+        let revoked = include_str!("../tests/crl/revoked.pem");
+        let good = include_str!("../tests/crl/good.pem");
+
+        let good_resp: &[u8] = include_bytes!("../tests/ocsp/good.der");
+        let revoked_resp: &[u8] = include_bytes!("../tests/ocsp/revoked.der");
+        let unknown_resp: &[u8] = include_bytes!("../tests/ocsp/unknown.der");
+
+        assert!(validate_with_ocsp(good, Some(good_resp), 1795000000));
+        assert!(!validate_with_ocsp(revoked, Some(revoked_resp), 1795000000));
+        assert!(validate_with_ocsp(revoked, None, 1795000000));
+
+        // Responses about other certificates are ignored
+        assert!(validate_with_ocsp(good, Some(revoked_resp), 1795000000));
+
+        // OpenSSL leaves unknown statuses to the application
+        assert!(validate_with_ocsp(good, Some(unknown_resp), 1795000000));
+
+        // Stale response (after nextUpdate)
+        assert!(!validate_with_ocsp(good, Some(good_resp), 1800000000));
+    }
+
+    #[test]
+    fn test_ocsp_issuer_name_mismatch() {
+        // Signed by the root with a matching issuerKeyHash,
+        // but the issuerNameHash of a different name
+        assert!(validate_with_ocsp(
+            include_str!("../tests/crl/revoked.pem"),
+            Some(&include_bytes!("../tests/ocsp/name_mismatch_revoked.der")[..]),
+            1795000000,
+        ));
+    }
+
+    #[test]
+    fn test_ocsp_delegated_responder() {
+        let revoked = include_str!("../tests/crl/revoked.pem");
+
+        // Signed by a responder certificate with id-kp-OCSPSigning
+        assert!(!validate_with_ocsp(
+            revoked,
+            Some(&include_bytes!("../tests/ocsp/delegated_revoked.der")[..]),
+            1795000000,
+        ));
+
+        // Signed by a certificate from the same issuer,
+        // but without id-kp-OCSPSigning, so the response is ignored
+        assert!(validate_with_ocsp(
+            revoked,
+            Some(&include_bytes!("../tests/ocsp/forged_revoked.der")[..]),
+            1795000000,
+        ));
+    }
+
+    /// Validate `leaf` issued directly by `root`
+    fn validate_with_root(root: &str, leaf: &str) -> bool {
+        let store = RootStore {
+            roots_der: vec![pem_to_der(root)],
+        };
+        let validator = Validator::from_root_store(OpenSSLPolicy::default(), &store).unwrap();
+
+        let leaf_der = pem_to_der(leaf);
+        let chain = VecDeep::from_vec(vec![parse_x509_der(&leaf_der).unwrap()]);
+
+        let task = ExecTask {
+            peer: None,
+            purpose: ExecPurpose::ServerAuth,
+            now: 1795000000,
+            time_check: ExecTimeCheck::Strict,
+            check_revocation: false,
+            revoked: vec![],
+            ocsp: vec![],
+        };
+
+        validator.validate(&chain, &task).unwrap()
+    }
+
+    #[test]
+    fn test_rsa_pss() {
+        let root = include_str!("../tests/pss/root.pem");
+
+        // SHA-256, MGF1 with SHA-256, 32-byte salt
+        assert!(validate_with_root(root, include_str!("../tests/pss/leaf.pem")));
+
+        // Default parameters (SHA-1) are not supported
+        assert!(!validate_with_root(root, include_str!("../tests/pss/leaf_sha1.pem")));
+    }
+
+    #[test]
+    fn test_eddsa() {
+        assert!(validate_with_root(
+            include_str!("../tests/eddsa/root.pem"),
+            include_str!("../tests/eddsa/leaf.pem"),
+        ));
+
+        // Ed448 signatures are not yet supported
+        assert!(!validate_with_root(
+            include_str!("../tests/eddsa/root_ed448.pem"),
+            include_str!("../tests/eddsa/leaf_ed448.pem"),
+        ));
+    }
+
+    #[test]
+    fn test_p521() {
+        let res = validate_with_root(
+            include_str!("../tests/p521/root.pem"),
+            include_str!("../tests/p521/leaf.pem"),
+        );
+
+        // No verified P-521 implementation is available
+        assert_eq!(res, !cfg!(feature = "verified-crypto"));
+    }
+
+    #[test]
+    fn test_subject_key() {
+        let subject_key = |pem: &str| {
+            let der = pem_to_der(pem);
+            let cert = parse_x509_der(&der).unwrap();
+            policy::SubjectKey::from(&cert.get().cert.get().subject_key).unwrap()
+        };
+
+        assert!(matches!(
+            subject_key(include_str!("../tests/p521/root.pem")),
+            policy::ExecSubjectKey::EC { curve, point_len: 133 } if curve == "1.3.132.0.35"
+        ));
+
+        assert!(matches!(
+            subject_key(include_str!("../tests/p521/leaf.pem")),
+            policy::ExecSubjectKey::EC { curve, point_len: 65 } if curve == "1.2.840.10045.3.1.7"
+        ));
+
+        assert!(matches!(
+            subject_key(include_str!("../tests/pss/root.pem")),
+            policy::ExecSubjectKey::RSA { mod_length: 2048, exponent } if exponent == "010001"
+        ));
+    }
 }